@@ -0,0 +1,533 @@
+//! In-memory cache backend
+//!
+//! This module provides an in-process LRU cache with TTL expiration
+//! for tests and single-node services that run without Redis.
+
+use super::CacheBackend;
+use crate::errors::CacheError;
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Stored value of an entry
+#[derive(Debug)]
+enum Stored {
+    Value(String),
+    Tags(HashSet<String>),
+}
+
+#[derive(Debug)]
+struct Entry {
+    stored: Stored,
+    expires_at: Option<Instant>,
+    /// Expiry reported by `ttl`; copies of another backend's values report the source's expiry
+    reported_expires_at: Option<Instant>,
+    /// Position in the LRU order; `None` for counters and tag sets, which are never evicted
+    lru_tick: Option<u64>,
+}
+
+impl Entry {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|at| at <= now)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    entries: HashMap<String, Entry>,
+    /// LRU order of cached values: tick -> key, oldest first
+    lru: BTreeMap<u64, String>,
+    next_tick: u64,
+}
+
+impl Inner {
+    /// Look up a live entry, dropping it if it has expired
+    fn live_entry(&mut self, key: &str, now: Instant) -> Option<&mut Entry> {
+        if self
+            .entries
+            .get(key)
+            .is_some_and(|entry| entry.is_expired(now))
+        {
+            self.remove(key);
+        }
+        self.entries.get_mut(key)
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Entry> {
+        let entry = self.entries.remove(key)?;
+        if let Some(tick) = entry.lru_tick {
+            self.lru.remove(&tick);
+        }
+        Some(entry)
+    }
+
+    /// Mark a cached value as most recently used
+    fn touch(&mut self, key: &str) {
+        let tick = self.next_tick;
+        if let Some(entry) = self.entries.get_mut(key)
+            && let Some(old_tick) = entry.lru_tick
+        {
+            entry.lru_tick = Some(tick);
+            self.lru.remove(&old_tick);
+            self.lru.insert(tick, key.to_string());
+            self.next_tick += 1;
+        }
+    }
+
+    fn insert_value(
+        &mut self,
+        key: &str,
+        value: &str,
+        expires_at: Instant,
+        reported_expires_at: Option<Instant>,
+        capacity: usize,
+    ) {
+        self.remove(key);
+
+        let tick = self.next_tick;
+        self.next_tick += 1;
+        self.entries.insert(
+            key.to_string(),
+            Entry {
+                stored: Stored::Value(value.to_string()),
+                expires_at: Some(expires_at),
+                reported_expires_at,
+                lru_tick: Some(tick),
+            },
+        );
+        self.lru.insert(tick, key.to_string());
+
+        // Evict least recently used values beyond capacity
+        while self.lru.len() > capacity {
+            match self.lru.pop_first() {
+                Some((_, evicted)) => {
+                    self.entries.remove(&evicted);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+/// In-process cache backend with LRU eviction and per-entry TTL
+///
+/// Only cached values count towards the capacity; generation counters and
+/// tag sets are kept until they are removed or expire. Entries are not
+/// shared between processes, so use this backend on a single node only.
+#[derive(Debug, Clone)]
+pub struct MemoryBackend {
+    inner: Arc<Mutex<Inner>>,
+    capacity: usize,
+}
+
+impl MemoryBackend {
+    /// Create a backend holding up to `capacity` cached values
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner::default())),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Number of live cached values
+    pub fn len(&self) -> usize {
+        self.lock().map(|inner| inner.lru.len()).unwrap_or(0)
+    }
+
+    /// Check whether no values are cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop every entry
+    pub fn clear(&self) {
+        if let Ok(mut inner) = self.lock() {
+            *inner = Inner::default();
+        }
+    }
+
+    /// Cache a copy of a value held by another backend
+    ///
+    /// The copy lives for `ttl` seconds, while `ttl()` reports the source's
+    /// remaining `source_ttl` seconds (`None` when the source never expires).
+    pub(crate) fn set_copy(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: u64,
+        source_ttl: Option<u64>,
+    ) -> Result<(), CacheError> {
+        let mut inner = self.lock()?;
+        let now = Instant::now();
+        inner.insert_value(
+            key,
+            value,
+            now + Duration::from_secs(ttl),
+            source_ttl.map(|ttl| now + Duration::from_secs(ttl)),
+            self.capacity,
+        );
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, Inner>, CacheError> {
+        self.inner
+            .lock()
+            .map_err(|_| CacheError::General("memory cache lock poisoned".to_string()))
+    }
+}
+
+#[async_trait]
+impl CacheBackend for MemoryBackend {
+    async fn get(&self, key: &str) -> Result<Option<String>, CacheError> {
+        let mut inner = self.lock()?;
+        let value = match inner.live_entry(key, Instant::now()) {
+            Some(Entry {
+                stored: Stored::Value(value),
+                ..
+            }) => value.clone(),
+            _ => return Ok(None),
+        };
+        inner.touch(key);
+        Ok(Some(value))
+    }
+
+    async fn set_ex(&self, key: &str, value: &str, ttl: u64) -> Result<(), CacheError> {
+        let mut inner = self.lock()?;
+        let expires_at = Instant::now() + Duration::from_secs(ttl);
+        inner.insert_value(key, value, expires_at, Some(expires_at), self.capacity);
+        Ok(())
+    }
+
+    async fn del(&self, keys: &[String]) -> Result<i32, CacheError> {
+        let mut inner = self.lock()?;
+        let now = Instant::now();
+        let mut deleted = 0;
+        for key in keys {
+            if let Some(entry) = inner.remove(key)
+                && !entry.is_expired(now)
+            {
+                deleted += 1;
+            }
+        }
+        Ok(deleted)
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, CacheError> {
+        let mut inner = self.lock()?;
+        Ok(inner.live_entry(key, Instant::now()).is_some())
+    }
+
+    async fn ttl(&self, key: &str) -> Result<i64, CacheError> {
+        let mut inner = self.lock()?;
+        let now = Instant::now();
+        Ok(match inner.live_entry(key, now) {
+            None => -2,
+            Some(Entry {
+                reported_expires_at: None,
+                ..
+            }) => -1,
+            Some(Entry {
+                reported_expires_at: Some(at),
+                ..
+            }) => at.saturating_duration_since(now).as_secs() as i64,
+        })
+    }
+
+    /// Returns every matching key in a single step
+    async fn scan(
+        &self,
+        _cursor: u64,
+        pattern: &str,
+        _count: usize,
+    ) -> Result<(u64, Vec<String>), CacheError> {
+        let inner = self.lock()?;
+        let now = Instant::now();
+        let keys = inner
+            .entries
+            .iter()
+            .filter(|(key, entry)| !entry.is_expired(now) && glob_match(pattern, key))
+            .map(|(key, _)| key.clone())
+            .collect();
+        Ok((0, keys))
+    }
+
+    async fn incr(&self, key: &str) -> Result<u64, CacheError> {
+        let mut inner = self.lock()?;
+        let current = match inner.live_entry(key, Instant::now()) {
+            Some(Entry {
+                stored: Stored::Value(value),
+                ..
+            }) => value
+                .parse::<u64>()
+                .map_err(|_| CacheError::General(format!("value of {} is not an integer", key)))?,
+            Some(_) => {
+                return Err(CacheError::General(format!("{} is not a counter", key)));
+            }
+            None => 0,
+        };
+
+        let next = current + 1;
+        inner.remove(key);
+        inner.entries.insert(
+            key.to_string(),
+            Entry {
+                stored: Stored::Value(next.to_string()),
+                expires_at: None,
+                reported_expires_at: None,
+                lru_tick: None,
+            },
+        );
+        Ok(next)
+    }
+
+    async fn set_ex_tagged(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: u64,
+        tag_key: &str,
+    ) -> Result<(), CacheError> {
+        let mut inner = self.lock()?;
+        let now = Instant::now();
+        let expires_at = now + Duration::from_secs(ttl);
+        inner.insert_value(key, value, expires_at, Some(expires_at), self.capacity);
+
+        let expires_at = Some(expires_at);
+        match inner.live_entry(tag_key, now) {
+            Some(Entry {
+                stored: Stored::Tags(keys),
+                expires_at: tag_expires_at,
+                ..
+            }) => {
+                keys.insert(key.to_string());
+                *tag_expires_at = expires_at;
+            }
+            _ => {
+                inner.remove(tag_key);
+                inner.entries.insert(
+                    tag_key.to_string(),
+                    Entry {
+                        stored: Stored::Tags(HashSet::from([key.to_string()])),
+                        expires_at,
+                        reported_expires_at: expires_at,
+                        lru_tick: None,
+                    },
+                );
+            }
+        }
+        Ok(())
+    }
+
+    async fn take_tagged(&self, tag_key: &str) -> Result<Vec<String>, CacheError> {
+        let mut inner = self.lock()?;
+        let now = Instant::now();
+        match inner.remove(tag_key) {
+            Some(Entry {
+                stored: Stored::Tags(keys),
+                expires_at,
+                ..
+            }) if expires_at.is_none_or(|at| at > now) => Ok(keys.into_iter().collect()),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    async fn set_nx(&self, key: &str, value: &str, ttl: Duration) -> Result<bool, CacheError> {
+        let mut inner = self.lock()?;
+        let now = Instant::now();
+        if inner.live_entry(key, now).is_some() {
+            return Ok(false);
+        }
+
+        // Locks are never evicted; they only expire
+        inner.entries.insert(
+            key.to_string(),
+            Entry {
+                stored: Stored::Value(value.to_string()),
+                expires_at: Some(now + ttl),
+                reported_expires_at: Some(now + ttl),
+                lru_tick: None,
+            },
+        );
+        Ok(true)
+    }
+
+    async fn del_if_eq(&self, key: &str, value: &str) -> Result<bool, CacheError> {
+        let mut inner = self.lock()?;
+        let matches = matches!(
+            inner.live_entry(key, Instant::now()),
+            Some(Entry {
+                stored: Stored::Value(current),
+                ..
+            }) if current == value
+        );
+        if matches {
+            inner.remove(key);
+        }
+        Ok(matches)
+    }
+
+    async fn ping(&self) -> Result<String, CacheError> {
+        Ok("PONG".to_string())
+    }
+}
+
+/// Match a key against a Redis-style glob pattern (`*` and `?`)
+fn glob_match(pattern: &str, key: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let key: Vec<char> = key.chars().collect();
+
+    let (mut p, mut k) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while k < key.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == key[k]) {
+            p += 1;
+            k += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, k));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            k = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_set_and_ttl() {
+        let backend = MemoryBackend::new(10);
+
+        backend
+            .set_ex("app:users:record:1", "alice", 60)
+            .await
+            .unwrap();
+        assert_eq!(
+            backend.get("app:users:record:1").await.unwrap(),
+            Some("alice".to_string())
+        );
+        assert!(backend.exists("app:users:record:1").await.unwrap());
+        assert!((59..=60).contains(&backend.ttl("app:users:record:1").await.unwrap()));
+        assert_eq!(backend.ttl("missing").await.unwrap(), -2);
+
+        backend.set_ex("expired", "gone", 0).await.unwrap();
+        assert_eq!(backend.get("expired").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_copies_report_the_source_ttl() {
+        let backend = MemoryBackend::new(10);
+
+        backend
+            .set_copy("app:users:record:1", "alice", 5, Some(600))
+            .unwrap();
+        assert!((599..=600).contains(&backend.ttl("app:users:record:1").await.unwrap()));
+
+        backend
+            .set_copy("app:users:record:2", "bob", 5, None)
+            .unwrap();
+        assert_eq!(backend.ttl("app:users:record:2").await.unwrap(), -1);
+
+        // The copy itself still expires after its own TTL
+        backend
+            .set_copy("app:users:record:3", "carol", 0, Some(600))
+            .unwrap();
+        assert_eq!(backend.get("app:users:record:3").await.unwrap(), None);
+        assert_eq!(backend.ttl("app:users:record:3").await.unwrap(), -2);
+    }
+
+    #[tokio::test]
+    async fn test_lru_eviction() {
+        let backend = MemoryBackend::new(2);
+
+        backend.set_ex("a", "1", 60).await.unwrap();
+        backend.set_ex("b", "2", 60).await.unwrap();
+        // Reading "a" makes "b" the least recently used value
+        backend.get("a").await.unwrap();
+        backend.set_ex("c", "3", 60).await.unwrap();
+
+        assert_eq!(backend.len(), 2);
+        assert!(backend.exists("a").await.unwrap());
+        assert!(!backend.exists("b").await.unwrap());
+        assert!(backend.exists("c").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_counters_tags_and_scan() {
+        let backend = MemoryBackend::new(1);
+
+        assert_eq!(backend.incr("app:users:generation").await.unwrap(), 1);
+        assert_eq!(backend.incr("app:users:generation").await.unwrap(), 2);
+        assert_eq!(backend.ttl("app:users:generation").await.unwrap(), -1);
+
+        backend
+            .set_ex_tagged("app:users:query:a", "[]", 60, "app:users:tags:query")
+            .await
+            .unwrap();
+        backend
+            .set_ex_tagged("app:users:query:b", "[]", 60, "app:users:tags:query")
+            .await
+            .unwrap();
+
+        // Capacity evicted the first query, but the tag set survives
+        let mut tagged = backend.take_tagged("app:users:tags:query").await.unwrap();
+        tagged.sort();
+        assert_eq!(tagged, vec!["app:users:query:a", "app:users:query:b"]);
+        assert!(
+            backend
+                .take_tagged("app:users:tags:query")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let (cursor, keys) = backend.scan(0, "app:users:query:*", 100).await.unwrap();
+        assert_eq!(cursor, 0);
+        assert_eq!(keys, vec!["app:users:query:b"]);
+    }
+
+    #[tokio::test]
+    async fn test_set_nx_and_del_if_eq() {
+        let backend = MemoryBackend::new(1);
+        let ttl = Duration::from_millis(50);
+
+        assert!(backend.set_nx("app:users:lock:1", "a", ttl).await.unwrap());
+        assert!(!backend.set_nx("app:users:lock:1", "b", ttl).await.unwrap());
+
+        // Locks are pinned, so filling the cache does not evict them
+        backend
+            .set_ex("app:users:record:1", "{}", 60)
+            .await
+            .unwrap();
+        backend
+            .set_ex("app:users:record:2", "{}", 60)
+            .await
+            .unwrap();
+
+        assert!(!backend.del_if_eq("app:users:lock:1", "b").await.unwrap());
+        assert!(backend.del_if_eq("app:users:lock:1", "a").await.unwrap());
+        assert!(!backend.exists("app:users:lock:1").await.unwrap());
+
+        // Expired locks can be taken again
+        assert!(backend.set_nx("app:users:lock:2", "a", ttl).await.unwrap());
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(backend.set_nx("app:users:lock:2", "b", ttl).await.unwrap());
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("app:users:query:*", "app:users:query:abc"));
+        assert!(glob_match("app:*:record:?", "app:users:record:1"));
+        assert!(!glob_match("app:users:query:*", "app:users:record:1"));
+        assert!(!glob_match("app:users", "app:users:query"));
+    }
+}
@@ -0,0 +1,84 @@
+//! Cache storage backends
+//!
+//! This module defines the CacheBackend trait used by CacheManager
+//! together with the Redis, in-memory and tiered implementations.
+
+pub mod memory;
+pub mod redis;
+pub mod tiered;
+
+use crate::errors::CacheError;
+use async_trait::async_trait;
+use config::{CacheBackendKind, CacheConfig};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub use self::memory::MemoryBackend;
+pub use self::redis::RedisBackend;
+pub use self::tiered::TieredBackend;
+
+/// Key-value storage operations the cache manager relies on
+///
+/// Semantics follow Redis: values are strings, TTLs are in seconds and
+/// `scan` walks keys with a cursor that starts and ends at 0.
+#[async_trait]
+pub trait CacheBackend: Send + Sync + Debug {
+    /// Get the value stored under a key
+    async fn get(&self, key: &str) -> Result<Option<String>, CacheError>;
+
+    /// Store a value that expires after `ttl` seconds
+    async fn set_ex(&self, key: &str, value: &str, ttl: u64) -> Result<(), CacheError>;
+
+    /// Delete keys, returning how many of them existed
+    async fn del(&self, keys: &[String]) -> Result<i32, CacheError>;
+
+    /// Check whether a key exists
+    async fn exists(&self, key: &str) -> Result<bool, CacheError>;
+
+    /// Remaining TTL in seconds; -2 if the key does not exist, -1 if it never expires
+    async fn ttl(&self, key: &str) -> Result<i64, CacheError>;
+
+    /// One iteration step over keys matching a glob pattern
+    ///
+    /// Returns the next cursor (0 when done) and a batch of keys.
+    async fn scan(
+        &self,
+        cursor: u64,
+        pattern: &str,
+        count: usize,
+    ) -> Result<(u64, Vec<String>), CacheError>;
+
+    /// Increment a counter that never expires, returning the new value
+    async fn incr(&self, key: &str) -> Result<u64, CacheError>;
+
+    /// Store a value and record its key in a tag set, refreshing the set's TTL
+    async fn set_ex_tagged(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: u64,
+        tag_key: &str,
+    ) -> Result<(), CacheError>;
+
+    /// Atomically read and remove all keys recorded in a tag set
+    async fn take_tagged(&self, tag_key: &str) -> Result<Vec<String>, CacheError>;
+
+    /// Store a value only if the key does not exist, expiring after `ttl`; returns whether it was set
+    async fn set_nx(&self, key: &str, value: &str, ttl: Duration) -> Result<bool, CacheError>;
+
+    /// Delete a key only if it still holds `value`; returns whether it was deleted
+    async fn del_if_eq(&self, key: &str, value: &str) -> Result<bool, CacheError>;
+
+    /// Check connectivity
+    async fn ping(&self) -> Result<String, CacheError>;
+}
+
+/// Create the backend selected in the configuration
+pub fn from_config(config: &CacheConfig) -> Result<Arc<dyn CacheBackend>, CacheError> {
+    match config.backend {
+        CacheBackendKind::Redis => Ok(Arc::new(RedisBackend::new(config)?)),
+        CacheBackendKind::Memory => Ok(Arc::new(MemoryBackend::new(config.memory_capacity))),
+        CacheBackendKind::Tiered => Ok(Arc::new(TieredBackend::new(config)?)),
+    }
+}
@@ -0,0 +1,203 @@
+//! Redis cache backend
+//!
+//! This module provides the RedisBackend used by default,
+//! sharing one multiplexed connection per backend.
+
+use super::CacheBackend;
+use crate::errors::CacheError;
+use async_trait::async_trait;
+use config::CacheConfig;
+use redis::{AsyncCommands, Client};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+/// Cache backend storing entries in Redis
+#[derive(Clone)]
+pub struct RedisBackend {
+    client: Arc<Client>,
+    connection_pool: Arc<RwLock<Option<redis::aio::MultiplexedConnection>>>,
+}
+
+impl Debug for RedisBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let connection_status = {
+            match self.connection_pool.try_read() {
+                Ok(pool) => {
+                    if pool.is_some() {
+                        "connected"
+                    } else {
+                        "no_connection"
+                    }
+                }
+                Err(_) => "lock_error",
+            }
+        };
+
+        f.debug_struct("RedisBackend")
+            .field("connected", &connection_status)
+            .finish()
+    }
+}
+
+impl RedisBackend {
+    /// Create a backend for the configured Redis URL (connects lazily)
+    pub fn new(config: &CacheConfig) -> Result<Self, CacheError> {
+        let client = Client::open(config.redis_url.as_str())?;
+
+        Ok(Self {
+            client: Arc::new(client),
+            connection_pool: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    /// Get the underlying Redis client
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Get or create Redis connection
+    pub async fn connection(&self) -> Result<redis::aio::MultiplexedConnection, CacheError> {
+        let mut pool = self.connection_pool.write().await;
+
+        if pool.is_none() {
+            let connection = self.client.get_multiplexed_async_connection().await?;
+            *pool = Some(connection);
+        }
+
+        // Safe extraction: we just ensured pool contains a connection above
+        Ok(pool
+            .as_ref()
+            .ok_or_else(|| CacheError::Connection("Failed to get connection from pool".into()))?
+            .clone())
+    }
+
+    /// Get a value with its remaining TTL in seconds (-1 without expiry) in one round trip
+    pub async fn get_with_ttl(&self, key: &str) -> Result<Option<(String, i64)>, CacheError> {
+        let mut conn = self.connection().await?;
+        let (value, ttl): (Option<String>, i64) = redis::pipe()
+            .atomic()
+            .get(key)
+            .ttl(key)
+            .query_async(&mut conn)
+            .await?;
+        Ok(value.map(|value| (value, ttl)))
+    }
+}
+
+#[async_trait]
+impl CacheBackend for RedisBackend {
+    async fn get(&self, key: &str) -> Result<Option<String>, CacheError> {
+        let mut conn = self.connection().await?;
+        Ok(conn.get(key).await?)
+    }
+
+    async fn set_ex(&self, key: &str, value: &str, ttl: u64) -> Result<(), CacheError> {
+        let mut conn = self.connection().await?;
+        let _: () = conn.set_ex(key, value, ttl).await?;
+        Ok(())
+    }
+
+    async fn del(&self, keys: &[String]) -> Result<i32, CacheError> {
+        if keys.is_empty() {
+            return Ok(0);
+        }
+        let mut conn = self.connection().await?;
+        Ok(conn.del(keys).await?)
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, CacheError> {
+        let mut conn = self.connection().await?;
+        Ok(conn.exists(key).await?)
+    }
+
+    async fn ttl(&self, key: &str) -> Result<i64, CacheError> {
+        let mut conn = self.connection().await?;
+        Ok(conn.ttl(key).await?)
+    }
+
+    async fn scan(
+        &self,
+        cursor: u64,
+        pattern: &str,
+        count: usize,
+    ) -> Result<(u64, Vec<String>), CacheError> {
+        let mut conn = self.connection().await?;
+        Ok(redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(pattern)
+            .arg("COUNT")
+            .arg(count)
+            .query_async(&mut conn)
+            .await?)
+    }
+
+    async fn incr(&self, key: &str) -> Result<u64, CacheError> {
+        let mut conn = self.connection().await?;
+        Ok(conn.incr(key, 1).await?)
+    }
+
+    async fn set_ex_tagged(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: u64,
+        tag_key: &str,
+    ) -> Result<(), CacheError> {
+        let mut conn = self.connection().await?;
+        let _: () = redis::pipe()
+            .atomic()
+            .set_ex(key, value, ttl)
+            .ignore()
+            .sadd(tag_key, key)
+            .ignore()
+            .expire(tag_key, ttl as i64)
+            .ignore()
+            .query_async(&mut conn)
+            .await?;
+        Ok(())
+    }
+
+    async fn take_tagged(&self, tag_key: &str) -> Result<Vec<String>, CacheError> {
+        let mut conn = self.connection().await?;
+        let (keys, _): (Vec<String>, i32) = redis::pipe()
+            .atomic()
+            .smembers(tag_key)
+            .del(tag_key)
+            .query_async(&mut conn)
+            .await?;
+        Ok(keys)
+    }
+
+    async fn set_nx(&self, key: &str, value: &str, ttl: Duration) -> Result<bool, CacheError> {
+        let mut conn = self.connection().await?;
+        let result: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("NX")
+            .arg("PX")
+            .arg(ttl.as_millis().max(1) as u64)
+            .query_async(&mut conn)
+            .await?;
+        Ok(result.is_some())
+    }
+
+    async fn del_if_eq(&self, key: &str, value: &str) -> Result<bool, CacheError> {
+        let mut conn = self.connection().await?;
+        let deleted: i32 = redis::Script::new(
+            "if redis.call('GET', KEYS[1]) == ARGV[1] then return redis.call('DEL', KEYS[1]) else return 0 end",
+        )
+        .key(key)
+        .arg(value)
+        .invoke_async(&mut conn)
+        .await?;
+        Ok(deleted > 0)
+    }
+
+    async fn ping(&self) -> Result<String, CacheError> {
+        let mut conn = self.connection().await?;
+        Ok(redis::cmd("PING").query_async(&mut conn).await?)
+    }
+}
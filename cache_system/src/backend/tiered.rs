@@ -0,0 +1,340 @@
+//! Two-tier cache backend
+//!
+//! This module provides the TieredBackend: an in-process LRU (L1) in front
+//! of Redis (L2), with invalidations broadcast to other nodes via pub/sub.
+
+use super::{CacheBackend, MemoryBackend, RedisBackend};
+use crate::errors::CacheError;
+use async_trait::async_trait;
+use config::CacheConfig;
+use futures_util::StreamExt;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::OnceCell;
+use tokio::task::JoinHandle;
+
+/// Delay before re-subscribing after the pub/sub connection is lost
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Distinguishes backends created in the same process
+static NEXT_BACKEND: AtomicU64 = AtomicU64::new(0);
+
+/// Invalidation broadcast to other nodes
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct InvalidationMessage {
+    /// Node that changed the keys; it has already updated its own L1
+    node: String,
+    keys: Vec<String>,
+}
+
+/// Stops the pub/sub listener when the last backend clone is dropped
+struct ListenerGuard(JoinHandle<()>);
+
+impl Drop for ListenerGuard {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Cache backend with an in-process L1 in front of Redis
+///
+/// Reads are served from L1 when possible and fall back to Redis. Every
+/// write updates Redis, updates or drops the local L1 entry and publishes
+/// the changed keys so that other nodes drop their L1 copies.
+///
+/// L1 entries live at most `l1_ttl_seconds`, which bounds staleness if an
+/// invalidation message is lost, and never outlive the Redis entry. They
+/// remember when the Redis entry expires, so `ttl` is answered from L1.
+/// After the pub/sub connection drops, L1 is cleared once the node has
+/// re-subscribed.
+#[derive(Clone)]
+pub struct TieredBackend {
+    l1: MemoryBackend,
+    l2: RedisBackend,
+    l1_ttl: u64,
+    channel: String,
+    node_id: String,
+    listener: Arc<OnceCell<ListenerGuard>>,
+}
+
+impl Debug for TieredBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TieredBackend")
+            .field("l1_entries", &self.l1.len())
+            .field("l2", &self.l2)
+            .field("l1_ttl", &self.l1_ttl)
+            .field("channel", &self.channel)
+            .field("node_id", &self.node_id)
+            .field("listening", &self.listener.initialized())
+            .finish()
+    }
+}
+
+impl TieredBackend {
+    /// Create a tiered backend (connects and subscribes lazily on first use)
+    pub fn new(config: &CacheConfig) -> Result<Self, CacheError> {
+        let started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos())
+            .unwrap_or_default();
+
+        Ok(Self {
+            l1: MemoryBackend::new(config.memory_capacity),
+            l2: RedisBackend::new(config)?,
+            l1_ttl: config.l1_ttl_seconds.max(1),
+            channel: config.invalidation_channel.clone(),
+            node_id: format!(
+                "{}-{:x}-{}",
+                std::process::id(),
+                started,
+                NEXT_BACKEND.fetch_add(1, Ordering::Relaxed)
+            ),
+            listener: Arc::new(OnceCell::new()),
+        })
+    }
+
+    /// The in-process tier
+    pub fn l1(&self) -> &MemoryBackend {
+        &self.l1
+    }
+
+    /// The Redis tier
+    pub fn l2(&self) -> &RedisBackend {
+        &self.l2
+    }
+
+    /// Start the invalidation listener if it is not running yet
+    async fn ensure_listener(&self) {
+        self.listener
+            .get_or_init(|| async {
+                ListenerGuard(tokio::spawn(listen(
+                    self.l2.client().clone(),
+                    self.channel.clone(),
+                    self.node_id.clone(),
+                    self.l1.clone(),
+                )))
+            })
+            .await;
+    }
+
+    /// Tell other nodes to drop their L1 entries for these keys
+    async fn publish(&self, keys: &[String]) -> Result<(), CacheError> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+
+        let message = serde_json::to_string(&InvalidationMessage {
+            node: self.node_id.clone(),
+            keys: keys.to_vec(),
+        })?;
+        let mut conn = self.l2.connection().await?;
+        let _: i64 = conn.publish(&self.channel, message).await?;
+        Ok(())
+    }
+
+    fn l1_ttl_for(&self, ttl: u64) -> u64 {
+        ttl.min(self.l1_ttl)
+    }
+
+    /// L1 lifetime and source TTL of a value Redis keeps for `remaining` seconds
+    ///
+    /// Redis reports -1 for values without expiry.
+    fn l1_copy_ttl(&self, remaining: i64) -> (u64, Option<u64>) {
+        match u64::try_from(remaining) {
+            Ok(remaining) => (self.l1_ttl_for(remaining), Some(remaining)),
+            Err(_) => (self.l1_ttl, None),
+        }
+    }
+}
+
+/// Apply invalidations published by other nodes to the local L1
+async fn listen(client: redis::Client, channel: String, node_id: String, l1: MemoryBackend) {
+    loop {
+        if let Ok(mut pubsub) = client.get_async_pubsub().await
+            && pubsub.subscribe(&channel).await.is_ok()
+        {
+            // Invalidations published before the subscription may have been missed
+            l1.clear();
+
+            let mut messages = pubsub.into_on_message();
+            while let Some(msg) = messages.next().await {
+                let Ok(payload) = msg.get_payload::<String>() else {
+                    continue;
+                };
+                match serde_json::from_str::<InvalidationMessage>(&payload) {
+                    Ok(message) if message.node == node_id => {}
+                    Ok(message) => {
+                        let _ = l1.del(&message.keys).await;
+                    }
+                    // Unknown payload: drop everything rather than risk stale reads
+                    Err(_) => l1.clear(),
+                }
+            }
+        }
+
+        l1.clear();
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+#[async_trait]
+impl CacheBackend for TieredBackend {
+    async fn get(&self, key: &str) -> Result<Option<String>, CacheError> {
+        self.ensure_listener().await;
+
+        if let Some(value) = self.l1.get(key).await? {
+            return Ok(Some(value));
+        }
+
+        let Some((value, remaining)) = self.l2.get_with_ttl(key).await? else {
+            return Ok(None);
+        };
+        let (l1_ttl, source_ttl) = self.l1_copy_ttl(remaining);
+        self.l1.set_copy(key, &value, l1_ttl, source_ttl)?;
+        Ok(Some(value))
+    }
+
+    async fn set_ex(&self, key: &str, value: &str, ttl: u64) -> Result<(), CacheError> {
+        self.ensure_listener().await;
+
+        self.l2.set_ex(key, value, ttl).await?;
+        self.l1
+            .set_copy(key, value, self.l1_ttl_for(ttl), Some(ttl))?;
+        self.publish(&[key.to_string()]).await
+    }
+
+    async fn del(&self, keys: &[String]) -> Result<i32, CacheError> {
+        let deleted = self.l2.del(keys).await?;
+        self.l1.del(keys).await?;
+        self.publish(keys).await?;
+        Ok(deleted)
+    }
+
+    async fn exists(&self, key: &str) -> Result<bool, CacheError> {
+        self.l2.exists(key).await
+    }
+
+    async fn ttl(&self, key: &str) -> Result<i64, CacheError> {
+        match self.l1.ttl(key).await? {
+            -2 => self.l2.ttl(key).await,
+            ttl => Ok(ttl),
+        }
+    }
+
+    async fn scan(
+        &self,
+        cursor: u64,
+        pattern: &str,
+        count: usize,
+    ) -> Result<(u64, Vec<String>), CacheError> {
+        self.l2.scan(cursor, pattern, count).await
+    }
+
+    async fn incr(&self, key: &str) -> Result<u64, CacheError> {
+        let value = self.l2.incr(key).await?;
+        let keys = [key.to_string()];
+        self.l1.del(&keys).await?;
+        self.publish(&keys).await?;
+        Ok(value)
+    }
+
+    async fn set_ex_tagged(
+        &self,
+        key: &str,
+        value: &str,
+        ttl: u64,
+        tag_key: &str,
+    ) -> Result<(), CacheError> {
+        self.ensure_listener().await;
+
+        self.l2.set_ex_tagged(key, value, ttl, tag_key).await?;
+        self.l1
+            .set_copy(key, value, self.l1_ttl_for(ttl), Some(ttl))?;
+        self.publish(&[key.to_string()]).await
+    }
+
+    async fn take_tagged(&self, tag_key: &str) -> Result<Vec<String>, CacheError> {
+        // Only the tag set is removed; the tagged keys are deleted through `del`
+        self.l2.take_tagged(tag_key).await
+    }
+
+    async fn set_nx(&self, key: &str, value: &str, ttl: Duration) -> Result<bool, CacheError> {
+        self.l2.set_nx(key, value, ttl).await
+    }
+
+    async fn del_if_eq(&self, key: &str, value: &str) -> Result<bool, CacheError> {
+        self.l2.del_if_eq(key, value).await
+    }
+
+    async fn ping(&self) -> Result<String, CacheError> {
+        self.l2.ping().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> CacheConfig {
+        CacheConfig::new("redis://localhost:6379".to_string(), 10, 5000, 100, 3000).with_l1_ttl(5)
+    }
+
+    #[test]
+    fn test_backends_have_distinct_node_ids() {
+        let first = TieredBackend::new(&config()).unwrap();
+        let second = TieredBackend::new(&config()).unwrap();
+        assert_ne!(first.node_id, second.node_id);
+        assert_eq!(first.node_id, first.clone().node_id);
+    }
+
+    #[test]
+    fn test_l1_ttl_is_capped() {
+        let backend = TieredBackend::new(&config()).unwrap();
+        assert_eq!(backend.l1_ttl_for(3600), 5);
+        assert_eq!(backend.l1_ttl_for(2), 2);
+    }
+
+    #[test]
+    fn test_l1_copies_never_outlive_redis() {
+        let backend = TieredBackend::new(&config()).unwrap();
+        assert_eq!(backend.l1_copy_ttl(3600), (5, Some(3600)));
+        assert_eq!(backend.l1_copy_ttl(2), (2, Some(2)));
+        assert_eq!(backend.l1_copy_ttl(-1), (5, None));
+    }
+
+    #[tokio::test]
+    async fn test_ttl_is_answered_from_l1() {
+        // Nothing listens on this port, so any Redis call would fail
+        let backend = TieredBackend::new(
+            &CacheConfig::new("redis://127.0.0.1:1".to_string(), 10, 5000, 100, 3000)
+                .with_l1_ttl(5),
+        )
+        .unwrap();
+        backend
+            .l1()
+            .set_copy("app:users:record:1", "{}", 5, Some(600))
+            .unwrap();
+        assert!((599..=600).contains(&backend.ttl("app:users:record:1").await.unwrap()));
+    }
+
+    #[test]
+    fn test_invalidation_message_format() {
+        let message = InvalidationMessage {
+            node: "node-1".to_string(),
+            keys: vec!["app:users:record:1".to_string()],
+        };
+        let payload = serde_json::to_string(&message).unwrap();
+        assert_eq!(
+            payload,
+            r#"{"node":"node-1","keys":["app:users:record:1"]}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<InvalidationMessage>(&payload).unwrap(),
+            message
+        );
+    }
+}
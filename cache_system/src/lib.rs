@@ -1,16 +1,20 @@
 //! Cache system for Redis-based caching
 //!
-//! This crate provides Redis caching functionality
-//! with configurable parameters and error handling.
+//! This crate provides caching functionality backed by Redis or an
+//! in-process store, with configurable parameters and error handling.
 
+pub mod backend;
 pub mod errors;
 pub mod manager;
 pub mod params;
 pub mod prelude;
+pub mod stampede;
 
 // Re-export centralized config
-pub use config::CacheConfig;
+pub use config::{CacheBackendKind, CacheConfig};
 
+pub use backend::{CacheBackend, MemoryBackend, RedisBackend, TieredBackend};
 pub use errors::CacheError;
-pub use manager::CacheManager;
-pub use params::CacheParams;
+pub use manager::{CacheManager, QueryInvalidation, QueryKey, RecordLock};
+pub use params::{CacheParams, CacheWriteMode, StampedeLock, StampedeProtection};
+pub use stampede::SingleFlight;
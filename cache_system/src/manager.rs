@@ -1,74 +1,117 @@
 //! Cache manager implementation
 //!
 //! This module provides the main CacheManager struct
-//! for Redis operations and connection management.
+//! for cache key management on top of a pluggable backend.
 
+use crate::backend::{self, CacheBackend};
 use crate::errors::CacheError;
+use crate::stampede::{self, SingleFlight};
 use config::CacheConfig;
-use redis::{AsyncCommands, Client};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
 use std::fmt::Debug;
+use std::future::Future;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+
+/// Number of keys requested per SCAN step and deleted per DEL batch
+const SCAN_BATCH_SIZE: usize = 500;
+
+/// Interval between cache checks while another node holds a reload lock
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+/// Strategy used to invalidate the cached query results of a table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QueryInvalidation {
+    /// Track query keys in a per-table Redis SET and delete exactly those keys
+    #[default]
+    TagSet,
+    /// Find query keys with batched SCAN iteration
+    Scan,
+    /// Embed a per-table generation in query keys; invalidation is a single INCR
+    /// and stale entries expire through their TTL
+    Versioned,
+}
+
+/// Reload lock on a cached record, released with `CacheManager::unlock_record`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordLock {
+    key: String,
+    token: String,
+}
+
+/// Cache key of one query's results, from `CacheManager::query_key`
+///
+/// Resolve it before running the query and use the same key to store the
+/// results. With `QueryInvalidation::Versioned` the key holds the table
+/// generation at that moment, so results read before a concurrent write are
+/// stored under the generation that write invalidated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryKey {
+    key: String,
+    /// Tag set tracking the key with `QueryInvalidation::TagSet`
+    tags_key: Option<String>,
+}
 
-/// Redis-based cache manager
+impl QueryKey {
+    /// Backend key of the cached results
+    pub fn as_str(&self) -> &str {
+        &self.key
+    }
+}
+
+/// Cache manager for records and query results
 #[derive(Clone)]
 pub struct CacheManager {
-    client: Arc<Client>,
+    backend: Arc<dyn CacheBackend>,
     config: Arc<CacheConfig>,
-    connection_pool: Arc<RwLock<Option<redis::aio::MultiplexedConnection>>>,
+    query_invalidation: QueryInvalidation,
+    record_loads: Arc<SingleFlight<Option<String>>>,
 }
 
 impl Debug for CacheManager {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let connection_status = {
-            match self.connection_pool.try_read() {
-                Ok(pool) => {
-                    if pool.is_some() {
-                        "connected"
-                    } else {
-                        "no_connection"
-                    }
-                }
-                Err(_) => "lock_error",
-            }
-        };
-
         f.debug_struct("CacheManager")
             .field("config", &self.config)
-            .field("connected", &connection_status)
+            .field("backend", &self.backend)
+            .field("query_invalidation", &self.query_invalidation)
+            .field("record_loads_in_flight", &self.record_loads.in_flight())
             .finish()
     }
 }
 
 impl CacheManager {
-    /// Create a new cache manager
+    /// Create a new cache manager using the backend selected in the configuration
     pub fn new(config: CacheConfig) -> Result<Self, CacheError> {
-        let client = Client::open(config.redis_url.as_str())?;
+        let backend = backend::from_config(&config)?;
+        Ok(Self::from_backend(config, backend))
+    }
 
-        Ok(Self {
-            client: Arc::new(client),
+    /// Create a cache manager on top of a custom backend
+    pub fn from_backend(config: CacheConfig, backend: Arc<dyn CacheBackend>) -> Self {
+        Self {
+            backend,
             config: Arc::new(config),
-            connection_pool: Arc::new(RwLock::new(None)),
-        })
+            query_invalidation: QueryInvalidation::default(),
+            record_loads: Arc::new(SingleFlight::new()),
+        }
     }
 
-    /// Get or create Redis connection
-    async fn get_connection(&self) -> Result<redis::aio::MultiplexedConnection, CacheError> {
-        let mut pool = self.connection_pool.write().await;
+    /// Choose how cached query results are invalidated (tag sets by default)
+    pub fn with_query_invalidation(mut self, query_invalidation: QueryInvalidation) -> Self {
+        self.query_invalidation = query_invalidation;
+        self
+    }
 
-        if pool.is_none() {
-            let connection = self.client.get_multiplexed_async_connection().await?;
-            *pool = Some(connection);
-        }
+    /// Get the query invalidation strategy
+    pub fn query_invalidation(&self) -> QueryInvalidation {
+        self.query_invalidation
+    }
 
-        // Safe extraction: we just ensured pool contains a connection above
-        Ok(pool
-            .as_ref()
-            .ok_or_else(|| CacheError::Connection("Failed to get connection from pool".into()))?
-            .clone())
+    /// Get the storage backend
+    pub fn backend(&self) -> &Arc<dyn CacheBackend> {
+        &self.backend
     }
 
     /// Generate cache key for record by ID
@@ -76,11 +119,85 @@ impl CacheManager {
         format!("{}:{}:record:{}", prefix, table_name, id)
     }
 
+    /// Generate key of the reload lock for a record
+    fn build_lock_key(&self, prefix: &str, table_name: &str, id: &str) -> String {
+        format!("{}:{}:lock:{}", prefix, table_name, id)
+    }
+
     /// Generate cache key for query results
     fn build_query_key(&self, prefix: &str, table_name: &str, query_hash: &str) -> String {
         format!("{}:{}:query:{}", prefix, table_name, query_hash)
     }
 
+    /// Generate cache key for query results within a table generation
+    fn build_versioned_query_key(
+        &self,
+        prefix: &str,
+        table_name: &str,
+        generation: u64,
+        query_hash: &str,
+    ) -> String {
+        format!(
+            "{}:{}:query:g{}:{}",
+            prefix, table_name, generation, query_hash
+        )
+    }
+
+    /// Generate key of the SET tracking a table's query keys
+    fn build_query_tags_key(&self, prefix: &str, table_name: &str) -> String {
+        format!("{}:{}:tags:query", prefix, table_name)
+    }
+
+    /// Generate key of a table's generation counter
+    fn build_generation_key(&self, prefix: &str, table_name: &str) -> String {
+        format!("{}:{}:generation", prefix, table_name)
+    }
+
+    /// Resolve the key for query results according to the invalidation strategy
+    ///
+    /// Call this before reading from the database and pass the key to both
+    /// `get_query` and `set_query`.
+    pub async fn query_key(
+        &self,
+        prefix: &str,
+        table_name: &str,
+        query_hash: &str,
+    ) -> Result<QueryKey, CacheError> {
+        let key = match self.query_invalidation {
+            QueryInvalidation::TagSet | QueryInvalidation::Scan => {
+                self.build_query_key(prefix, table_name, query_hash)
+            }
+            QueryInvalidation::Versioned => {
+                let generation = self
+                    .backend
+                    .get(&self.build_generation_key(prefix, table_name))
+                    .await?
+                    .and_then(|value| value.parse::<u64>().ok())
+                    .unwrap_or(0);
+                self.build_versioned_query_key(prefix, table_name, generation, query_hash)
+            }
+        };
+        let tags_key = (self.query_invalidation == QueryInvalidation::TagSet)
+            .then(|| self.build_query_tags_key(prefix, table_name));
+        Ok(QueryKey { key, tags_key })
+    }
+
+    /// Delete all keys matching a pattern using batched SCAN iteration
+    async fn scan_delete(&self, pattern: &str) -> Result<i32, CacheError> {
+        let mut cursor: u64 = 0;
+        let mut deleted = 0;
+
+        loop {
+            let (next_cursor, keys) = self.backend.scan(cursor, pattern, SCAN_BATCH_SIZE).await?;
+            deleted += self.backend.del(&keys).await?;
+
+            if next_cursor == 0 {
+                return Ok(deleted);
+            }
+            cursor = next_cursor;
+        }
+    }
+
     /// Generate hash for query parameters
     pub fn hash_query<T: Hash>(&self, query: &T) -> String {
         let mut hasher = DefaultHasher::new();
@@ -99,9 +216,7 @@ impl CacheManager {
         T: for<'de> Deserialize<'de>,
     {
         let cache_key = self.build_record_key(prefix, table_name, id);
-        let mut conn = self.get_connection().await?;
-
-        let cached_data: Option<String> = conn.get(&cache_key).await?;
+        let cached_data = self.backend.get(&cache_key).await?;
 
         match cached_data {
             Some(json_str) => {
@@ -112,6 +227,114 @@ impl CacheManager {
         }
     }
 
+    /// Get single record from cache together with its remaining TTL in seconds
+    pub async fn get_record_with_ttl<T>(
+        &self,
+        prefix: &str,
+        table_name: &str,
+        id: &str,
+    ) -> Result<Option<(T, i64)>, CacheError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let cache_key = self.build_record_key(prefix, table_name, id);
+        let Some(json_str) = self.backend.get(&cache_key).await? else {
+            return Ok(None);
+        };
+
+        let value: T = serde_json::from_str(&json_str)?;
+        let remaining = self.backend.ttl(&cache_key).await?;
+        Ok(Some((value, remaining)))
+    }
+
+    /// Get the serialized form of a cached record
+    pub async fn get_record_json(
+        &self,
+        prefix: &str,
+        table_name: &str,
+        id: &str,
+    ) -> Result<Option<String>, CacheError> {
+        let cache_key = self.build_record_key(prefix, table_name, id);
+        self.backend.get(&cache_key).await
+    }
+
+    /// Store an already serialized record
+    pub async fn set_record_json(
+        &self,
+        prefix: &str,
+        table_name: &str,
+        id: &str,
+        json_str: &str,
+        ttl: u64,
+    ) -> Result<(), CacheError> {
+        let cache_key = self.build_record_key(prefix, table_name, id);
+        self.backend.set_ex(&cache_key, json_str, ttl).await
+    }
+
+    /// Load a serialized record, coalescing concurrent loads of the same record
+    ///
+    /// Callers arriving while a load for the same record is in flight in this
+    /// process wait for it and share its result instead of running `load`.
+    pub async fn load_record_json<F, Fut, E>(
+        &self,
+        prefix: &str,
+        table_name: &str,
+        id: &str,
+        load: F,
+    ) -> Result<Option<String>, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Option<String>, E>>,
+    {
+        let cache_key = self.build_record_key(prefix, table_name, id);
+        self.record_loads.run(&cache_key, load).await
+    }
+
+    /// Try to take the reload lock of a record; `None` if another caller holds it
+    pub async fn try_lock_record(
+        &self,
+        prefix: &str,
+        table_name: &str,
+        id: &str,
+        ttl: Duration,
+    ) -> Result<Option<RecordLock>, CacheError> {
+        let lock = RecordLock {
+            key: self.build_lock_key(prefix, table_name, id),
+            token: stampede::random_token(),
+        };
+
+        let acquired = self.backend.set_nx(&lock.key, &lock.token, ttl).await?;
+        Ok(acquired.then_some(lock))
+    }
+
+    /// Release a reload lock unless it already expired and was taken by someone else
+    pub async fn unlock_record(&self, lock: &RecordLock) -> Result<bool, CacheError> {
+        self.backend.del_if_eq(&lock.key, &lock.token).await
+    }
+
+    /// Wait up to `max_wait` for a record to appear in the cache
+    pub async fn wait_for_record_json(
+        &self,
+        prefix: &str,
+        table_name: &str,
+        id: &str,
+        max_wait: Duration,
+    ) -> Result<Option<String>, CacheError> {
+        let deadline = Instant::now() + max_wait;
+
+        loop {
+            if let Some(json_str) = self.get_record_json(prefix, table_name, id).await? {
+                return Ok(Some(json_str));
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(None);
+            }
+            tokio::time::sleep(LOCK_POLL_INTERVAL.min(deadline - now)).await;
+        }
+    }
+
     /// Set single record in cache by ID
     pub async fn set_record<T>(
         &self,
@@ -140,28 +363,17 @@ impl CacheManager {
     where
         T: Serialize,
     {
-        let cache_key = self.build_record_key(prefix, table_name, id);
         let json_str = serde_json::to_string(value)?;
-        let mut conn = self.get_connection().await?;
-
-        let _: () = conn.set_ex(&cache_key, &json_str, ttl).await?;
-        Ok(())
+        self.set_record_json(prefix, table_name, id, &json_str, ttl)
+            .await
     }
 
     /// Get query results from cache
-    pub async fn get_query<T>(
-        &self,
-        prefix: &str,
-        table_name: &str,
-        query_hash: &str,
-    ) -> Result<Option<Vec<T>>, CacheError>
+    pub async fn get_query<T>(&self, key: &QueryKey) -> Result<Option<Vec<T>>, CacheError>
     where
         T: for<'de> Deserialize<'de>,
     {
-        let cache_key = self.build_query_key(prefix, table_name, query_hash);
-        let mut conn = self.get_connection().await?;
-
-        let cached_data: Option<String> = conn.get(&cache_key).await?;
+        let cached_data = self.backend.get(&key.key).await?;
 
         match cached_data {
             Some(json_str) => {
@@ -172,40 +384,27 @@ impl CacheManager {
         }
     }
 
-    /// Set query results in cache
+    /// Set query results in cache under a key resolved before the query ran
     pub async fn set_query<T>(
         &self,
-        prefix: &str,
-        table_name: &str,
-        query_hash: &str,
-        results: &Vec<T>,
+        key: &QueryKey,
+        results: &[T],
         ttl: u64,
     ) -> Result<(), CacheError>
     where
         T: Serialize,
     {
-        self.set_query_with_ttl(prefix, table_name, query_hash, results, ttl)
-            .await
-    }
-
-    /// Set query results in cache with custom TTL
-    pub async fn set_query_with_ttl<T>(
-        &self,
-        prefix: &str,
-        table_name: &str,
-        query_hash: &str,
-        results: &Vec<T>,
-        ttl: u64,
-    ) -> Result<(), CacheError>
-    where
-        T: Serialize,
-    {
-        let cache_key = self.build_query_key(prefix, table_name, query_hash);
         let json_str = serde_json::to_string(results)?;
-        let mut conn = self.get_connection().await?;
 
-        let _: () = conn.set_ex(&cache_key, &json_str, ttl).await?;
-        Ok(())
+        match &key.tags_key {
+            // The tag set lives as long as the latest entry added to it
+            Some(tags_key) => {
+                self.backend
+                    .set_ex_tagged(&key.key, &json_str, ttl, tags_key)
+                    .await
+            }
+            None => self.backend.set_ex(&key.key, &json_str, ttl).await,
+        }
     }
 
     /// Delete specific record from cache
@@ -216,9 +415,8 @@ impl CacheManager {
         id: &str,
     ) -> Result<bool, CacheError> {
         let cache_key = self.build_record_key(prefix, table_name, id);
-        let mut conn = self.get_connection().await?;
 
-        let deleted: i32 = conn.del(&cache_key).await?;
+        let deleted = self.backend.del(&[cache_key]).await?;
         Ok(deleted > 0)
     }
 
@@ -238,30 +436,42 @@ impl CacheManager {
             .map(|id| self.build_record_key(prefix, table_name, &id))
             .collect();
 
-        let mut conn = self.get_connection().await?;
-        let deleted: i32 = conn.del(cache_keys).await?;
-        Ok(deleted)
+        self.backend.del(&cache_keys).await
     }
 
     /// Invalidate all query cache for a table (when data changes)
+    ///
+    /// Returns the number of deleted keys; with `QueryInvalidation::Versioned`
+    /// nothing is deleted and the result is always 0.
     pub async fn invalidate_queries(
         &self,
         prefix: &str,
         table_name: &str,
     ) -> Result<i32, CacheError> {
-        let pattern = format!("{}:{}:query:*", prefix, table_name);
-        let mut conn = self.get_connection().await?;
+        match self.query_invalidation {
+            QueryInvalidation::TagSet => {
+                let tags_key = self.build_query_tags_key(prefix, table_name);
 
-        // Get all matching query keys
-        let keys: Vec<String> = conn.keys(&pattern).await?;
+                // Read and drop the tag set atomically so concurrently added keys are not lost
+                let keys = self.backend.take_tagged(&tags_key).await?;
 
-        if keys.is_empty() {
-            return Ok(0);
+                let mut deleted = 0;
+                for batch in keys.chunks(SCAN_BATCH_SIZE) {
+                    deleted += self.backend.del(batch).await?;
+                }
+                Ok(deleted)
+            }
+            QueryInvalidation::Scan => {
+                self.scan_delete(&format!("{}:{}:query:*", prefix, table_name))
+                    .await
+            }
+            QueryInvalidation::Versioned => {
+                self.backend
+                    .incr(&self.build_generation_key(prefix, table_name))
+                    .await?;
+                Ok(0)
+            }
         }
-
-        // Delete all query cache keys
-        let deleted: i32 = conn.del(keys).await?;
-        Ok(deleted)
     }
 
     /// Full invalidation for table (records + queries) - use sparingly
@@ -270,22 +480,11 @@ impl CacheManager {
         prefix: &str,
         table_name: &str,
     ) -> Result<i32, CacheError> {
-        let record_pattern = format!("{}:{}:record:*", prefix, table_name);
-        let query_pattern = format!("{}:{}:query:*", prefix, table_name);
-        let mut conn = self.get_connection().await?;
-
-        // Get all matching keys
-        let mut all_keys: Vec<String> = Vec::new();
-        all_keys.extend(conn.keys::<_, Vec<String>>(&record_pattern).await?);
-        all_keys.extend(conn.keys::<_, Vec<String>>(&query_pattern).await?);
-
-        if all_keys.is_empty() {
-            return Ok(0);
-        }
-
-        // Delete all keys
-        let deleted: i32 = conn.del(all_keys).await?;
-        Ok(deleted)
+        let records = self
+            .scan_delete(&format!("{}:{}:record:*", prefix, table_name))
+            .await?;
+        let queries = self.invalidate_queries(prefix, table_name).await?;
+        Ok(records + queries)
     }
 
     /// Check if record exists in cache
@@ -296,10 +495,7 @@ impl CacheManager {
         id: &str,
     ) -> Result<bool, CacheError> {
         let cache_key = self.build_record_key(prefix, table_name, id);
-        let mut conn = self.get_connection().await?;
-
-        let exists: bool = conn.exists(&cache_key).await?;
-        Ok(exists)
+        self.backend.exists(&cache_key).await
     }
 
     /// Get TTL for a record
@@ -310,19 +506,12 @@ impl CacheManager {
         id: &str,
     ) -> Result<i64, CacheError> {
         let cache_key = self.build_record_key(prefix, table_name, id);
-        let mut conn = self.get_connection().await?;
-
-        let ttl: i64 = conn.ttl(&cache_key).await?;
-        Ok(ttl)
+        self.backend.ttl(&cache_key).await
     }
 
-    /// Ping Redis to check connectivity
+    /// Ping the backend to check connectivity
     pub async fn ping(&self) -> Result<String, CacheError> {
-        let mut conn = self.get_connection().await?;
-
-        // Use redis::cmd! macro for ping
-        let pong: String = redis::cmd("PING").query_async(&mut conn).await?;
-        Ok(pong)
+        self.backend.ping().await
     }
 
     /// Get current configuration
@@ -330,3 +519,66 @@ impl CacheManager {
         &self.config
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager() -> CacheManager {
+        let config = CacheConfig::new("redis://localhost:6379".to_string(), 10, 5000, 100, 3000);
+        CacheManager::new(config).expect("valid redis url")
+    }
+
+    #[test]
+    fn test_query_invalidation_strategy() {
+        assert_eq!(manager().query_invalidation(), QueryInvalidation::TagSet);
+        assert_eq!(
+            manager()
+                .with_query_invalidation(QueryInvalidation::Versioned)
+                .query_invalidation(),
+            QueryInvalidation::Versioned
+        );
+    }
+
+    #[test]
+    fn test_invalidation_keys_stay_within_table_namespace() {
+        let manager = manager();
+
+        assert_eq!(
+            manager.build_versioned_query_key("app", "users", 3, "abc"),
+            "app:users:query:g3:abc"
+        );
+        assert_eq!(
+            manager.build_query_tags_key("app", "users"),
+            "app:users:tags:query"
+        );
+        assert_eq!(
+            manager.build_generation_key("app", "users"),
+            "app:users:generation"
+        );
+        // Tag sets and counters must never be matched by the query key pattern
+        assert!(
+            !manager
+                .build_query_tags_key("app", "users")
+                .starts_with("app:users:query:")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_versioned_key_is_resolved_before_the_read() {
+        let manager = CacheManager::new(CacheConfig::memory(100))
+            .expect("valid memory config")
+            .with_query_invalidation(QueryInvalidation::Versioned);
+
+        // A reader resolves its key, then a write invalidates while it queries
+        let key = manager.query_key("app", "users", "abc").await.unwrap();
+        manager.invalidate_queries("app", "users").await.unwrap();
+        manager.set_query(&key, &[1_i64], 60).await.unwrap();
+
+        // The stale result stays under the old generation
+        let current = manager.query_key("app", "users", "abc").await.unwrap();
+        assert_ne!(current, key);
+        assert_eq!(manager.get_query::<i64>(&current).await.unwrap(), None);
+        assert_eq!(manager.get_query::<i64>(&key).await.unwrap(), Some(vec![1]));
+    }
+}
@@ -5,6 +5,69 @@
 
 use crate::CacheManager;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// How cached records are kept in sync after a write
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheWriteMode {
+    /// Evict the record entry; the next read reloads it from the database
+    #[default]
+    Invalidate,
+    /// Store the row returned by the write as the new record entry
+    WriteThrough,
+}
+
+/// Protection against many concurrent reloads of the same expired record
+#[derive(Debug, Clone, PartialEq)]
+pub struct StampedeProtection {
+    /// Coalesce concurrent cache misses for the same record within this process
+    pub single_flight: bool,
+    /// Cross-node lock held while one node reloads a record
+    pub lock: Option<StampedeLock>,
+    /// Probabilistic early expiration factor (0 disables); see `should_refresh_early`
+    pub early_expiration_beta: f64,
+}
+
+/// Settings of the cross-node reload lock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StampedeLock {
+    /// Lock expiry, so a crashed holder never blocks reloads for long
+    pub ttl: Duration,
+    /// How long other nodes wait for the holder to fill the cache before loading themselves
+    pub max_wait: Duration,
+}
+
+impl Default for StampedeProtection {
+    fn default() -> Self {
+        Self {
+            single_flight: true,
+            lock: None,
+            early_expiration_beta: 0.0,
+        }
+    }
+}
+
+impl StampedeProtection {
+    /// No protection at all
+    pub fn disabled() -> Self {
+        Self {
+            single_flight: false,
+            ..Self::default()
+        }
+    }
+
+    /// Take a cache lock while reloading, waiting up to `max_wait` for another holder
+    pub fn with_lock(mut self, ttl: Duration, max_wait: Duration) -> Self {
+        self.lock = Some(StampedeLock { ttl, max_wait });
+        self
+    }
+
+    /// Refresh hot records shortly before they expire (typical values: 0.01 - 0.1)
+    pub fn with_early_expiration(mut self, beta: f64) -> Self {
+        self.early_expiration_beta = beta;
+        self
+    }
+}
 
 /// Cache parameters for configuring cache behavior per store/entity
 #[derive(Debug, Clone)]
@@ -15,6 +78,14 @@ pub struct CacheParams {
     pub ttl: u64,
     /// Prefix for cache keys
     pub prefix: String,
+    /// Cache results of `find`, `find_one` and `count_where` (disabled by default)
+    pub cache_queries: bool,
+    /// What happens to cached records on update
+    pub write_mode: CacheWriteMode,
+    /// How `get_by_id` cache misses are coalesced
+    pub stampede: StampedeProtection,
+    /// TTL in seconds for remembering ids that don't exist (`None` disables negative caching)
+    pub negative_ttl: Option<u64>,
 }
 
 impl CacheParams {
@@ -23,6 +94,37 @@ impl CacheParams {
             ttl,
             prefix: prefix.to_string(),
             manager,
+            cache_queries: false,
+            write_mode: CacheWriteMode::default(),
+            stampede: StampedeProtection::default(),
+            negative_ttl: None,
         }
     }
+
+    /// Enable or disable caching of query results (disabled by default)
+    ///
+    /// Cached queries are invalidated by writes through StoreHaus only, so
+    /// enable this for tables that are not written by other clients.
+    pub fn with_query_cache(mut self, enabled: bool) -> Self {
+        self.cache_queries = enabled;
+        self
+    }
+
+    /// Choose how updated records are written to the cache
+    pub fn with_write_mode(mut self, write_mode: CacheWriteMode) -> Self {
+        self.write_mode = write_mode;
+        self
+    }
+
+    /// Configure stampede protection for `get_by_id` (single-flight only by default)
+    pub fn with_stampede_protection(mut self, stampede: StampedeProtection) -> Self {
+        self.stampede = stampede;
+        self
+    }
+
+    /// Cache `get_by_id` misses for `ttl` seconds; the entry is dropped when the id is created
+    pub fn with_negative_caching(mut self, ttl: u64) -> Self {
+        self.negative_ttl = Some(ttl);
+        self
+    }
 }
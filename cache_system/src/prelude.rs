@@ -1,12 +1,14 @@
 //! Convenience re-exports for common cache-system usage
 
 // Core cache system components
+pub use crate::backend::{CacheBackend, MemoryBackend, RedisBackend, TieredBackend};
 pub use crate::errors::CacheError;
-pub use crate::manager::CacheManager;
-pub use crate::params::CacheParams;
+pub use crate::manager::{CacheManager, QueryInvalidation, RecordLock};
+pub use crate::params::{CacheParams, CacheWriteMode, StampedeLock, StampedeProtection};
+pub use crate::stampede::SingleFlight;
 
 // Re-export centralized config
-pub use config::CacheConfig;
+pub use config::{CacheBackendKind, CacheConfig};
 
 // Common external dependencies
 pub use async_trait::async_trait;
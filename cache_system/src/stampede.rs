@@ -0,0 +1,167 @@
+//! Cache stampede protection
+//!
+//! This module provides request coalescing for concurrent cache misses
+//! and the probabilistic early expiration check used on cache hits.
+
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+static RANDOM_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Coalesces concurrent loads of the same key within this process
+///
+/// The first caller for a key runs the loader; callers arriving while it is
+/// in flight wait for and share its result. Errors are not shared: if the
+/// loader fails, the next waiter runs it again.
+#[derive(Debug)]
+pub struct SingleFlight<V> {
+    inflight: Mutex<HashMap<String, Arc<OnceCell<V>>>>,
+}
+
+impl<V> Default for SingleFlight<V> {
+    fn default() -> Self {
+        Self {
+            inflight: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<V: Clone> SingleFlight<V> {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `load` for `key` unless a load for the same key is already in flight
+    pub async fn run<F, Fut, E>(&self, key: &str, load: F) -> Result<V, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        let cell = match self.inflight.lock() {
+            Ok(mut inflight) => inflight
+                .entry(key.to_string())
+                .or_insert_with(|| Arc::new(OnceCell::new()))
+                .clone(),
+            // A poisoned registry only loses coalescing, never correctness
+            Err(_) => Arc::new(OnceCell::new()),
+        };
+
+        let result = cell.get_or_try_init(load).await.cloned();
+
+        if let Ok(mut inflight) = self.inflight.lock()
+            && inflight
+                .get(key)
+                .is_some_and(|current| Arc::ptr_eq(current, &cell))
+        {
+            inflight.remove(key);
+        }
+
+        result
+    }
+
+    /// Number of keys currently being loaded
+    pub fn in_flight(&self) -> usize {
+        self.inflight
+            .lock()
+            .map(|inflight| inflight.len())
+            .unwrap_or(0)
+    }
+}
+
+/// Decide whether a cache hit should be recomputed before it expires
+///
+/// Implements probabilistic early expiration: the probability of refreshing
+/// is `exp(-remaining / (beta * ttl))`, so it approaches 1 as the entry nears
+/// expiry and only a few of many concurrent readers refresh it.
+pub fn should_refresh_early(remaining_secs: i64, ttl: u64, beta: f64) -> bool {
+    // -1 (no expiry) and -2 (missing) never trigger early refreshes
+    if beta <= 0.0 || remaining_secs < 0 {
+        return false;
+    }
+
+    let window = beta * ttl as f64;
+    -window * random_unit().ln() >= remaining_secs as f64
+}
+
+/// Random value in (0, 1]
+fn random_unit() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(RANDOM_COUNTER.fetch_add(1, Ordering::Relaxed));
+    let bits = hasher.finish() >> 11;
+    (bits as f64 + 1.0) / (1u64 << 53) as f64
+}
+
+/// Random token identifying a lock holder
+pub(crate) fn random_token() -> String {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(RANDOM_COUNTER.fetch_add(1, Ordering::Relaxed));
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_single_flight_coalesces_concurrent_loads() {
+        let flights = Arc::new(SingleFlight::<Option<String>>::new());
+        let loads = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..10)
+            .map(|_| {
+                let flights = flights.clone();
+                let loads = loads.clone();
+                tokio::spawn(async move {
+                    flights
+                        .run("users:1", || async {
+                            loads.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                            Ok::<_, ()>(Some("alice".to_string()))
+                        })
+                        .await
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            assert_eq!(task.await.unwrap(), Ok(Some("alice".to_string())));
+        }
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        assert_eq!(flights.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_single_flight_does_not_share_errors() {
+        let flights = SingleFlight::<u32>::new();
+
+        let failed = flights
+            .run("k", || async { Err::<u32, _>("db down") })
+            .await;
+        assert_eq!(failed, Err("db down"));
+
+        let loaded = flights.run("k", || async { Ok::<_, &str>(7) }).await;
+        assert_eq!(loaded, Ok(7));
+    }
+
+    #[test]
+    fn test_should_refresh_early() {
+        // Disabled, never-expiring or missing entries are not refreshed
+        assert!(!should_refresh_early(0, 60, 0.0));
+        assert!(!should_refresh_early(-1, 60, 1.0));
+        assert!(!should_refresh_early(-2, 60, 1.0));
+
+        // Expired entries are always refreshed
+        assert!(should_refresh_early(0, 60, 0.1));
+
+        // Fresh entries are almost never refreshed: exp(-3600 / 36) is ~4e-44
+        assert!((0..1000).all(|_| !should_refresh_early(3600, 3600, 0.01)));
+    }
+}
@@ -48,6 +48,9 @@
 //! timeout_ms = 5000
 //! max_connections = 100
 //! connection_timeout_ms = 3000
+//! backend = "redis"            # "memory" for an in-process cache, "tiered" for memory + redis
+//! memory_capacity = 10000      # entries kept in process (memory and tiered backends)
+//! l1_ttl_seconds = 30          # max lifetime of in-process entries (tiered backend)
 //!
 //! [signal]
 //! callback_timeout_seconds = 30
@@ -60,14 +63,17 @@
 //! ```
 //!
 //! Load configuration:
-//! ```rust
+//! ```rust,no_run
 //! use config::AppConfig;
 //!
+//! # fn main() -> Result<(), config::ConfigError> {
 //! // Load from storehaus.toml
 //! let config = AppConfig::load()?;
 //!
 //! // Or load from custom path
 //! let config = AppConfig::from_file("config/production.toml")?;
+//! # Ok(())
+//! # }
 //! ```
 
 use serde::{Deserialize, Serialize};
@@ -113,6 +119,31 @@ pub struct DatabaseConfig {
     pub max_lifetime_seconds: u64,
 }
 
+/// Storage used by the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheBackendKind {
+    /// Shared Redis server
+    #[default]
+    Redis,
+    /// In-process LRU store with TTL expiration (single node only)
+    Memory,
+    /// In-process LRU (L1) in front of Redis (L2), invalidated across nodes via pub/sub
+    Tiered,
+}
+
+fn default_memory_capacity() -> usize {
+    10_000
+}
+
+fn default_l1_ttl_seconds() -> u64 {
+    30
+}
+
+fn default_invalidation_channel() -> String {
+    "storehaus:cache:invalidate".to_string()
+}
+
 /// Cache configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheConfig {
@@ -121,6 +152,17 @@ pub struct CacheConfig {
     pub timeout_ms: u64,
     pub max_connections: u32,
     pub connection_timeout_ms: u64,
+    #[serde(default)]
+    pub backend: CacheBackendKind,
+    /// Maximum number of entries kept by the in-memory backend (or the L1 tier)
+    #[serde(default = "default_memory_capacity")]
+    pub memory_capacity: usize,
+    /// Upper bound for the lifetime of L1 entries of the tiered backend
+    #[serde(default = "default_l1_ttl_seconds")]
+    pub l1_ttl_seconds: u64,
+    /// Redis pub/sub channel used by the tiered backend to broadcast invalidations
+    #[serde(default = "default_invalidation_channel")]
+    pub invalidation_channel: String,
 }
 
 /// Signal system configuration
@@ -215,7 +257,17 @@ impl AppConfig {
         }
 
         // Cache validations
-        if self.cache.redis_url.is_empty() {
+        if self.cache.backend != CacheBackendKind::Redis && self.cache.memory_capacity == 0 {
+            return Err(ConfigError::Invalid(
+                "Cache memory_capacity must be greater than 0".to_string(),
+            ));
+        }
+        if self.cache.backend == CacheBackendKind::Tiered && self.cache.l1_ttl_seconds == 0 {
+            return Err(ConfigError::Invalid(
+                "Cache l1_ttl_seconds must be greater than 0".to_string(),
+            ));
+        }
+        if self.cache.backend != CacheBackendKind::Memory && self.cache.redis_url.is_empty() {
             return Err(ConfigError::Invalid(
                 "Redis URL cannot be empty".to_string(),
             ));
@@ -272,8 +324,43 @@ impl CacheConfig {
             timeout_ms,
             max_connections,
             connection_timeout_ms,
+            backend: CacheBackendKind::Redis,
+            memory_capacity: default_memory_capacity(),
+            l1_ttl_seconds: default_l1_ttl_seconds(),
+            invalidation_channel: default_invalidation_channel(),
         }
     }
+
+    /// Create a configuration for the in-memory backend holding up to `capacity` entries
+    pub fn memory(capacity: usize) -> Self {
+        Self::new(String::new(), 1, 5000, 1, 3000)
+            .with_backend(CacheBackendKind::Memory)
+            .with_memory_capacity(capacity)
+    }
+
+    /// Select the cache backend
+    pub fn with_backend(mut self, backend: CacheBackendKind) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Set the maximum number of entries of the in-memory backend
+    pub fn with_memory_capacity(mut self, capacity: usize) -> Self {
+        self.memory_capacity = capacity;
+        self
+    }
+
+    /// Set the maximum lifetime of L1 entries of the tiered backend
+    pub fn with_l1_ttl(mut self, seconds: u64) -> Self {
+        self.l1_ttl_seconds = seconds;
+        self
+    }
+
+    /// Set the pub/sub channel used to broadcast invalidations between nodes
+    pub fn with_invalidation_channel(mut self, channel: &str) -> Self {
+        self.invalidation_channel = channel.to_string();
+        self
+    }
 }
 
 impl SignalConfig {
@@ -301,6 +388,7 @@ impl SignalConfig {
 
 impl DatabaseConfig {
     /// Create a new database configuration
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         host: String,
         port: u16,
@@ -239,10 +239,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 match (event.table_name.as_str(), &event.event_type) {
                     ("orders", EventType::Create) => {
                         m.order_count += 1;
-                        if let Some(amount_value) = event.payload.get("total_amount") {
-                            if let Ok(amount) = serde_json::from_value::<i32>(json!(amount_value)) {
-                                m.revenue += amount as i64;
-                            }
+                        if let Some(amount_value) = event.payload.get("total_amount")
+                            && let Ok(amount) = serde_json::from_value::<i32>(json!(amount_value))
+                        {
+                            m.revenue += amount as i64;
                         }
                     }
                     ("users", EventType::Create) => {
@@ -261,37 +261,35 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Real-time notifications
     signal_manager.add_callback(|event: DatabaseEvent| async move {
         // High-value order alerts
-        if event.table_name == "orders" && matches!(event.event_type, EventType::Create) {
-            if let Some(amount_value) = event.payload.get("total_amount") {
-                if let Ok(amount) = serde_json::from_value::<i32>(json!(amount_value)) {
-                    if amount > 1000 {
-                        println!(
-                            "🚨 HIGH-VALUE ORDER: ${} - Alert sent to sales team",
-                            amount
-                        );
-                    }
-                }
-            }
+        if event.table_name == "orders"
+            && matches!(event.event_type, EventType::Create)
+            && let Some(amount_value) = event.payload.get("total_amount")
+            && let Ok(amount) = serde_json::from_value::<i32>(json!(amount_value))
+            && amount > 1000
+        {
+            println!(
+                "🚨 HIGH-VALUE ORDER: ${} - Alert sent to sales team",
+                amount
+            );
         }
 
         // Low inventory alerts
-        if event.table_name == "products" && matches!(event.event_type, EventType::Update) {
-            if let Some(stock_value) = event.payload.get("stock") {
-                if let Ok(stock) = serde_json::from_value::<i32>(json!(stock_value)) {
-                    if stock <= 5 {
-                        println!("📦 LOW INVENTORY WARNING: Product stock is {}", stock);
-                    }
-                }
-            }
+        if event.table_name == "products"
+            && matches!(event.event_type, EventType::Update)
+            && let Some(stock_value) = event.payload.get("stock")
+            && let Ok(stock) = serde_json::from_value::<i32>(json!(stock_value))
+            && stock <= 5
+        {
+            println!("📦 LOW INVENTORY WARNING: Product stock is {}", stock);
         }
 
         // User status changes
-        if event.table_name == "users" && matches!(event.event_type, EventType::Update) {
-            if let Some(status) = event.payload.get("status") {
-                if format!("{:?}", status).contains("premium") {
-                    println!("⭐ USER UPGRADED to premium - Send welcome package");
-                }
-            }
+        if event.table_name == "users"
+            && matches!(event.event_type, EventType::Update)
+            && let Some(status) = event.payload.get("status")
+            && format!("{:?}", status).contains("premium")
+        {
+            println!("⭐ USER UPGRADED to premium - Send welcome package");
         }
         Ok(())
     }).await?;
@@ -6,7 +6,6 @@
 //! - GROUP BY with HAVING clauses
 //! - Complex queries combining JOINs, aggregations, and filtering
 
-use serde::{Deserialize, Serialize};
 use serde_json::json;
 use sqlx::PgPool;
 use std::error::Error;
@@ -348,7 +347,10 @@ async fn demo_join_queries(_pool: &PgPool) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-async fn demo_group_by_queries(_pool: &PgPool) -> Result<(), Box<dyn Error>> {
+async fn demo_group_by_queries(pool: &PgPool) -> Result<(), Box<dyn Error>> {
+    let user_store = GenericStore::<User>::new(pool.clone(), None, None);
+    let order_store = GenericStore::<Order>::new(pool.clone(), None, None);
+
     // Group orders by status
     let query = QueryBuilder::new()
         .select_fields(vec![
@@ -368,7 +370,11 @@ async fn demo_group_by_queries(_pool: &PgPool) -> Result<(), Box<dyn Error>> {
 
     println!("GROUP BY Status:");
     println!("SQL: {}", sql);
-    println!("Params: {:?}\n", where_values);
+    println!("Params: {:?}", where_values);
+    for row in order_store.find_rows(query).await? {
+        println!("  {:?}", row);
+    }
+    println!();
 
     // Group by user with order statistics
     let query = QueryBuilder::new()
@@ -395,11 +401,17 @@ async fn demo_group_by_queries(_pool: &PgPool) -> Result<(), Box<dyn Error>> {
     println!("GROUP BY User:");
     println!("SQL: {}", sql);
     println!("Params: {:?}", where_values);
+    for row in user_store.find_rows(query).await? {
+        println!("  {:?}", row);
+    }
 
     Ok(())
 }
 
-async fn demo_having_queries(_pool: &PgPool) -> Result<(), Box<dyn Error>> {
+async fn demo_having_queries(pool: &PgPool) -> Result<(), Box<dyn Error>> {
+    let user_store = GenericStore::<User>::new(pool.clone(), None, None);
+    let order_store = GenericStore::<Order>::new(pool.clone(), None, None);
+
     // Find users with more than 1 order
     let query = QueryBuilder::new()
         .select_fields(vec![
@@ -428,7 +440,11 @@ async fn demo_having_queries(_pool: &PgPool) -> Result<(), Box<dyn Error>> {
     println!("HAVING Example - Users with > 1 order:");
     println!("SQL: {}", sql);
     println!("WHERE Params: {:?}", where_values);
-    println!("HAVING Params: {:?}\n", having_values);
+    println!("HAVING Params: {:?}", having_values);
+    for row in user_store.find_rows(query).await? {
+        println!("  {:?}", row);
+    }
+    println!();
 
     // Find order statuses with high total revenue
     let query = QueryBuilder::new()
@@ -454,6 +470,9 @@ async fn demo_having_queries(_pool: &PgPool) -> Result<(), Box<dyn Error>> {
     println!("SQL: {}", sql);
     println!("WHERE Params: {:?}", where_values);
     println!("HAVING Params: {:?}", having_values);
+    for row in order_store.find_rows(query).await? {
+        println!("  {:?}", row);
+    }
 
     Ok(())
 }
\ No newline at end of file
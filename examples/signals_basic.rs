@@ -146,18 +146,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!("   Table: {}", event.table_name);
 
                 // Example: Track important status changes
-                if event.table_name == "users" {
-                    if let Some(status) = event.payload.get("status") {
-                        println!("   👤 User status changed: {:?}", status);
-                        // In a real app: send notifications, update permissions, etc.
-                    }
+                if event.table_name == "users"
+                    && let Some(status) = event.payload.get("status")
+                {
+                    println!("   👤 User status changed: {:?}", status);
+                    // In a real app: send notifications, update permissions, etc.
                 }
 
-                if event.table_name == "products" {
-                    if let Some(stock) = event.payload.get("stock") {
-                        println!("   📦 Product stock updated: {:?}", stock);
-                        // In a real app: check low stock alerts, update inventory, etc.
-                    }
+                if event.table_name == "products"
+                    && let Some(stock) = event.payload.get("stock")
+                {
+                    println!("   📦 Product stock updated: {:?}", stock);
+                    // In a real app: check low stock alerts, update inventory, etc.
                 }
             }
             Ok(())
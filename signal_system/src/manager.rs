@@ -47,6 +47,9 @@ struct CallbackMeta {
     created_at: std::time::Instant,
 }
 
+/// Handler invoked for callbacks that fail or time out
+type ErrorHandler = Box<dyn Fn(EventProcessingError) + Send + Sync>;
+
 /// Improved signal manager with resource cleanup
 pub struct SignalManager {
     // Callbacks stored with IDs for removal
@@ -56,7 +59,7 @@ pub struct SignalManager {
     // Configuration
     config: SignalConfig,
     // Error handler
-    error_handler: Arc<RwLock<Option<Box<dyn Fn(EventProcessingError) + Send + Sync>>>>,
+    error_handler: Arc<RwLock<Option<ErrorHandler>>>,
     // Cleanup task handle
     cleanup_handle: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
 }
@@ -145,6 +148,14 @@ impl SignalManager {
 
     /// Emit event with timeout and resource management
     pub async fn emit(&self, event: DatabaseEvent) {
+        self.try_emit(event).await;
+    }
+
+    /// Emit event and report whether every callback succeeded
+    ///
+    /// Failures are still passed to the error handler; the result lets callers
+    /// such as the event outbox retry delivery later.
+    pub async fn try_emit(&self, event: DatabaseEvent) -> bool {
         // First, collect all callbacks and prepare them for execution
         let callback_futures = {
             let mut callbacks = self.callbacks.write().await;
@@ -173,6 +184,7 @@ impl SignalManager {
         // Process results and update metadata
         let mut callbacks = self.callbacks.write().await;
         let mut ids_to_remove = Vec::new();
+        let all_succeeded = results.iter().all(|(_, result)| matches!(result, Ok(Ok(()))));
 
         for (id, result) in results {
             if let Some(meta) = callbacks.get_mut(&id) {
@@ -241,6 +253,8 @@ impl SignalManager {
         for id in ids_to_remove {
             callbacks.remove(&id);
         }
+
+        all_succeeded
     }
 
     /// Set error handler for failed callbacks
@@ -9,12 +9,14 @@ use std::time::Duration;
 use store_object::traits::StoreObject;
 
 use crate::errors::StoreHausError;
+use crate::unit_of_work::TransactionRetry;
 use config::DatabaseConfig;
 
 /// Main StoreHaus coordinator that manages database connection and store objects
 pub struct StoreHaus {
     pool: PgPool,
     stores: HashMap<String, Box<dyn std::any::Any + Send + Sync>>,
+    transaction_retry: TransactionRetry,
 }
 
 impl StoreHaus {
@@ -38,14 +40,34 @@ impl StoreHaus {
         Ok(Self {
             pool,
             stores: HashMap::new(),
+            transaction_retry: TransactionRetry::default(),
         })
     }
 
+    /// Create StoreHaus from an existing connection pool
+    pub fn from_pool(pool: PgPool) -> Self {
+        Self {
+            pool,
+            stores: HashMap::new(),
+            transaction_retry: TransactionRetry::default(),
+        }
+    }
+
     /// Get database pool reference
     pub fn pool(&self) -> &PgPool {
         &self.pool
     }
 
+    /// Set how `transaction` retries units of work after transient failures
+    pub fn set_transaction_retry(&mut self, retry: TransactionRetry) {
+        self.transaction_retry = retry;
+    }
+
+    /// Retry policy used by `transaction`
+    pub fn transaction_retry(&self) -> TransactionRetry {
+        self.transaction_retry
+    }
+
     /// Register a store object with a given name
     pub fn register_store<T>(&mut self, name: String, store: T) -> Result<(), StoreHausError>
     where
@@ -14,4 +14,13 @@ pub enum StoreHausError {
 
     #[error("Store object already registered: {0}")]
     StoreAlreadyRegistered(String),
+
+    #[error("Invalid migration: {0}")]
+    InvalidMigration(String),
+
+    #[error("Checksum mismatch for applied migration {version} ({name})")]
+    MigrationChecksumMismatch { version: i64, name: String },
+
+    #[error("Migration {0} has no down script and cannot be rolled back")]
+    IrreversibleMigration(i64),
 }
@@ -87,14 +87,19 @@ macro_rules! trace_log {
 pub mod core;
 pub mod errors;
 pub mod migration;
+pub mod migrator;
 pub mod prelude;
+pub mod unit_of_work;
 
 // Re-export the main public types for convenience
 pub use core::StoreHaus;
 pub use errors::StoreHausError;
+pub use migration::MigrationPlan;
+pub use migrator::{Migration, Migrator};
+pub use unit_of_work::{TransactionRetry, UnitOfWork, UnitOfWorkFuture};
 
 // Re-export centralized config
-pub use config::{AppConfig, CacheConfig, DatabaseConfig, SignalConfig};
+pub use config::{AppConfig, CacheBackendKind, CacheConfig, DatabaseConfig, SignalConfig};
 
 // Re-export internal crates used by macros and public API
 // These MUST be public for the generated macro code to work correctly
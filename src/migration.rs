@@ -2,14 +2,264 @@
 //!
 //! This module provides automatic table creation and migration utilities
 //! for StoreObject types managed by StoreHaus.
+//!
+//! For tables that already exist, `auto_migrate` compares the live schema
+//! (from `information_schema.columns`) with the model's declared columns and
+//! applies the `ALTER TABLE` statements needed to bring them in line.
+//! Columns present in the database but not in the model are never dropped.
+//!
+//! Changes that cannot be applied safely are refused with
+//! `StoreHausError::InvalidMigration` instead of failing halfway: adding a
+//! primary key column, or a NOT NULL column without a default to a table that
+//! already has rows. Such changes belong in a versioned migration (`Migrator`)
+//! that adds the column as nullable, backfills it and then sets NOT NULL.
 
-use crate::{core::StoreHaus, debug_log};
+use crate::core::StoreHaus;
 use crate::errors::StoreHausError;
+use sqlx::Row;
+use store_object::ColumnDefinition;
 use store_object::traits::{StoreObject, TableMetadata};
 
+/// Column as currently defined in the database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExistingColumn {
+    /// Column name
+    pub name: String,
+    /// Normalized PostgreSQL type (lowercase, e.g. `numeric(28,10)`, `text[]`)
+    pub pg_type: String,
+    /// Whether the column accepts NULL values
+    pub nullable: bool,
+}
+
+/// Schema changes planned for a single table
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationPlan {
+    /// Table the plan applies to
+    pub table_name: String,
+    /// True when the table does not exist yet and will be created
+    pub creates_table: bool,
+    /// SQL statements to execute, in order
+    pub statements: Vec<String>,
+    /// Columns present in the database but not declared by the model (left untouched)
+    pub unmanaged_columns: Vec<String>,
+}
+
+impl MigrationPlan {
+    /// True when the table already matches the model
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+}
+
+/// Compare declared model columns with the live table and plan the ALTER statements
+///
+/// `has_rows` tells whether the table holds data, which NOT NULL columns
+/// without a default cannot be added to.
+pub fn diff_columns(
+    table_name: &str,
+    expected: &[ColumnDefinition],
+    existing: &[ExistingColumn],
+    has_rows: bool,
+) -> Result<MigrationPlan, StoreHausError> {
+    let quoted_table = quote_identifier(table_name);
+    let mut plan = MigrationPlan {
+        table_name: table_name.to_string(),
+        ..Default::default()
+    };
+
+    for column in expected {
+        let quoted_column = quote_identifier(column.name);
+        let ddl_type = ddl_type(column.pg_type);
+
+        let Some(current) = existing.iter().find(|c| c.name == column.name) else {
+            if column.primary_key {
+                return Err(StoreHausError::InvalidMigration(format!(
+                    "primary key column \"{}\" does not exist in table \"{}\"; \
+                     changing the primary key needs a versioned migration",
+                    column.name, table_name
+                )));
+            }
+            if !column.nullable && column.default.is_none() && has_rows {
+                return Err(StoreHausError::InvalidMigration(format!(
+                    "cannot add NOT NULL column \"{}\" without a default to non-empty \
+                     table \"{}\"; make the field optional or add it with a versioned \
+                     migration (add as nullable, backfill, then SET NOT NULL)",
+                    column.name, table_name
+                )));
+            }
+            let mut definition = format!(
+                "ALTER TABLE {} ADD COLUMN {} {}",
+                quoted_table, quoted_column, column.pg_type
+            );
+            if !column.nullable {
+                definition.push_str(" NOT NULL");
+            }
+            if let Some(default) = column.default {
+                definition.push_str(&format!(" DEFAULT {}", default));
+            }
+            plan.statements.push(definition);
+            continue;
+        };
+
+        if normalize_pg_type(column.pg_type) != current.pg_type {
+            plan.statements.push(format!(
+                "ALTER TABLE {} ALTER COLUMN {} TYPE {} USING {}::{}",
+                quoted_table, quoted_column, ddl_type, quoted_column, ddl_type
+            ));
+        }
+
+        // Primary keys are implicitly NOT NULL
+        if column.primary_key {
+            continue;
+        }
+        if column.nullable && !current.nullable {
+            plan.statements.push(format!(
+                "ALTER TABLE {} ALTER COLUMN {} DROP NOT NULL",
+                quoted_table, quoted_column
+            ));
+        } else if !column.nullable && current.nullable {
+            plan.statements.push(format!(
+                "ALTER TABLE {} ALTER COLUMN {} SET NOT NULL",
+                quoted_table, quoted_column
+            ));
+        }
+    }
+
+    plan.unmanaged_columns = existing
+        .iter()
+        .filter(|c| !expected.iter().any(|e| e.name == c.name))
+        .map(|c| c.name.clone())
+        .collect();
+
+    Ok(plan)
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Type usable in `ALTER COLUMN ... TYPE` (serial pseudo-types are not allowed there)
+fn ddl_type(pg_type: &str) -> &str {
+    match pg_type.trim().to_uppercase().as_str() {
+        "SMALLSERIAL" => "SMALLINT",
+        "SERIAL" => "INTEGER",
+        "BIGSERIAL" => "BIGINT",
+        _ => pg_type,
+    }
+}
+
+/// Normalize a declared type to the spelling used when reading `information_schema`
+fn normalize_pg_type(pg_type: &str) -> String {
+    let lowered = pg_type.trim().to_lowercase();
+    let collapsed = lowered.split_whitespace().collect::<Vec<_>>().join(" ");
+    let collapsed = collapsed.replace(", ", ",");
+
+    match collapsed.as_str() {
+        "smallserial" | "int2" => "smallint".to_string(),
+        "serial" | "int" | "int4" => "integer".to_string(),
+        "bigserial" | "int8" => "bigint".to_string(),
+        "bool" => "boolean".to_string(),
+        "float4" => "real".to_string(),
+        "float8" => "double precision".to_string(),
+        "varchar" => "character varying".to_string(),
+        "timestamptz" => "timestamp with time zone".to_string(),
+        _ => collapsed,
+    }
+}
+
+/// Build the normalized type of an existing column from `information_schema.columns`
+fn existing_column_type(
+    data_type: &str,
+    udt_name: &str,
+    numeric_precision: Option<i32>,
+    numeric_scale: Option<i32>,
+) -> String {
+    match data_type {
+        "ARRAY" => format!("{}[]", normalize_pg_type(udt_name.trim_start_matches('_'))),
+        "numeric" => match (numeric_precision, numeric_scale) {
+            (Some(precision), Some(scale)) => format!("numeric({},{})", precision, scale),
+            _ => "numeric".to_string(),
+        },
+        "USER-DEFINED" => udt_name.to_lowercase(),
+        other => normalize_pg_type(other),
+    }
+}
+
 impl StoreHaus {
+    /// Load the current column layout of a table
+    /// Returns an empty list when the table does not exist
+    pub async fn existing_columns(
+        &self,
+        table_name: &str,
+    ) -> Result<Vec<ExistingColumn>, StoreHausError> {
+        let rows = sqlx::query(
+            "SELECT column_name::text, data_type::text, udt_name::text, is_nullable::text, \
+                    numeric_precision::int4, numeric_scale::int4 \
+             FROM information_schema.columns \
+             WHERE table_schema = current_schema() AND table_name = $1 \
+             ORDER BY ordinal_position",
+        )
+        .bind(table_name)
+        .fetch_all(self.pool())
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let data_type: String = row.try_get("data_type")?;
+                let udt_name: String = row.try_get("udt_name")?;
+                let is_nullable: String = row.try_get("is_nullable")?;
+                Ok(ExistingColumn {
+                    name: row.try_get("column_name")?,
+                    pg_type: existing_column_type(
+                        &data_type,
+                        &udt_name,
+                        row.try_get("numeric_precision")?,
+                        row.try_get("numeric_scale")?,
+                    ),
+                    nullable: is_nullable == "YES",
+                })
+            })
+            .collect()
+    }
+
+    /// Compute the schema changes `auto_migrate` would apply, without executing them
+    pub async fn plan_migration<T>(&self) -> Result<MigrationPlan, StoreHausError>
+    where
+        T: TableMetadata + Send + Sync,
+    {
+        let table_name = T::table_name();
+        let existing = self.existing_columns(table_name).await?;
+        let expected = T::get_table_columns();
+
+        if existing.is_empty() {
+            return Ok(MigrationPlan {
+                table_name: table_name.to_string(),
+                creates_table: true,
+                statements: vec![T::create_table_sql()],
+                unmanaged_columns: Vec::new(),
+            });
+        }
+
+        // Models without column definitions only support create-if-missing
+        if expected.is_empty() {
+            return Ok(MigrationPlan {
+                table_name: table_name.to_string(),
+                ..Default::default()
+            });
+        }
+
+        let has_rows: bool = sqlx::query_scalar(&format!(
+            "SELECT EXISTS (SELECT 1 FROM {})",
+            quote_identifier(table_name)
+        ))
+        .fetch_one(self.pool())
+        .await?;
+        diff_columns(table_name, &expected, &existing, has_rows)
+    }
+
     /// Automatically create table and indexes for a model
-    /// If recreate is true, drops existing table first
+    /// If recreate is true, drops existing table first.
+    /// Existing tables are altered to match the model (see `plan_migration`)
     pub async fn auto_migrate<T>(&self, recreate: bool) -> Result<(), StoreHausError>
     where
         T: TableMetadata + Send + Sync,
@@ -23,10 +273,16 @@ impl StoreHaus {
             sqlx::query(&drop_sql).execute(self.pool()).await?;
         }
 
-        // Create the table
-        let create_table_sql = T::create_table_sql();
-        debug_log!("Creating table with SQL: {}", create_table_sql);
-        sqlx::query(&create_table_sql).execute(self.pool()).await?;
+        // Create the table or bring its columns in line with the model
+        let plan = self.plan_migration::<T>().await?;
+        if !plan.is_empty() {
+            let mut tx = self.pool().begin().await?;
+            for statement in &plan.statements {
+                debug_log!("Applying migration SQL: {}", statement);
+                sqlx::query(statement).execute(&mut *tx).await?;
+            }
+            tx.commit().await?;
+        }
 
         // Create __updated_at__ trigger function if it doesn't exist
         let trigger_function_sql = r#"
@@ -89,3 +345,179 @@ impl StoreHaus {
         self.register_store(name, store)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &'static str, pg_type: &'static str, nullable: bool) -> ColumnDefinition {
+        ColumnDefinition {
+            name,
+            pg_type,
+            nullable,
+            default: None,
+            primary_key: false,
+        }
+    }
+
+    fn existing(name: &str, pg_type: &str, nullable: bool) -> ExistingColumn {
+        ExistingColumn {
+            name: name.to_string(),
+            pg_type: pg_type.to_string(),
+            nullable,
+        }
+    }
+
+    #[test]
+    fn test_diff_matching_schema_is_empty() {
+        let expected = vec![
+            ColumnDefinition {
+                primary_key: true,
+                ..column("id", "SERIAL", false)
+            },
+            column("price", "NUMERIC(28,10)", false),
+            column("labels", "TEXT[]", false),
+            column("created", "TIMESTAMP WITH TIME ZONE", true),
+        ];
+        let current = vec![
+            existing("id", "integer", false),
+            existing("price", "numeric(28,10)", false),
+            existing("labels", "text[]", false),
+            existing("created", "timestamp with time zone", true),
+        ];
+
+        let plan = diff_columns("products", &expected, &current, true).unwrap();
+        assert!(
+            plan.is_empty(),
+            "unexpected statements: {:?}",
+            plan.statements
+        );
+        assert!(plan.unmanaged_columns.is_empty());
+    }
+
+    #[test]
+    fn test_diff_adds_missing_columns() {
+        let expected = vec![
+            column("name", "TEXT", false),
+            ColumnDefinition {
+                default: Some("TRUE"),
+                ..column("is_active", "BOOLEAN", true)
+            },
+        ];
+        let current = vec![existing("name", "text", false)];
+
+        let plan = diff_columns("users", &expected, &current, true).unwrap();
+        assert_eq!(
+            plan.statements,
+            vec![r#"ALTER TABLE "users" ADD COLUMN "is_active" BOOLEAN DEFAULT TRUE"#]
+        );
+    }
+
+    #[test]
+    fn test_diff_refuses_not_null_column_without_default_on_filled_table() {
+        let expected = vec![
+            column("name", "TEXT", false),
+            column("email", "TEXT", false),
+        ];
+        let current = vec![existing("name", "text", false)];
+
+        assert!(matches!(
+            diff_columns("users", &expected, &current, true),
+            Err(StoreHausError::InvalidMigration(message)) if message.contains("\"email\"")
+        ));
+
+        // An empty table has no rows that would violate the constraint
+        let plan = diff_columns("users", &expected, &current, false).unwrap();
+        assert_eq!(
+            plan.statements,
+            vec![r#"ALTER TABLE "users" ADD COLUMN "email" TEXT NOT NULL"#]
+        );
+
+        // A default fills existing rows
+        let expected = vec![
+            column("name", "TEXT", false),
+            ColumnDefinition {
+                default: Some("''"),
+                ..column("email", "TEXT", false)
+            },
+        ];
+        let plan = diff_columns("users", &expected, &current, true).unwrap();
+        assert_eq!(
+            plan.statements,
+            vec![r#"ALTER TABLE "users" ADD COLUMN "email" TEXT NOT NULL DEFAULT ''"#]
+        );
+    }
+
+    #[test]
+    fn test_diff_refuses_new_primary_key() {
+        let expected = vec![
+            ColumnDefinition {
+                primary_key: true,
+                ..column("uid", "UUID", false)
+            },
+            column("name", "TEXT", false),
+        ];
+        let current = vec![
+            existing("id", "integer", false),
+            existing("name", "text", false),
+        ];
+
+        for has_rows in [true, false] {
+            assert!(matches!(
+                diff_columns("users", &expected, &current, has_rows),
+                Err(StoreHausError::InvalidMigration(message)) if message.contains("primary key")
+            ));
+        }
+    }
+
+    #[test]
+    fn test_diff_changes_type_and_nullability() {
+        let expected = vec![column("age", "BIGINT", false), column("bio", "TEXT", true)];
+        let current = vec![
+            existing("age", "integer", true),
+            existing("bio", "text", false),
+        ];
+
+        let plan = diff_columns("users", &expected, &current, true).unwrap();
+        assert_eq!(
+            plan.statements,
+            vec![
+                r#"ALTER TABLE "users" ALTER COLUMN "age" TYPE BIGINT USING "age"::BIGINT"#,
+                r#"ALTER TABLE "users" ALTER COLUMN "age" SET NOT NULL"#,
+                r#"ALTER TABLE "users" ALTER COLUMN "bio" DROP NOT NULL"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_reports_unmanaged_columns() {
+        let expected = vec![column("name", "TEXT", false)];
+        let current = vec![
+            existing("name", "text", false),
+            existing("legacy", "text", true),
+        ];
+
+        let plan = diff_columns("users", &expected, &current, true).unwrap();
+        assert!(plan.is_empty());
+        assert_eq!(plan.unmanaged_columns, vec!["legacy".to_string()]);
+    }
+
+    #[test]
+    fn test_existing_column_type_normalization() {
+        assert_eq!(existing_column_type("ARRAY", "_text", None, None), "text[]");
+        assert_eq!(
+            existing_column_type("ARRAY", "_int4", None, None),
+            "integer[]"
+        );
+        assert_eq!(
+            existing_column_type("numeric", "numeric", Some(20), Some(0)),
+            "numeric(20,0)"
+        );
+        assert_eq!(
+            existing_column_type("numeric", "numeric", None, None),
+            "numeric"
+        );
+        assert_eq!(normalize_pg_type("NUMERIC(20, 0)"), "numeric(20,0)");
+        assert_eq!(normalize_pg_type("VARCHAR"), "character varying");
+    }
+}
@@ -0,0 +1,514 @@
+//! Versioned migration runner
+//!
+//! Complements `auto_migrate` with explicit, ordered migrations. Each applied
+//! migration is recorded in the `storehaus_migrations` table together with a
+//! checksum of its SQL, so every environment keeps a record of the DDL that
+//! has run against it. Pending migrations are applied in a single transaction
+//! guarded by an advisory lock, and can be reverted through their down scripts.
+
+use crate::core::StoreHaus;
+use crate::errors::StoreHausError;
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::PgConnection;
+use std::fmt;
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Table used to record applied migrations
+pub const MIGRATIONS_TABLE: &str = "storehaus_migrations";
+
+/// Advisory lock key serializing migration runs across processes
+const MIGRATION_LOCK_KEY: i64 = 0x5354_4f52_4548_4155;
+
+/// Future returned by Rust migration closures
+pub type MigrationFuture<'c> =
+    Pin<Box<dyn Future<Output = Result<(), StoreHausError>> + Send + 'c>>;
+
+type MigrationFn = Arc<dyn for<'c> Fn(&'c mut PgConnection) -> MigrationFuture<'c> + Send + Sync>;
+
+/// A single migration direction
+#[derive(Clone)]
+enum MigrationStep {
+    Sql(String),
+    Rust(MigrationFn),
+}
+
+impl MigrationStep {
+    async fn run(&self, conn: &mut PgConnection) -> Result<(), StoreHausError> {
+        match self {
+            MigrationStep::Sql(sql) => {
+                debug_log!("Running migration SQL: {}", sql);
+                sqlx::raw_sql(sql).execute(&mut *conn).await?;
+                Ok(())
+            }
+            MigrationStep::Rust(step) => step(conn).await,
+        }
+    }
+}
+
+/// Versioned migration with an up step and an optional down step
+#[derive(Clone)]
+pub struct Migration {
+    version: i64,
+    name: String,
+    up: MigrationStep,
+    down: Option<MigrationStep>,
+}
+
+impl Migration {
+    /// Create a migration from SQL (may contain several statements)
+    pub fn sql(version: i64, name: impl Into<String>, up: impl Into<String>) -> Self {
+        Self {
+            version,
+            name: name.into(),
+            up: MigrationStep::Sql(up.into()),
+            down: None,
+        }
+    }
+
+    /// Create a migration from a Rust closure running on the migration transaction
+    ///
+    /// ```ignore
+    /// Migration::rust(3, "backfill_slugs", |conn| {
+    ///     Box::pin(async move {
+    ///         sqlx::query("UPDATE posts SET slug = lower(title)")
+    ///             .execute(&mut *conn)
+    ///             .await?;
+    ///         Ok(())
+    ///     })
+    /// })
+    /// ```
+    pub fn rust<F>(version: i64, name: impl Into<String>, up: F) -> Self
+    where
+        F: for<'c> Fn(&'c mut PgConnection) -> MigrationFuture<'c> + Send + Sync + 'static,
+    {
+        Self {
+            version,
+            name: name.into(),
+            up: MigrationStep::Rust(Arc::new(up)),
+            down: None,
+        }
+    }
+
+    /// Set the SQL used to roll this migration back
+    pub fn with_down_sql(mut self, down: impl Into<String>) -> Self {
+        self.down = Some(MigrationStep::Sql(down.into()));
+        self
+    }
+
+    /// Set a Rust closure used to roll this migration back
+    pub fn with_down<F>(mut self, down: F) -> Self
+    where
+        F: for<'c> Fn(&'c mut PgConnection) -> MigrationFuture<'c> + Send + Sync + 'static,
+    {
+        self.down = Some(MigrationStep::Rust(Arc::new(down)));
+        self
+    }
+
+    /// Migration version (applied in ascending order)
+    pub fn version(&self) -> i64 {
+        self.version
+    }
+
+    /// Human readable migration name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether the migration has a down step
+    pub fn is_reversible(&self) -> bool {
+        self.down.is_some()
+    }
+
+    /// SHA-256 of the up SQL; `None` for Rust migrations
+    pub fn checksum(&self) -> Option<String> {
+        match &self.up {
+            MigrationStep::Sql(sql) => Some(
+                Sha256::digest(sql.as_bytes())
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect(),
+            ),
+            MigrationStep::Rust(_) => None,
+        }
+    }
+}
+
+impl fmt::Debug for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Migration")
+            .field("version", &self.version)
+            .field("name", &self.name)
+            .field("checksum", &self.checksum())
+            .field("reversible", &self.is_reversible())
+            .finish()
+    }
+}
+
+/// Migration record stored in `storehaus_migrations`
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::FromRow)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub name: String,
+    pub checksum: Option<String>,
+    pub applied_at: DateTime<Utc>,
+}
+
+/// Ordered set of migrations
+#[derive(Debug, Clone, Default)]
+pub struct Migrator {
+    migrations: Vec<Migration>,
+}
+
+impl Migrator {
+    /// Create an empty migrator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a migration; migrations are kept ordered by version
+    pub fn add_migration(mut self, migration: Migration) -> Self {
+        self.migrations.push(migration);
+        self.migrations.sort_by_key(|m| m.version);
+        self
+    }
+
+    /// Load SQL migrations from a directory
+    ///
+    /// Files are named `<version>_<name>.sql` (or `.up.sql`), with optional
+    /// `<version>_<name>.down.sql` rollback scripts next to them.
+    pub fn from_directory(path: impl AsRef<Path>) -> Result<Self, StoreHausError> {
+        let path = path.as_ref();
+        let entries = std::fs::read_dir(path)
+            .map_err(|e| StoreHausError::InvalidMigration(format!("{}: {}", path.display(), e)))?;
+
+        let mut ups: Vec<(i64, String, String)> = Vec::new();
+        let mut downs: Vec<(i64, String)> = Vec::new();
+
+        for entry in entries {
+            let file_path = entry
+                .map_err(|e| {
+                    StoreHausError::InvalidMigration(format!("{}: {}", path.display(), e))
+                })?
+                .path();
+            let Some(file_name) = file_path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let Some(stem) = file_name.strip_suffix(".sql") else {
+                continue;
+            };
+
+            let (stem, is_down) = match stem.strip_suffix(".down") {
+                Some(stem) => (stem, true),
+                None => (stem.strip_suffix(".up").unwrap_or(stem), false),
+            };
+            let (version, name) = parse_migration_stem(stem).ok_or_else(|| {
+                StoreHausError::InvalidMigration(format!(
+                    "{}: expected <version>_<name>.sql",
+                    file_path.display()
+                ))
+            })?;
+
+            let sql = std::fs::read_to_string(&file_path).map_err(|e| {
+                StoreHausError::InvalidMigration(format!("{}: {}", file_path.display(), e))
+            })?;
+
+            if is_down {
+                downs.push((version, sql));
+            } else {
+                ups.push((version, name.to_string(), sql));
+            }
+        }
+
+        let mut migrator = Self::new();
+        for (version, name, sql) in ups {
+            let mut migration = Migration::sql(version, name, sql);
+            if let Some(index) = downs.iter().position(|(v, _)| *v == version) {
+                migration = migration.with_down_sql(downs.swap_remove(index).1);
+            }
+            migrator = migrator.add_migration(migration);
+        }
+
+        if let Some((version, _)) = downs.first() {
+            return Err(StoreHausError::InvalidMigration(format!(
+                "down script for version {} has no matching up script",
+                version
+            )));
+        }
+
+        Ok(migrator)
+    }
+
+    /// All migrations, ordered by version
+    pub fn migrations(&self) -> &[Migration] {
+        &self.migrations
+    }
+
+    fn find(&self, version: i64) -> Option<&Migration> {
+        self.migrations.iter().find(|m| m.version == version)
+    }
+
+    fn validate(&self) -> Result<(), StoreHausError> {
+        for pair in self.migrations.windows(2) {
+            if pair[0].version == pair[1].version {
+                return Err(StoreHausError::InvalidMigration(format!(
+                    "duplicate migration version {}",
+                    pair[0].version
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_migration_stem(stem: &str) -> Option<(i64, &str)> {
+    let (version, name) = stem.split_once('_')?;
+    if name.is_empty() {
+        return None;
+    }
+    Some((version.parse().ok()?, name))
+}
+
+/// Verify that applied migrations still match their definitions
+fn verify_checksums(
+    migrator: &Migrator,
+    applied: &[AppliedMigration],
+) -> Result<(), StoreHausError> {
+    for record in applied {
+        let Some(migration) = migrator.find(record.version) else {
+            continue;
+        };
+        if let (Some(expected), Some(stored)) = (migration.checksum(), &record.checksum)
+            && &expected != stored
+        {
+            return Err(StoreHausError::MigrationChecksumMismatch {
+                version: record.version,
+                name: record.name.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+async fn lock_and_load(conn: &mut PgConnection) -> Result<Vec<AppliedMigration>, StoreHausError> {
+    sqlx::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(MIGRATION_LOCK_KEY)
+        .execute(&mut *conn)
+        .await?;
+
+    sqlx::query(&format!(
+        "CREATE TABLE IF NOT EXISTS {} (
+            version BIGINT PRIMARY KEY,
+            name TEXT NOT NULL,
+            checksum TEXT,
+            applied_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+        )",
+        MIGRATIONS_TABLE
+    ))
+    .execute(&mut *conn)
+    .await?;
+
+    let applied = sqlx::query_as::<_, AppliedMigration>(&format!(
+        "SELECT version, name, checksum, applied_at FROM {} ORDER BY version",
+        MIGRATIONS_TABLE
+    ))
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(applied)
+}
+
+impl StoreHaus {
+    /// List migrations recorded as applied, ordered by version
+    pub async fn applied_migrations(&self) -> Result<Vec<AppliedMigration>, StoreHausError> {
+        let mut tx = self.pool().begin().await?;
+        let applied = lock_and_load(&mut tx).await?;
+        tx.commit().await?;
+        Ok(applied)
+    }
+
+    /// List versions of migrations that have not been applied yet
+    pub async fn pending_migrations(
+        &self,
+        migrator: &Migrator,
+    ) -> Result<Vec<i64>, StoreHausError> {
+        let applied = self.applied_migrations().await?;
+        Ok(migrator
+            .migrations()
+            .iter()
+            .filter(|m| !applied.iter().any(|a| a.version == m.version))
+            .map(|m| m.version)
+            .collect())
+    }
+
+    /// Apply all pending migrations in one transaction
+    /// Returns the versions applied, in order
+    pub async fn run_migrations(&self, migrator: &Migrator) -> Result<Vec<i64>, StoreHausError> {
+        migrator.validate()?;
+
+        let mut tx = self.pool().begin().await?;
+        let applied = lock_and_load(&mut tx).await?;
+        verify_checksums(migrator, &applied)?;
+
+        let mut applied_now = Vec::new();
+        for migration in migrator.migrations() {
+            if applied.iter().any(|a| a.version == migration.version) {
+                continue;
+            }
+
+            debug_log!(
+                "Applying migration {} ({})",
+                migration.version,
+                migration.name
+            );
+            migration.up.run(&mut tx).await?;
+
+            sqlx::query(&format!(
+                "INSERT INTO {} (version, name, checksum) VALUES ($1, $2, $3)",
+                MIGRATIONS_TABLE
+            ))
+            .bind(migration.version)
+            .bind(&migration.name)
+            .bind(migration.checksum())
+            .execute(&mut *tx)
+            .await?;
+
+            applied_now.push(migration.version);
+        }
+
+        tx.commit().await?;
+        Ok(applied_now)
+    }
+
+    /// Roll back the most recently applied `steps` migrations using their down steps
+    /// Returns the versions rolled back, newest first
+    pub async fn rollback_migrations(
+        &self,
+        migrator: &Migrator,
+        steps: usize,
+    ) -> Result<Vec<i64>, StoreHausError> {
+        migrator.validate()?;
+
+        let mut tx = self.pool().begin().await?;
+        let applied = lock_and_load(&mut tx).await?;
+        verify_checksums(migrator, &applied)?;
+
+        let mut rolled_back = Vec::new();
+        for record in applied.iter().rev().take(steps) {
+            let down = migrator
+                .find(record.version)
+                .and_then(|m| m.down.as_ref())
+                .ok_or(StoreHausError::IrreversibleMigration(record.version))?;
+
+            debug_log!(
+                "Rolling back migration {} ({})",
+                record.version,
+                record.name
+            );
+            down.run(&mut tx).await?;
+
+            sqlx::query(&format!(
+                "DELETE FROM {} WHERE version = $1",
+                MIGRATIONS_TABLE
+            ))
+            .bind(record.version)
+            .execute(&mut *tx)
+            .await?;
+
+            rolled_back.push(record.version);
+        }
+
+        tx.commit().await?;
+        Ok(rolled_back)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrator_orders_by_version() {
+        let migrator = Migrator::new()
+            .add_migration(Migration::sql(3, "third", "SELECT 3"))
+            .add_migration(Migration::sql(1, "first", "SELECT 1"))
+            .add_migration(Migration::sql(2, "second", "SELECT 2"));
+
+        let versions: Vec<i64> = migrator.migrations().iter().map(|m| m.version()).collect();
+        assert_eq!(versions, vec![1, 2, 3]);
+        assert!(migrator.validate().is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_versions_rejected() {
+        let migrator = Migrator::new()
+            .add_migration(Migration::sql(1, "a", "SELECT 1"))
+            .add_migration(Migration::sql(1, "b", "SELECT 2"));
+
+        assert!(matches!(
+            migrator.validate(),
+            Err(StoreHausError::InvalidMigration(_))
+        ));
+    }
+
+    #[test]
+    fn test_checksum_tracks_sql() {
+        let a = Migration::sql(1, "a", "CREATE TABLE a (id INT)");
+        let b = Migration::sql(1, "a", "CREATE TABLE a (id BIGINT)");
+        let rust = Migration::rust(2, "noop", |_conn| Box::pin(async { Ok(()) }));
+
+        assert_eq!(a.checksum().map(|c| c.len()), Some(64));
+        assert_ne!(a.checksum(), b.checksum());
+        assert_eq!(rust.checksum(), None);
+    }
+
+    #[test]
+    fn test_verify_checksums_detects_edits() {
+        let migrator = Migrator::new().add_migration(Migration::sql(1, "a", "SELECT 1"));
+        let record = AppliedMigration {
+            version: 1,
+            name: "a".to_string(),
+            checksum: Some("stale".to_string()),
+            applied_at: Utc::now(),
+        };
+
+        assert!(matches!(
+            verify_checksums(&migrator, &[record]),
+            Err(StoreHausError::MigrationChecksumMismatch { version: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_directory() {
+        let dir = std::env::temp_dir().join(format!("storehaus_migrations_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("2_add_index.sql"), "CREATE INDEX i ON t (c)").unwrap();
+        std::fs::write(dir.join("1_create_table.up.sql"), "CREATE TABLE t (c INT)").unwrap();
+        std::fs::write(dir.join("1_create_table.down.sql"), "DROP TABLE t").unwrap();
+        std::fs::write(dir.join("README.md"), "ignored").unwrap();
+
+        let migrator = Migrator::from_directory(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let migrations = migrator.migrations();
+        assert_eq!(migrations.len(), 2);
+        assert_eq!(migrations[0].name(), "create_table");
+        assert!(migrations[0].is_reversible());
+        assert_eq!(migrations[1].version(), 2);
+        assert!(!migrations[1].is_reversible());
+    }
+
+    #[test]
+    fn test_parse_migration_stem() {
+        assert_eq!(
+            parse_migration_stem("20240101_init"),
+            Some((20240101, "init"))
+        );
+        assert_eq!(parse_migration_stem("init"), None);
+        assert_eq!(parse_migration_stem("v1_init"), None);
+        assert_eq!(parse_migration_stem("1_"), None);
+    }
+}
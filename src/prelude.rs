@@ -15,6 +15,8 @@
 pub use crate::core::StoreHaus;
 pub use crate::errors::StoreHausError;
 pub use crate::migration;
+pub use crate::migrator::{Migration, MigrationFuture, Migrator};
+pub use crate::unit_of_work::{TransactionRetry, UnitOfWork, UnitOfWorkFuture};
 
 // Re-export centralized config
 pub use config::{AppConfig, CacheConfig, DatabaseConfig, SignalConfig};
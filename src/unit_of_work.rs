@@ -0,0 +1,326 @@
+//! Cross-store units of work
+//!
+//! `StoreHaus::transaction` runs writes of several registered stores in one
+//! PostgreSQL transaction. The transaction commits when the closure succeeds
+//! and rolls back when it fails; signals and cache updates of all stores are
+//! applied only after the commit. Serialization failures and deadlocks, and
+//! connection failures before the transaction began, retry the whole closure.
+//! A failed COMMIT is never retried unless PostgreSQL reports that it rolled
+//! back, since the first commit may already have been applied.
+
+use crate::core::StoreHaus;
+use sqlx::{Postgres, Transaction};
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use store_object::errors::StorehausError;
+use store_object::generic_store::{
+    GenericStore, PendingEffects, Savepoint, TransactionOptions, TransactionalStore,
+};
+use store_object::traits::TableMetadata;
+use store_object::traits::table_metadata::DatabaseExecutor;
+
+/// Future returned by unit of work closures
+pub type UnitOfWorkFuture<'u, R> =
+    Pin<Box<dyn Future<Output = Result<R, StorehausError>> + Send + 'u>>;
+
+/// How often `StoreHaus::transaction` retries after a transient failure
+///
+/// Retries back off exponentially from `base_delay` up to `max_delay`, with
+/// random jitter so that conflicting transactions don't retry in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionRetry {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for TransactionRetry {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+impl TransactionRetry {
+    /// Retry up to `max_attempts` attempts in total
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            ..Self::default()
+        }
+    }
+
+    /// Run every unit of work exactly once
+    pub fn disabled() -> Self {
+        Self::new(1)
+    }
+
+    /// Set the delay before the first retry
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Set the upper bound of the delay between attempts
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Upper bound of the delay after the given failed attempt (starting at 1)
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// Randomized delay after the given failed attempt
+    fn delay(&self, attempt: u32) -> Duration {
+        let backoff = self.backoff(attempt);
+        backoff / 2 + backoff.mul_f64(rand::random_range(0.0..0.5))
+    }
+}
+
+/// Registered stores bound to one transaction
+///
+/// Created by `StoreHaus::transaction` for every attempt.
+pub struct UnitOfWork<'a> {
+    storehaus: &'a StoreHaus,
+    tx: Transaction<'static, Postgres>,
+    effects: PendingEffects<'a>,
+    attempt: u32,
+}
+
+impl std::fmt::Debug for UnitOfWork<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UnitOfWork")
+            .field("attempt", &self.attempt)
+            .field("effects", &self.effects)
+            .finish()
+    }
+}
+
+impl<'a> UnitOfWork<'a> {
+    /// Registered `GenericStore<T>` bound to this unit of work
+    pub fn store<T>(&mut self, name: &str) -> Result<TransactionalStore<'_, 'a, T>, StorehausError>
+    where
+        T: TableMetadata
+            + DatabaseExecutor
+            + for<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow>
+            + serde::Serialize
+            + Unpin
+            + 'static,
+    {
+        let store = self
+            .storehaus
+            .get_store::<GenericStore<T>>(name)
+            .map_err(|_| StorehausError::InvalidConfiguration {
+                message: format!(
+                    "No GenericStore<{}> registered as '{}'",
+                    std::any::type_name::<T>(),
+                    name
+                ),
+            })?;
+        Ok(TransactionalStore::new(
+            store,
+            &mut self.tx,
+            &mut self.effects,
+        ))
+    }
+
+    /// The underlying transaction, for queries outside the registered stores
+    pub fn transaction(&mut self) -> &mut Transaction<'static, Postgres> {
+        &mut self.tx
+    }
+
+    /// Create a savepoint covering all stores of the unit of work
+    pub async fn savepoint(&mut self) -> Result<Savepoint, StorehausError> {
+        self.effects.savepoint(&mut self.tx).await
+    }
+
+    /// Undo the changes made since the savepoint, with their signals and cache updates
+    pub async fn rollback_to(&mut self, savepoint: &Savepoint) -> Result<(), StorehausError> {
+        self.effects.rollback_to(&mut self.tx, savepoint).await
+    }
+
+    /// Keep the changes made since the savepoint and forget the savepoint
+    pub async fn release(&mut self, savepoint: Savepoint) -> Result<(), StorehausError> {
+        self.effects.release(&mut self.tx, savepoint).await
+    }
+
+    /// Current attempt, starting at 1
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+}
+
+impl StoreHaus {
+    /// Run a unit of work across registered stores in one transaction
+    ///
+    /// The transaction commits when `work` returns `Ok` and rolls back when it
+    /// returns an error. Serialization failures and deadlocks
+    /// (`StorehausError::is_serialization_failure`), and transient errors while
+    /// beginning the transaction, run `work` again according to
+    /// `transaction_retry()`. `work` may therefore be called several times and
+    /// should not have effects outside the unit of work. Any other error,
+    /// including an I/O error during COMMIT whose outcome is unknown, is
+    /// returned unchanged.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let order = storehaus
+    ///     .transaction(|uow| {
+    ///         let order = order.clone();
+    ///         Box::pin(async move {
+    ///             let order = uow.store::<Order>("orders")?.create(order, None).await?;
+    ///             uow.store::<Stock>("stock")?
+    ///                 .update_where(
+    ///                     QueryBuilder::new()
+    ///                         .filter(QueryFilter::eq("sku", json!(order.sku)))
+    ///                         .update(UpdateSet::new().decrement("quantity", json!(1))),
+    ///                     None,
+    ///                 )
+    ///                 .await?;
+    ///             Ok(order)
+    ///         })
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn transaction<'s, R, F>(&'s self, work: F) -> Result<R, StorehausError>
+    where
+        F: for<'u> FnMut(&'u mut UnitOfWork<'s>) -> UnitOfWorkFuture<'u, R>,
+    {
+        self.transaction_with(TransactionOptions::default(), work)
+            .await
+    }
+
+    /// Run a unit of work with an isolation level or access mode
+    ///
+    /// Like `transaction`; SERIALIZABLE units of work are retried when they
+    /// lose a serialization race.
+    ///
+    /// # Example
+    /// ```ignore
+    /// storehaus
+    ///     .transaction_with(TransactionOptions::serializable(), |uow| {
+    ///         Box::pin(async move { post_ledger_entries(uow).await })
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn transaction_with<'s, R, F>(
+        &'s self,
+        options: TransactionOptions,
+        mut work: F,
+    ) -> Result<R, StorehausError>
+    where
+        F: for<'u> FnMut(&'u mut UnitOfWork<'s>) -> UnitOfWorkFuture<'u, R>,
+    {
+        let retry = self.transaction_retry();
+        let mut attempt = 1;
+        loop {
+            match self.run_unit_of_work(&options, &mut work, attempt).await {
+                Ok(value) => return Ok(value),
+                Err(AttemptError::Retryable(e)) if attempt < retry.max_attempts => {
+                    tracing::debug!(attempt, error = %e, "Retrying unit of work");
+                    tokio::time::sleep(retry.delay(attempt)).await;
+                    attempt += 1;
+                }
+                Err(AttemptError::Retryable(e) | AttemptError::Final(e)) => return Err(e),
+            }
+        }
+    }
+
+    /// Run one attempt of a unit of work
+    async fn run_unit_of_work<'s, R, F>(
+        &'s self,
+        options: &TransactionOptions,
+        work: &mut F,
+        attempt: u32,
+    ) -> Result<R, AttemptError>
+    where
+        F: for<'u> FnMut(&'u mut UnitOfWork<'s>) -> UnitOfWorkFuture<'u, R>,
+    {
+        let tx = options
+            .begin(self.pool())
+            .await
+            .map_err(AttemptError::at_begin)?;
+        let mut uow = UnitOfWork {
+            storehaus: self,
+            tx,
+            effects: PendingEffects::new(),
+            attempt,
+        };
+
+        // On error the transaction and the buffered effects are dropped, which rolls back
+        let value = work(&mut uow).await.map_err(AttemptError::after_begin)?;
+        uow.effects
+            .commit(uow.tx)
+            .await
+            .map_err(AttemptError::after_begin)?;
+        Ok(value)
+    }
+}
+
+/// Failed attempt of a unit of work
+enum AttemptError {
+    /// Nothing was committed; the unit of work may run again
+    Retryable(StorehausError),
+    /// Returned to the caller as is
+    Final(StorehausError),
+}
+
+impl AttemptError {
+    /// Nothing has run yet, so a pool timeout or I/O error while beginning can be retried
+    fn at_begin(error: StorehausError) -> Self {
+        let retryable = matches!(
+            &error,
+            StorehausError::TransactionError {
+                source: sqlx::Error::PoolTimedOut | sqlx::Error::Io(_),
+                ..
+            }
+        ) || error.is_serialization_failure();
+        if retryable {
+            Self::Retryable(error)
+        } else {
+            Self::Final(error)
+        }
+    }
+
+    /// Once the transaction has begun, only a reported rollback makes a retry safe
+    fn after_begin(error: StorehausError) -> Self {
+        if error.is_serialization_failure() {
+            Self::Retryable(error)
+        } else {
+            Self::Final(error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_backoff_is_capped() {
+        let retry = TransactionRetry::new(5)
+            .with_base_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(300));
+
+        assert_eq!(retry.backoff(1), Duration::from_millis(100));
+        assert_eq!(retry.backoff(2), Duration::from_millis(200));
+        assert_eq!(retry.backoff(3), Duration::from_millis(300));
+        assert_eq!(retry.backoff(40), Duration::from_millis(300));
+
+        let delay = retry.delay(2);
+        assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_retry_runs_at_least_once() {
+        assert_eq!(TransactionRetry::new(0).max_attempts, 1);
+        assert_eq!(TransactionRetry::disabled().max_attempts, 1);
+    }
+}
@@ -51,6 +51,13 @@ pub enum StorehausError {
         identifier: String,
     },
 
+    #[error("Concurrent modification of {table} with {identifier}: expected version {expected_version}")]
+    ConcurrencyConflict {
+        table: String,
+        identifier: String,
+        expected_version: i64,
+    },
+
     #[error("Validation failed for field '{field}' in table '{table}': {reason}")]
     ValidationError {
         table: String,
@@ -123,6 +130,15 @@ impl StorehausError {
         }
     }
 
+    /// Create a concurrency conflict error for a versioned record
+    pub fn concurrency_conflict(table: &str, identifier: &str, expected_version: i64) -> Self {
+        Self::ConcurrencyConflict {
+            table: table.to_string(),
+            identifier: identifier.to_string(),
+            expected_version,
+        }
+    }
+
     /// Create a cache error with context
     pub fn cache_operation(
         operation: &str,
@@ -153,6 +169,8 @@ impl StorehausError {
     }
 
     /// Check if this is a transient error that can be retried
+    ///
+    /// Includes serialization failures (SQLSTATE 40001) and deadlocks (40P01).
     pub fn is_transient(&self) -> bool {
         match self {
             Self::DatabaseOperation { source, .. }
@@ -162,13 +180,37 @@ impl StorehausError {
                 matches!(
                     source,
                     sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_)
-                )
+                ) || self.is_serialization_failure()
             }
             Self::CacheError { .. } => true, // Cache errors are usually transient
             _ => false,
         }
     }
 
+    /// Check if PostgreSQL rolled the transaction back after a serialization
+    /// failure (SQLSTATE 40001) or deadlock (40P01)
+    ///
+    /// Nothing of the transaction was committed, so it can run again.
+    pub fn is_serialization_failure(&self) -> bool {
+        match self.sqlx_source() {
+            Some(sqlx::Error::Database(db_error)) => {
+                matches!(db_error.code().as_deref(), Some("40001") | Some("40P01"))
+            }
+            _ => false,
+        }
+    }
+
+    /// The database error behind this error, if any
+    fn sqlx_source(&self) -> Option<&sqlx::Error> {
+        match self {
+            Self::DatabaseOperation { source, .. }
+            | Self::QueryExecution { source, .. }
+            | Self::TransactionError { source, .. }
+            | Self::ConnectionPoolError { source } => Some(source),
+            _ => None,
+        }
+    }
+
     /// Get the error context for logging
     pub fn context(&self) -> std::collections::HashMap<String, String> {
         let mut context = std::collections::HashMap::new();
@@ -200,6 +242,15 @@ impl StorehausError {
                 context.insert("resource".to_string(), resource.clone());
                 context.insert("identifier".to_string(), identifier.clone());
             }
+            Self::ConcurrencyConflict {
+                table,
+                identifier,
+                expected_version,
+            } => {
+                context.insert("table".to_string(), table.clone());
+                context.insert("identifier".to_string(), identifier.clone());
+                context.insert("expected_version".to_string(), expected_version.to_string());
+            }
             Self::CacheError { operation, key, .. } => {
                 context.insert("operation".to_string(), operation.clone());
                 if let Some(key) = key {
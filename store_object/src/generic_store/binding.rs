@@ -0,0 +1,277 @@
+//! Parameter binding for GenericStore
+//!
+//! This module turns typed query parameters into the Rust values sqlx binds.
+//! A value is converted to the declared type of its column, so a text column
+//! compared with `"2024-01-01T00:00:00Z"` gets text and a NUMERIC column
+//! keeps every digit. Values for fields outside the model (joined tables,
+//! aggregates) have no declared type and are bound by their own type.
+//!
+//! Identifiers are checked here too: every name a query writes into the SQL
+//! must be a model column, a joined table or alias, or a SELECT alias.
+
+use super::core::GenericStore;
+use crate::errors::StorehausError;
+use crate::query_builder::{ColumnTypes, QueryBuilder, QueryParam};
+use crate::table_metadata::TableMetadata;
+use crate::validation::ValidatedFieldName;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::Value;
+use type_mapping::PostgresValue;
+use uuid::Uuid;
+
+/// Rust value bound for one query parameter
+#[derive(Debug, Clone, PartialEq)]
+pub enum BindValue {
+    Null,
+    Bool(bool),
+    SmallInt(i16),
+    Integer(i32),
+    BigInt(i64),
+    Double(f64),
+    /// Text, and NUMERIC values whose placeholder is cast to NUMERIC
+    Text(String),
+    Uuid(Uuid),
+    Timestamp(DateTime<Utc>),
+    Date(NaiveDate),
+    Json(Value),
+    TextArray(Vec<String>),
+}
+
+impl BindValue {
+    /// Convert a parameter to the value bound for its declared column type
+    ///
+    /// Fails when the value cannot represent the column type, e.g. a
+    /// non-numeric string for a BIGINT column.
+    pub fn from_param(param: &QueryParam) -> Result<Self, String> {
+        let value = &param.value;
+        if matches!(value, PostgresValue::Null | PostgresValue::Json(Value::Null)) {
+            return Ok(BindValue::Null);
+        }
+        let Some(pg_type) = param.pg_type else {
+            return Ok(Self::infer(value));
+        };
+
+        // NUMERIC(28,10) -> NUMERIC, VARCHAR(255) -> VARCHAR
+        let base_type = pg_type
+            .split('(')
+            .next()
+            .unwrap_or(pg_type)
+            .trim()
+            .to_ascii_uppercase();
+        let converted = match base_type.as_str() {
+            "BOOLEAN" | "BOOL" => as_bool(value).map(BindValue::Bool),
+            "SMALLINT" | "INT2" | "SMALLSERIAL" => as_i64(value)
+                .and_then(|i| i16::try_from(i).ok())
+                .map(BindValue::SmallInt),
+            "INTEGER" | "INT" | "INT4" | "SERIAL" => as_i64(value)
+                .and_then(|i| i32::try_from(i).ok())
+                .map(BindValue::Integer),
+            "BIGINT" | "INT8" | "BIGSERIAL" => as_i64(value).map(BindValue::BigInt),
+            "REAL" | "FLOAT4" | "DOUBLE PRECISION" | "FLOAT8" => {
+                as_f64(value).map(BindValue::Double)
+            }
+            "NUMERIC" | "DECIMAL" => as_decimal(value).map(BindValue::Text),
+            "UUID" => match value {
+                PostgresValue::Uuid(u) => Some(*u),
+                other => as_str(other).and_then(|s| Uuid::parse_str(s).ok()),
+            }
+            .map(BindValue::Uuid),
+            "TIMESTAMP WITH TIME ZONE" | "TIMESTAMPTZ" | "TIMESTAMP" => as_timestamp(value)
+                .map(BindValue::Timestamp),
+            "DATE" => match value {
+                PostgresValue::Timestamp(ts) => Some(ts.date_naive()),
+                other => as_str(other)
+                    .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()),
+            }
+            .map(BindValue::Date),
+            "TEXT" | "VARCHAR" | "CHAR" | "CHARACTER" | "CHARACTER VARYING" => {
+                as_str(value).map(|s| BindValue::Text(s.to_string()))
+            }
+            "JSON" | "JSONB" => Some(BindValue::Json(value.to_json())),
+            "TEXT[]" | "VARCHAR[]" => as_text_array(value).map(BindValue::TextArray),
+            // Types this module does not know are bound as the value's own type
+            _ => Some(Self::infer(value)),
+        };
+
+        converted.ok_or_else(|| {
+            format!(
+                "Value {} is not a valid {} for field '{}'",
+                value.to_json(),
+                pg_type,
+                param.field
+            )
+        })
+    }
+
+    /// Value for a parameter without a declared column type
+    fn infer(value: &PostgresValue) -> Self {
+        match value {
+            PostgresValue::Null => BindValue::Null,
+            PostgresValue::Text(s) | PostgresValue::Decimal(s) => BindValue::Text(s.clone()),
+            PostgresValue::SmallInt(i) => BindValue::SmallInt(*i),
+            PostgresValue::Integer(i) => BindValue::Integer(*i),
+            PostgresValue::BigInt(i) => BindValue::BigInt(*i),
+            PostgresValue::Float(f) => BindValue::Double(*f),
+            PostgresValue::Boolean(b) => BindValue::Bool(*b),
+            PostgresValue::Uuid(u) => BindValue::Uuid(*u),
+            PostgresValue::Timestamp(ts) => BindValue::Timestamp(*ts),
+            PostgresValue::Array(_) => match as_text_array(value) {
+                Some(items) => BindValue::TextArray(items),
+                None => BindValue::Json(value.to_json()),
+            },
+            PostgresValue::Record(_) => BindValue::Json(value.to_json()),
+            PostgresValue::Json(json) => Self::infer_json(json),
+        }
+    }
+
+    /// Value for an untyped JSON parameter without a declared column type
+    ///
+    /// Only fields outside the model get here. Strings are bound as text and
+    /// never guessed to be timestamps or UUIDs, and integers as BIGINT.
+    fn infer_json(json: &Value) -> Self {
+        match json {
+            Value::String(s) => BindValue::Text(s.clone()),
+            Value::Number(n) => {
+                if let Some(i) = n.as_i64() {
+                    BindValue::BigInt(i)
+                } else if let Some(f) = n.as_f64() {
+                    BindValue::Double(f)
+                } else {
+                    BindValue::Text(n.to_string())
+                }
+            }
+            Value::Bool(b) => BindValue::Bool(*b),
+            Value::Null => BindValue::Null,
+            // Arrays and objects are bound as JSONB
+            other => BindValue::Json(other.clone()),
+        }
+    }
+}
+
+fn as_str(value: &PostgresValue) -> Option<&str> {
+    match value {
+        PostgresValue::Text(s) | PostgresValue::Json(Value::String(s)) => Some(s),
+        _ => None,
+    }
+}
+
+fn as_bool(value: &PostgresValue) -> Option<bool> {
+    match value {
+        PostgresValue::Boolean(b) | PostgresValue::Json(Value::Bool(b)) => Some(*b),
+        _ => None,
+    }
+}
+
+fn as_i64(value: &PostgresValue) -> Option<i64> {
+    match value {
+        PostgresValue::SmallInt(i) => Some(*i as i64),
+        PostgresValue::Integer(i) => Some(*i as i64),
+        PostgresValue::BigInt(i) => Some(*i),
+        PostgresValue::Json(Value::Number(n)) => n.as_i64(),
+        _ => None,
+    }
+}
+
+fn as_f64(value: &PostgresValue) -> Option<f64> {
+    match value {
+        PostgresValue::Float(f) => Some(*f),
+        PostgresValue::Json(Value::Number(n)) => n.as_f64(),
+        other => as_i64(other).map(|i| i as f64),
+    }
+}
+
+/// Decimal digits of a NUMERIC value, checked by PostgreSQL when cast
+fn as_decimal(value: &PostgresValue) -> Option<String> {
+    match value {
+        PostgresValue::Decimal(s) => Some(s.clone()),
+        PostgresValue::Float(f) => Some(f.to_string()),
+        PostgresValue::Json(Value::Number(n)) => Some(n.to_string()),
+        other => as_i64(other)
+            .map(|i| i.to_string())
+            .or_else(|| as_str(other).map(str::to_string)),
+    }
+}
+
+fn as_timestamp(value: &PostgresValue) -> Option<DateTime<Utc>> {
+    match value {
+        PostgresValue::Timestamp(ts) => Some(*ts),
+        other => as_str(other)
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|dt| dt.with_timezone(&Utc)),
+    }
+}
+
+fn as_text_array(value: &PostgresValue) -> Option<Vec<String>> {
+    match value {
+        PostgresValue::Array(items) => items
+            .iter()
+            .map(|item| as_str(item).map(str::to_string))
+            .collect(),
+        PostgresValue::Json(Value::Array(items)) => items
+            .iter()
+            .map(|item| item.as_str().map(str::to_string))
+            .collect(),
+        _ => None,
+    }
+}
+
+/// A column or table name of `T` as a quoted SQL identifier
+pub(crate) fn quoted<T: TableMetadata>(name: &str) -> Result<String, StorehausError> {
+    ValidatedFieldName::referenced(name)
+        .map(|name| name.quoted())
+        .map_err(|e| StorehausError::validation(T::table_name(), name, &e.to_string()))
+}
+
+impl<T: TableMetadata> GenericStore<T> {
+    /// Declared column types of the model, used to type query parameters
+    pub(crate) fn column_types() -> ColumnTypes {
+        ColumnTypes::new(T::table_name(), T::get_table_fields())
+    }
+
+    /// Check the identifiers of a query against the model and quote them
+    ///
+    /// Unknown columns and names that are not plain identifiers are rejected
+    /// with a `ValidationError` before any SQL is built.
+    pub(crate) fn checked_query(query: &QueryBuilder) -> Result<QueryBuilder, StorehausError> {
+        query
+            .check_identifiers(&Self::column_types())
+            .map_err(|invalid| {
+                StorehausError::validation(T::table_name(), &invalid.field, &invalid.error.to_string())
+            })
+    }
+
+    /// Convert query parameters to the values bound for their columns
+    pub(crate) fn bind_values(params: &[QueryParam]) -> Result<Vec<BindValue>, StorehausError> {
+        params
+            .iter()
+            .map(|param| {
+                BindValue::from_param(param).map_err(|message| {
+                    StorehausError::validation(T::table_name(), &param.field, &message)
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn untyped(value: Value) -> BindValue {
+        BindValue::from_param(&QueryParam::new("b.label", PostgresValue::Json(value), None))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_untyped_json_is_not_guessed() {
+        let text = |s: &str| BindValue::Text(s.to_string());
+        assert_eq!(untyped(json!("2024-01-01T00:00:00Z")), text("2024-01-01T00:00:00Z"));
+        assert_eq!(
+            untyped(json!("67e55044-10b1-426f-9247-bb680e5fe0c8")),
+            text("67e55044-10b1-426f-9247-bb680e5fe0c8")
+        );
+        assert_eq!(untyped(json!(7)), BindValue::BigInt(7));
+        assert_eq!(untyped(json!(1.5)), BindValue::Double(1.5));
+    }
+}
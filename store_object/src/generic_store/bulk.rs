@@ -0,0 +1,371 @@
+//! Bulk writes for GenericStore
+//!
+//! This module writes many records with few round trips: multi-row
+//! `INSERT ... RETURNING` statements chunked under the bind parameter limit,
+//! `COPY ... FROM STDIN (FORMAT BINARY)` for very large loads, and set-based
+//! `UPDATE ... FROM (VALUES ...)` / `WHERE pk = ANY($1)` statements.
+
+use super::binding::quoted;
+use super::core::GenericStore;
+use crate::errors::StorehausError;
+use crate::table_metadata::TableMetadata;
+use crate::traits::table_metadata::DatabaseExecutor;
+use sqlx::encode::IsNull;
+use sqlx::postgres::{PgArgumentBuffer, PgRow};
+use sqlx::{Encode, Postgres, Row};
+use std::collections::{HashMap, HashSet};
+
+/// Maximum number of bind parameters PostgreSQL accepts in one statement
+pub const MAX_BIND_PARAMS: usize = 65535;
+
+/// Signature and header extension of the binary COPY format
+const COPY_HEADER: &[u8] = b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0";
+
+/// Amount of encoded COPY data buffered before it is sent to the server
+const COPY_SEND_THRESHOLD: usize = 1 << 20;
+
+/// Append one field of a binary COPY tuple: its length and its binary encoding,
+/// or a length of -1 for NULL
+///
+/// Values are encoded with their `sqlx::Encode` implementation, so arrays of
+/// custom types (whose OIDs sqlx resolves per connection) are not supported.
+pub fn encode_copy_field<V>(buf: &mut Vec<u8>, value: &V) -> Result<(), sqlx::Error>
+where
+    V: for<'q> Encode<'q, Postgres>,
+{
+    let mut field = PgArgumentBuffer::default();
+    match value
+        .encode_by_ref(&mut field)
+        .map_err(sqlx::Error::Encode)?
+    {
+        IsNull::Yes => buf.extend_from_slice(&(-1i32).to_be_bytes()),
+        IsNull::No => {
+            let len = i32::try_from(field.len())
+                .map_err(|_| sqlx::Error::Encode("COPY field exceeds 1 GiB".into()))?;
+            buf.extend_from_slice(&len.to_be_bytes());
+            buf.extend_from_slice(&field);
+        }
+    }
+    Ok(())
+}
+
+/// Multi-row INSERT of the CREATE fields for `rows` records
+///
+/// Models without CREATE fields (e.g. a generated key and system fields only)
+/// insert rows of timestamps alone.
+fn create_many_sql<T: TableMetadata>(rows: usize) -> Result<String, StorehausError> {
+    let fields = T::create_fields();
+    let mut columns = fields
+        .iter()
+        .map(|f| quoted::<T>(f))
+        .collect::<Result<Vec<_>, _>>()?;
+    columns.extend(["__created_at__".to_string(), "__updated_at__".to_string()]);
+    let values: Vec<_> = (0..rows)
+        .map(|row| {
+            let mut values: Vec<_> = (1..=fields.len())
+                .map(|i| format!("${}", row * fields.len() + i))
+                .collect();
+            values.extend(["NOW()".to_string(), "NOW()".to_string()]);
+            format!("({})", values.join(", "))
+        })
+        .collect();
+
+    Ok(format!(
+        "INSERT INTO {} ({}) VALUES {} RETURNING *",
+        quoted::<T>(T::table_name())?,
+        columns.join(", "),
+        values.join(", ")
+    ))
+}
+
+/// Set-based UPDATE of the UPDATE fields for `rows` records
+///
+/// Each row of the VALUES list carries the primary key, the UPDATE fields and,
+/// for versioned models, the version the record was read with. Models with
+/// neither UPDATE fields nor a version have nothing to update and are rejected.
+fn update_many_sql<T: TableMetadata>(rows: usize) -> Result<String, StorehausError> {
+    let update_fields = T::update_fields()
+        .iter()
+        .map(|f| quoted::<T>(f))
+        .collect::<Result<Vec<_>, _>>()?;
+    let version_field = T::version_field().map(quoted::<T>).transpose()?;
+    if update_fields.is_empty() && version_field.is_none() {
+        return Err(StorehausError::validation(
+            T::table_name(),
+            "update_fields",
+            "Model has no update fields",
+        ));
+    }
+    let table = quoted::<T>(T::table_name())?;
+    let pk = quoted::<T>(T::primary_key_field())?;
+
+    let mut columns = vec![pk.clone()];
+    columns.extend(update_fields.iter().cloned());
+    let mut assignments: Vec<_> = update_fields
+        .iter()
+        .map(|f| format!("{} = v.{}", f, f))
+        .collect();
+    let mut condition = format!("{}.{} = v.{}", table, pk, pk);
+    if let Some(version_field) = &version_field {
+        columns.push(version_field.clone());
+        assignments.push(format!(
+            "{} = {}.{} + 1",
+            version_field, table, version_field
+        ));
+        condition.push_str(&format!(
+            " AND {}.{} = v.{}",
+            table, version_field, version_field
+        ));
+    }
+    assignments.push("__updated_at__ = NOW()".to_string());
+
+    let values: Vec<_> = (0..rows)
+        .map(|row| {
+            let placeholders: Vec<_> = (1..=columns.len())
+                .map(|i| format!("${}", row * columns.len() + i))
+                .collect();
+            format!("({})", placeholders.join(", "))
+        })
+        .collect();
+
+    Ok(format!(
+        "UPDATE {} SET {} FROM (VALUES {}) AS v ({}) WHERE {} RETURNING {}.*",
+        table,
+        assignments.join(", "),
+        values.join(", "),
+        columns.join(", "),
+        condition,
+        table
+    ))
+}
+
+/// DELETE (or soft delete) of all rows whose primary key is in the array `$1`
+fn delete_many_sql<T: TableMetadata>() -> Result<String, StorehausError> {
+    let table = quoted::<T>(T::table_name())?;
+    let pk = quoted::<T>(T::primary_key_field())?;
+    Ok(match T::soft_delete_field() {
+        Some(soft_delete_field) if T::supports_soft_delete() => format!(
+            "UPDATE {} SET {} = false, __updated_at__ = NOW() WHERE {} = ANY($1) RETURNING {}",
+            table,
+            quoted::<T>(soft_delete_field)?,
+            pk,
+            pk
+        ),
+        _ => format!(
+            "DELETE FROM {} WHERE {} = ANY($1) RETURNING {}",
+            table, pk, pk
+        ),
+    })
+}
+
+impl<T> GenericStore<T>
+where
+    T: TableMetadata
+        + DatabaseExecutor
+        + for<'r> sqlx::FromRow<'r, PgRow>
+        + serde::Serialize
+        + Unpin,
+{
+    /// Insert records with multi-row INSERT statements on one connection
+    ///
+    /// Each statement carries as many rows as fit under `MAX_BIND_PARAMS`;
+    /// created rows are returned in input order.
+    pub(crate) async fn execute_create_many(
+        conn: &mut sqlx::PgConnection,
+        data: &[T],
+    ) -> Result<Vec<T>, StorehausError> {
+        let rows_per_chunk = (MAX_BIND_PARAMS / T::create_fields().len().max(1)).max(1);
+
+        let mut created = Vec::with_capacity(data.len());
+        for chunk in data.chunks(rows_per_chunk) {
+            let sql = create_many_sql::<T>(chunk.len())?;
+            let query = chunk.iter().fold(sqlx::query(&sql), |query, record| {
+                record.bind_create_params(query)
+            });
+            let rows = query
+                .fetch_all(&mut *conn)
+                .await
+                .map_err(|e| StorehausError::database_operation(T::table_name(), "create", e))?;
+            for row in &rows {
+                created.push(T::from_row(row).map_err(|e| {
+                    StorehausError::database_operation(T::table_name(), "create", e)
+                })?);
+            }
+        }
+        Ok(created)
+    }
+
+    /// Update records with set-based UPDATE statements on one connection
+    ///
+    /// Each statement joins as many `(id, record)` rows as fit under
+    /// `MAX_BIND_PARAMS`; updated rows are returned in input order. Fails if an
+    /// id matches no row, or for versioned models, no row with the record's version.
+    pub(crate) async fn execute_update_many(
+        conn: &mut sqlx::PgConnection,
+        updates: &[(T::Id, T)],
+    ) -> Result<Vec<T>, StorehausError> {
+        let versioned = T::version_field().is_some();
+        let params_per_row = T::update_fields().len() + 1 + usize::from(versioned);
+        let rows_per_chunk = (MAX_BIND_PARAMS / params_per_row).max(1);
+
+        let mut updated = Vec::with_capacity(updates.len());
+        for chunk in updates.chunks(rows_per_chunk) {
+            let sql = update_many_sql::<T>(chunk.len())?;
+            let query = chunk.iter().fold(sqlx::query(&sql), |query, (id, record)| {
+                let query = record.bind_update_params(query.bind(id));
+                if versioned {
+                    query.bind(record.version())
+                } else {
+                    query
+                }
+            });
+            let rows = query
+                .fetch_all(&mut *conn)
+                .await
+                .map_err(|e| StorehausError::database_operation(T::table_name(), "update", e))?;
+
+            let mut by_id = HashMap::with_capacity(rows.len());
+            for row in &rows {
+                let record = T::from_row(row).map_err(|e| {
+                    StorehausError::database_operation(T::table_name(), "update", e)
+                })?;
+                by_id.insert(Self::record_cache_key(&record.extract_id()), record);
+            }
+            for (id, record) in chunk {
+                let key = Self::record_cache_key(id);
+                match by_id.get(&key) {
+                    Some(stored) => updated.push(stored.clone()),
+                    // A versioned row that still exists was changed since it was read
+                    None if versioned && Self::exists_by_id(&mut *conn, id).await? => {
+                        return Err(StorehausError::concurrency_conflict(
+                            T::table_name(),
+                            &key,
+                            record.version().unwrap_or_default(),
+                        ))
+                    }
+                    None => return Err(StorehausError::not_found(T::table_name(), &key)),
+                }
+            }
+        }
+        Ok(updated)
+    }
+
+    /// Whether a row with primary key `id` exists
+    async fn exists_by_id(
+        conn: &mut sqlx::PgConnection,
+        id: &T::Id,
+    ) -> Result<bool, StorehausError> {
+        sqlx::query_scalar(&T::exists_by_id_sql())
+            .bind(id)
+            .fetch_one(conn)
+            .await
+            .map_err(|e| StorehausError::database_operation(T::table_name(), "update", e))
+    }
+
+    /// Delete (or soft delete) records in one statement
+    ///
+    /// Returns the ids of the rows that existed, in input order.
+    pub(crate) async fn execute_delete_many<'e, E>(
+        executor: E,
+        ids: &[T::Id],
+    ) -> Result<Vec<T::Id>, StorehausError>
+    where
+        E: sqlx::Executor<'e, Database = Postgres>,
+    {
+        let rows = sqlx::query(&delete_many_sql::<T>()?)
+            .bind(ids)
+            .fetch_all(executor)
+            .await
+            .map_err(|e| StorehausError::database_operation(T::table_name(), "delete", e))?;
+
+        let mut deleted = HashSet::with_capacity(rows.len());
+        for row in &rows {
+            let id: T::Id = row
+                .try_get(0)
+                .map_err(|e| StorehausError::database_operation(T::table_name(), "delete", e))?;
+            deleted.insert(Self::record_cache_key(&id));
+        }
+        // Each deleted id is reported once, even if it was passed several times
+        Ok(ids
+            .iter()
+            .filter(|id| deleted.remove(&Self::record_cache_key(id)))
+            .cloned()
+            .collect())
+    }
+
+    /// Load records with `COPY ... FROM STDIN (FORMAT BINARY)`
+    ///
+    /// The fastest path for very large loads. Only the CREATE fields are
+    /// copied, the remaining columns take their defaults, and rows are not
+    /// returned. The copy runs in its own transaction and emits one batched
+    /// Create signal with all records. Returns the number of copied rows.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let copied = store.copy_in(&users).await?;
+    /// assert_eq!(copied, users.len() as u64);
+    /// ```
+    pub async fn copy_in(&self, data: &[T]) -> Result<u64, StorehausError> {
+        if data.is_empty() {
+            return Ok(0);
+        }
+
+        let columns = T::create_fields()
+            .iter()
+            .map(|f| quoted::<T>(f))
+            .collect::<Result<Vec<_>, _>>()?;
+        let sql = format!(
+            "COPY {} ({}) FROM STDIN (FORMAT BINARY)",
+            quoted::<T>(T::table_name())?,
+            columns.join(", ")
+        );
+
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(|e| StorehausError::transaction("begin", e))?;
+        let copy_error = |e| StorehausError::database_operation(T::table_name(), "copy_in", e);
+
+        let mut copy = tx.copy_in_raw(&sql).await.map_err(copy_error)?;
+        let mut buf = COPY_HEADER.to_vec();
+        for record in data {
+            if let Err(e) = record.encode_copy_row(&mut buf) {
+                let _ = copy.abort(e.to_string()).await;
+                return Err(copy_error(e));
+            }
+            if buf.len() >= COPY_SEND_THRESHOLD {
+                copy.send(std::mem::take(&mut buf))
+                    .await
+                    .map_err(copy_error)?;
+            }
+        }
+        // File trailer: a tuple with field count -1
+        buf.extend_from_slice(&(-1i16).to_be_bytes());
+        copy.send(buf).await.map_err(copy_error)?;
+        let copied = copy.finish().await.map_err(copy_error)?;
+
+        // Build single batch create signal if signals are enabled
+        let mut events = Vec::new();
+        if self.has_event_sink() {
+            events.push(self.created_many_event(data));
+        }
+        let events = self.commit_write(Some(tx), events).await?;
+
+        // Forget cached misses for copied ids, then drop query caches since data changed
+        if T::create_fields().contains(&T::primary_key_field())
+            && self
+                .cache_params
+                .as_ref()
+                .is_some_and(|cp| cp.negative_ttl.is_some())
+        {
+            let ids: Vec<T::Id> = data.iter().map(|record| record.extract_id()).collect();
+            self.evict_record_cache(&ids).await;
+        }
+        self.invalidate_query_cache().await;
+
+        self.emit_signals(events).await;
+
+        Ok(copied)
+    }
+}
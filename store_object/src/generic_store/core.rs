@@ -2,10 +2,15 @@
 //!
 //! This module provides generic database store functionality.
 
+use crate::errors::StorehausError;
+use crate::id_type::HasUniversalId;
+use crate::outbox::EventOutbox;
+use crate::query_builder::CursorCodec;
 use crate::table_metadata::TableMetadata;
 use crate::DbPool;
-use cache_system::{CacheManager, CacheParams};
-use signal_system::SignalManager;
+use cache_system::{CacheManager, CacheParams, CacheWriteMode, QueryKey};
+use signal_system::{DatabaseEvent, SignalManager};
+use sqlx::{Postgres, Transaction};
 use std::sync::Arc;
 
 /// Generic database store that provides default implementations for all database operations
@@ -14,6 +19,8 @@ pub struct GenericStore<T: TableMetadata> {
     pub(crate) db_pool: DbPool,
     pub(crate) signal_manager: Option<Arc<SignalManager>>,
     pub(crate) cache_params: Option<CacheParams>,
+    pub(crate) outbox: Option<Arc<EventOutbox>>,
+    pub(crate) cursor_codec: CursorCodec,
     pub(crate) _phantom: std::marker::PhantomData<T>,
 }
 
@@ -23,6 +30,7 @@ impl<T: TableMetadata> std::fmt::Debug for GenericStore<T> {
             .field("has_signals", &self.has_signals())
             .field("has_cache_manager", &self.has_cache_manager())
             .field("cache_params", &self.cache_params)
+            .field("has_outbox", &self.has_outbox())
             .finish()
     }
 }
@@ -37,6 +45,8 @@ impl<T: TableMetadata> GenericStore<T> {
             db_pool,
             signal_manager,
             cache_params,
+            outbox: None,
+            cursor_codec: CursorCodec::default(),
             _phantom: std::marker::PhantomData,
         }
     }
@@ -56,6 +66,96 @@ impl<T: TableMetadata> GenericStore<T> {
         self.signal_manager.is_some()
     }
 
+    /// Route this store's signals through a transactional outbox
+    ///
+    /// Events are then stored in the same transaction as each write and
+    /// delivered by the outbox dispatcher after commit, instead of being
+    /// emitted directly to the signal manager.
+    pub fn set_outbox(&mut self, outbox: Arc<EventOutbox>) {
+        self.outbox = Some(outbox);
+    }
+
+    /// Emit signals directly again
+    pub fn remove_outbox(&mut self) {
+        self.outbox = None;
+    }
+
+    /// Check if signals go through an outbox
+    pub fn has_outbox(&self) -> bool {
+        self.outbox.is_some()
+    }
+
+    /// Sign page cursors with a shared secret
+    ///
+    /// By default cursors are signed with a random per-process key, so they stop
+    /// verifying after a restart or on another node; the first cursor signed
+    /// with it logs a warning.
+    pub fn set_cursor_secret(&mut self, secret: impl AsRef<[u8]>) {
+        self.cursor_codec = CursorCodec::new(secret);
+    }
+
+    /// Check if writes produce events, either for the signal manager or the outbox
+    pub(crate) fn has_event_sink(&self) -> bool {
+        self.signal_manager.is_some() || self.outbox.is_some()
+    }
+
+    /// Begin a transaction for a write whose events must be stored in the outbox
+    ///
+    /// Returns `None` without an outbox, so single-statement writes keep running
+    /// directly on the pool.
+    pub(crate) async fn begin_outbox_write(
+        &self,
+    ) -> Result<Option<Transaction<'static, Postgres>>, StorehausError> {
+        if self.outbox.is_none() {
+            return Ok(None);
+        }
+        let tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(|e| StorehausError::transaction("begin", e))?;
+        Ok(Some(tx))
+    }
+
+    /// Commit a write together with its events
+    ///
+    /// With an outbox the events are stored in the write's transaction before it
+    /// commits and the dispatcher is woken afterwards; the returned list is then
+    /// empty. Without an outbox the events are returned for `emit_signals` once
+    /// caches are up to date.
+    pub(crate) async fn commit_write(
+        &self,
+        tx: Option<Transaction<'_, Postgres>>,
+        events: Vec<DatabaseEvent>,
+    ) -> Result<Vec<DatabaseEvent>, StorehausError> {
+        let Some(outbox) = &self.outbox else {
+            if let Some(tx) = tx {
+                tx.commit()
+                    .await
+                    .map_err(|e| StorehausError::transaction("commit", e))?;
+            }
+            return Ok(events);
+        };
+
+        let mut tx = match tx {
+            Some(tx) => tx,
+            None => self
+                .db_pool
+                .begin()
+                .await
+                .map_err(|e| StorehausError::transaction("begin", e))?,
+        };
+        outbox.enqueue(&mut tx, &events).await?;
+        tx.commit()
+            .await
+            .map_err(|e| StorehausError::transaction("commit", e))?;
+
+        if !events.is_empty() {
+            outbox.notify();
+        }
+        Ok(Vec::new())
+    }
+
     /// Set cache manager for this store
     pub fn set_cache_manager(&mut self, cache_params: CacheParams) {
         self.cache_params = Some(cache_params);
@@ -89,12 +189,159 @@ impl<T: TableMetadata> GenericStore<T> {
         self.cache_params.as_ref().map(|cp| &cp.manager)
     }
 
+    /// Get cache manager reference if query results should be cached
+    pub(crate) fn query_cache_manager(&self) -> Option<&Arc<CacheManager>> {
+        self.cache_params
+            .as_ref()
+            .filter(|cp| cp.cache_queries)
+            .map(|cp| &cp.manager)
+    }
+
+    /// Look up cached query results by hash, returning the key to store fresh results under
+    ///
+    /// The key is resolved before the database read, so results read while a
+    /// write invalidates the table never land under the new generation. Cache
+    /// failures are logged and read as a miss: the query falls through to the
+    /// database instead of failing.
+    pub(crate) async fn get_cached_query<R>(
+        &self,
+        operation: &str,
+        hash: &str,
+    ) -> (Option<Vec<R>>, Option<QueryKey>)
+    where
+        R: for<'de> serde::Deserialize<'de>,
+    {
+        let Some(cache_manager) = self.query_cache_manager() else {
+            return (None, None);
+        };
+        let key = match cache_manager
+            .query_key(self.get_cache_prefix(), T::table_name(), hash)
+            .await
+        {
+            Ok(key) => key,
+            Err(e) => {
+                tracing::warn!(
+                    table = T::table_name(),
+                    operation,
+                    error = %e,
+                    "Query cache lookup failed"
+                );
+                return (None, None);
+            }
+        };
+        match cache_manager.get_query::<R>(&key).await {
+            Ok(cached) => (cached, Some(key)),
+            // Storing fresh results replaces an undecodable entry
+            Err(e) => {
+                tracing::warn!(
+                    table = T::table_name(),
+                    operation,
+                    error = %e,
+                    "Query cache lookup failed"
+                );
+                (None, Some(key))
+            }
+        }
+    }
+
+    /// Store query results under a key from `get_cached_query`, logging failures
+    pub(crate) async fn set_cached_query<R: serde::Serialize>(
+        &self,
+        operation: &str,
+        key: &QueryKey,
+        results: &[R],
+    ) {
+        let Some(cache_manager) = self.query_cache_manager() else {
+            return;
+        };
+        if let Err(e) = cache_manager
+            .set_query(key, results, self.get_cache_ttl())
+            .await
+        {
+            tracing::warn!(
+                table = T::table_name(),
+                operation,
+                error = %e,
+                "Query cache store failed"
+            );
+        }
+    }
+
+    /// Cache key of a record, derived from its primary key via `HasUniversalId`
+    pub(crate) fn record_cache_key(id: &T::Id) -> String {
+        id.universal_id().to_string_fast()
+    }
+
+    /// Bring cached records in line with rows returned by a write
+    ///
+    /// Depending on `CacheParams::write_mode` the rows are either stored as the
+    /// new record entries or evicted so that the next read reloads them.
+    pub(crate) async fn refresh_record_cache(&self, records: &[T]) {
+        let Some(cache_params) = &self.cache_params else {
+            return;
+        };
+        if records.is_empty() {
+            return;
+        }
+
+        match cache_params.write_mode {
+            CacheWriteMode::Invalidate => {
+                let ids: Vec<T::Id> = records.iter().map(|record| record.extract_id()).collect();
+                self.evict_record_cache(&ids).await;
+            }
+            CacheWriteMode::WriteThrough => {
+                for record in records {
+                    let key = Self::record_cache_key(&record.extract_id());
+                    let _ = cache_params
+                        .manager
+                        .set_record_with_ttl(
+                            self.get_cache_prefix(),
+                            T::table_name(),
+                            &key,
+                            record,
+                            cache_params.ttl,
+                        )
+                        .await;
+                }
+            }
+        }
+    }
+
+    /// Evict cached records of deleted (or otherwise changed) rows
+    pub(crate) async fn evict_record_cache(&self, ids: &[T::Id]) {
+        if let Some(cache_manager) = self.cache_manager() {
+            if ids.is_empty() {
+                return;
+            }
+            let keys = ids.iter().map(Self::record_cache_key).collect();
+            let _ = cache_manager
+                .delete_records(self.get_cache_prefix(), T::table_name(), keys)
+                .await;
+        }
+    }
+
+    /// Drop all cached query results for this table after a write
+    pub(crate) async fn invalidate_query_cache(&self) {
+        if let Some(cache_manager) = self.cache_manager() {
+            let _ = cache_manager
+                .invalidate_queries(self.get_cache_prefix(), T::table_name())
+                .await;
+        }
+    }
+
     pub(crate) async fn emit_signal(&self, event: signal_system::DatabaseEvent) {
         if let Some(signal_manager) = &self.signal_manager {
             signal_manager.emit(event).await;
         }
     }
 
+    /// Emit events returned by `commit_write`
+    pub(crate) async fn emit_signals(&self, events: Vec<DatabaseEvent>) {
+        for event in events {
+            self.emit_signal(event).await;
+        }
+    }
+
     /// Create event with explicit tags
     pub(crate) fn create_event_with_explicit_tags(
         &self,
@@ -135,15 +382,128 @@ impl<T: TableMetadata> GenericStore<T> {
         event
     }
 
-    /// Extract tags from payload and create event with tags (legacy method)
-    pub(crate) fn create_event_with_tags(
+    /// Event for a created record
+    pub(crate) fn created_event(&self, record: &T, tags: Option<Vec<String>>) -> DatabaseEvent {
+        self.create_event_with_explicit_tags(
+            signal_system::EventType::Create,
+            T::table_name().to_string(),
+            record,
+            None,
+            tags.unwrap_or_default(),
+        )
+    }
+
+    /// Event for an updated record, with the full row from the database as `__record__`
+    pub(crate) fn updated_event(
         &self,
-        event_type: signal_system::EventType,
-        table_name: String,
+        id: &T::Id,
         record: &T,
-        record_id: Option<String>,
-    ) -> signal_system::DatabaseEvent {
-        // For backward compatibility - no tags
-        self.create_event_with_explicit_tags(event_type, table_name, record, record_id, Vec::new())
+        tags: Option<Vec<String>>,
+    ) -> DatabaseEvent {
+        let mut event = self.create_event_with_explicit_tags(
+            signal_system::EventType::Update,
+            T::table_name().to_string(),
+            record,
+            Some(Self::record_cache_key(id)),
+            tags.unwrap_or_default(),
+        );
+        event.add_payload(
+            "__record__".to_string(),
+            signal_system::serialize_to_postgres_record(record),
+        );
+        event
+    }
+
+    /// Event for a deleted record
+    pub(crate) fn deleted_event(&self, id: &T::Id) -> DatabaseEvent {
+        DatabaseEvent::new(signal_system::EventType::Delete, T::table_name().to_string())
+            .with_record_id(Self::record_cache_key(id))
+    }
+
+    /// Event for several created rows: all rows as `__record__` plus `created_count`
+    pub(crate) fn created_many_event(&self, records: &[T]) -> DatabaseEvent {
+        let mut event =
+            DatabaseEvent::new(signal_system::EventType::Create, T::table_name().to_string());
+
+        let all_records: Vec<signal_system::PostgresValue> = records
+            .iter()
+            .map(signal_system::serialize_to_postgres_record)
+            .collect();
+        event.add_payload(
+            "__record__".to_string(),
+            signal_system::PostgresValue::Json(
+                serde_json::to_value(all_records).unwrap_or_default(),
+            ),
+        );
+        event.add_payload(
+            "created_count".to_string(),
+            signal_system::PostgresValue::Integer(records.len() as i32),
+        );
+        event
+    }
+
+    /// Event for rows changed by `update_where`: all rows as `__record__` plus `updated_count`
+    pub(crate) fn updated_where_event(&self, records: &[T]) -> DatabaseEvent {
+        let mut event =
+            DatabaseEvent::new(signal_system::EventType::Update, T::table_name().to_string());
+
+        let all_records: Vec<signal_system::PostgresValue> = records
+            .iter()
+            .map(signal_system::serialize_to_postgres_record)
+            .collect();
+        event.add_payload(
+            "__record__".to_string(),
+            signal_system::PostgresValue::Json(
+                serde_json::to_value(all_records).unwrap_or_default(),
+            ),
+        );
+        event.add_payload(
+            "updated_count".to_string(),
+            signal_system::PostgresValue::Integer(records.len() as i32),
+        );
+        event
+    }
+
+    /// Event for several deleted rows, listing their ids under the primary key field name
+    pub(crate) fn deleted_many_event(&self, ids: &[T::Id]) -> DatabaseEvent {
+        let mut event =
+            DatabaseEvent::new(signal_system::EventType::Delete, T::table_name().to_string());
+
+        // Convert deleted IDs to PostgresValue array
+        let ids_as_postgres: Vec<signal_system::PostgresValue> = ids
+            .iter()
+            .map(|id| {
+                let id_str = Self::record_cache_key(id);
+                if id_str.starts_with('"') && id_str.ends_with('"') {
+                    // String/UUID
+                    signal_system::PostgresValue::Text(id_str.trim_matches('"').to_string())
+                } else if let Ok(int_id) = id_str.parse::<i32>() {
+                    signal_system::PostgresValue::Integer(int_id)
+                } else if let Ok(bigint_id) = id_str.parse::<i64>() {
+                    signal_system::PostgresValue::BigInt(bigint_id)
+                } else {
+                    // Fallback to text
+                    signal_system::PostgresValue::Text(id_str)
+                }
+            })
+            .collect();
+
+        event.add_payload(
+            T::primary_key_field().to_string(),
+            signal_system::PostgresValue::Json(
+                serde_json::to_value(ids_as_postgres).unwrap_or_default(),
+            ),
+        );
+        event
+    }
+
+    /// Event for rows removed by `delete_where`: their ids plus `deleted_count`
+    pub(crate) fn deleted_where_event(&self, ids: &[T::Id]) -> DatabaseEvent {
+        let mut event = self.deleted_many_event(ids);
+        event.add_payload(
+            "deleted_count".to_string(),
+            signal_system::PostgresValue::Integer(ids.len() as i32),
+        );
+        event
     }
 }
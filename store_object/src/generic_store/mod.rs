@@ -2,11 +2,24 @@
 //!
 //! This module provides generic database store functionality.
 
+pub mod binding;
+pub mod bulk;
 pub mod core;
 pub mod filterable;
+pub mod paginate;
+pub mod rows;
 pub mod soft_deletable;
 pub mod store_object;
+pub mod stream;
 pub mod transaction;
+pub mod upsert;
 
+pub use bulk::{encode_copy_field, MAX_BIND_PARAMS};
 pub use core::GenericStore;
-pub use transaction::GenericStoreTransaction;
+pub use rows::JsonRow;
+pub use stream::{StreamMode, DEFAULT_STREAM_BATCH_SIZE};
+pub use transaction::{
+    GenericStoreTransaction, IsolationLevel, PendingEffects, Savepoint, TransactionOptions,
+    TransactionalStore,
+};
+pub use upsert::{ConflictAction, ConflictTarget, OnConflict, UpsertOutcome};
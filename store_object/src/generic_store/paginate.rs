@@ -0,0 +1,299 @@
+//! Pagination for GenericStore
+//!
+//! This module reads pages relative to a cursor instead of an OFFSET, so
+//! pages stay fast on large tables and do not shift when rows are inserted
+//! or deleted in between requests. Numbered pages with a total count are
+//! available for admin views where both are needed.
+
+use super::core::GenericStore;
+use super::rows::column_json;
+use crate::errors::StorehausError;
+use crate::query_builder::{
+    CursorDirection, CursorPosition, Page, PagedResult, QueryBuilder, QueryFilter, SortOrder,
+};
+use crate::table_metadata::TableMetadata;
+use crate::traits::table_metadata::DatabaseExecutor;
+use serde_json::Value;
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+
+/// Column carrying `COUNT(*) OVER()` in `find_page` queries
+const TOTAL_COUNT_COLUMN: &str = "__total_count__";
+
+/// Column name of a possibly table-qualified field (`users.name` -> `name`)
+fn column_name(field: &str) -> &str {
+    field
+        .rsplit('.')
+        .next()
+        .unwrap_or(field)
+        .trim_matches('"')
+}
+
+/// Condition selecting the rows after (or before) `values` in `ordering`
+///
+/// Expands to `(a > $1) OR (a = $1 AND b > $2) OR ...`, with the comparison
+/// flipped for descending fields, so mixed sort directions are supported.
+fn keyset_filter(
+    ordering: &[(String, SortOrder)],
+    values: &[Value],
+    direction: CursorDirection,
+) -> QueryFilter {
+    let branches = (0..ordering.len())
+        .map(|k| {
+            let mut conditions: Vec<_> = ordering[..k]
+                .iter()
+                .zip(values)
+                .map(|((field, _), value)| QueryFilter::eq(field, value.clone()))
+                .collect();
+            let (field, order) = &ordering[k];
+            let value = values[k].clone();
+            conditions.push(match (order, direction) {
+                (SortOrder::Asc, CursorDirection::After)
+                | (SortOrder::Desc, CursorDirection::Before) => QueryFilter::gt(field, value),
+                _ => QueryFilter::lt(field, value),
+            });
+            QueryFilter::and(conditions)
+        })
+        .collect();
+
+    QueryFilter::or(branches)
+}
+
+impl<T> GenericStore<T>
+where
+    T: TableMetadata
+        + DatabaseExecutor
+        + for<'r> sqlx::FromRow<'r, PgRow>
+        + serde::Serialize
+        + Unpin,
+{
+    /// Read one page of up to `limit` records, continuing from the query's cursor
+    ///
+    /// Rows are ordered by the query's `order_by` fields with the primary key
+    /// appended as a tie-breaker. Ordering fields must be NOT NULL. Cursors are
+    /// signed and bound to the table and ordering they were issued for; a
+    /// modified or foreign cursor fails with `StorehausError::ValidationError`.
+    /// Cursor values are read from the result columns, so serde attributes on
+    /// the model do not affect them. Reads go straight to the database.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let query = QueryBuilder::new().order_by("__created_at__", SortOrder::Desc);
+    /// let first = user_store.paginate(query.clone(), 20).await?;
+    /// if let Some(cursor) = first.next_cursor {
+    ///     let second = user_store.paginate(query.after(cursor), 20).await?;
+    /// }
+    /// ```
+    pub async fn paginate(
+        &self,
+        query: QueryBuilder,
+        limit: i64,
+    ) -> Result<Page<T>, StorehausError> {
+        if limit < 1 {
+            return Err(StorehausError::validation(
+                T::table_name(),
+                "limit",
+                "Page size must be at least 1",
+            ));
+        }
+        if query.offset.is_some() {
+            return Err(StorehausError::validation(
+                T::table_name(),
+                "offset",
+                "Cursor pagination cannot be combined with OFFSET",
+            ));
+        }
+        let primary_key = T::primary_key_field();
+        if primary_key.is_empty() {
+            return Err(StorehausError::validation(
+                T::table_name(),
+                "primary_key",
+                "Cursor pagination requires a primary key",
+            ));
+        }
+
+        let mut ordering = query.order_by.clone();
+        if !ordering
+            .iter()
+            .any(|(field, _)| column_name(field) == primary_key)
+        {
+            ordering.push((primary_key.to_string(), SortOrder::Asc));
+        }
+        let ordering_spec: Vec<String> = ordering
+            .iter()
+            .map(|(field, order)| format!("{} {}", field, order.to_sql()))
+            .collect();
+
+        let direction = query
+            .cursor
+            .as_ref()
+            .map(|cursor| cursor.direction)
+            .unwrap_or(CursorDirection::After);
+
+        let mut page_query = query.clone();
+        page_query.cursor = None;
+        if let Some(cursor) = &query.cursor {
+            let position = self
+                .cursor_codec
+                .decode(&cursor.token)
+                .filter(|position| {
+                    position.table == T::table_name()
+                        && position.ordering == ordering_spec
+                        && position.values.len() == ordering.len()
+                })
+                .ok_or_else(|| {
+                    StorehausError::validation(
+                        T::table_name(),
+                        "cursor",
+                        "Invalid page cursor for this query",
+                    )
+                })?;
+            page_query = page_query.filter(keyset_filter(&ordering, &position.values, direction));
+        }
+
+        // Pages before a cursor are read backwards, then put back in query order
+        page_query.order_by = match direction {
+            CursorDirection::After => ordering.clone(),
+            CursorDirection::Before => ordering
+                .iter()
+                .map(|(field, order)| (field.clone(), order.reverse()))
+                .collect(),
+        };
+        page_query.limit = Some(limit + 1);
+
+        let (sql, params) = Self::build_find_sql(&page_query)?;
+        let mut sqlx_query = sqlx::query(&sql);
+        for value in Self::bind_values(&params)? {
+            sqlx_query = self.bind_param_raw(sqlx_query, value);
+        }
+        let mut rows = sqlx_query
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| StorehausError::query_execution(T::table_name(), &sql, e))?;
+
+        let has_more = rows.len() as i64 > limit;
+        rows.truncate(limit as usize);
+        if direction == CursorDirection::Before {
+            rows.reverse();
+        }
+        let items = rows
+            .iter()
+            .map(T::from_row)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| StorehausError::query_execution(T::table_name(), &sql, e))?;
+
+        let cursor_at = |row: Option<&PgRow>| -> Result<Option<String>, StorehausError> {
+            let Some(row) = row else {
+                return Ok(None);
+            };
+            let values = ordering
+                .iter()
+                .map(|(field, _)| column_json(row, column_name(field)))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| StorehausError::query_execution(T::table_name(), &sql, e))?;
+            Ok(Some(self.cursor_codec.encode(&CursorPosition {
+                table: T::table_name().to_string(),
+                ordering: ordering_spec.clone(),
+                values,
+            })))
+        };
+
+        let (next_cursor, prev_cursor) = match direction {
+            CursorDirection::After => (
+                if has_more { cursor_at(rows.last())? } else { None },
+                if query.cursor.is_some() { cursor_at(rows.first())? } else { None },
+            ),
+            CursorDirection::Before => (
+                cursor_at(rows.last())?,
+                if has_more { cursor_at(rows.first())? } else { None },
+            ),
+        };
+
+        Ok(Page {
+            items,
+            next_cursor,
+            prev_cursor,
+            has_more,
+        })
+    }
+
+    /// Read page `page` (from 1) of `per_page` records together with the total count
+    ///
+    /// The total comes from `COUNT(*) OVER()` in the same statement, so it
+    /// always matches the filters and soft delete handling of the items. Only
+    /// a page past the end, which has no rows to carry the total, costs a
+    /// second COUNT query. Reads go straight to the database, never to the
+    /// query cache. A custom `select_base_sql` that does not start with
+    /// `SELECT * ` is rejected with `StorehausError::ValidationError`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let page = user_store
+    ///     .find_page(QueryBuilder::new().order_by("name", SortOrder::Asc), 2, 50)
+    ///     .await?;
+    /// println!("page {} of {} ({} users)", page.page, page.total_pages, page.total);
+    /// ```
+    pub async fn find_page(
+        &self,
+        query: QueryBuilder,
+        page: i64,
+        per_page: i64,
+    ) -> Result<PagedResult<T>, StorehausError> {
+        if page < 1 || per_page < 1 {
+            return Err(StorehausError::validation(
+                T::table_name(),
+                "page",
+                "Page and page size must be at least 1",
+            ));
+        }
+        if query.get_lock().is_some() {
+            return Err(StorehausError::validation(
+                T::table_name(),
+                "lock",
+                "Row locks cannot be combined with a window count; use find",
+            ));
+        }
+
+        let count_query = query.clone();
+        let page_query = query.limit(per_page).offset((page - 1) * per_page);
+        let (sql, params) = Self::build_find_sql(&page_query)?;
+        let sql = sql
+            .strip_prefix("SELECT * ")
+            .map(|rest| format!("SELECT *, COUNT(*) OVER() AS {} {}", TOTAL_COUNT_COLUMN, rest))
+            .ok_or_else(|| {
+                StorehausError::validation(
+                    T::table_name(),
+                    "select",
+                    "find_page needs a `SELECT * ` base statement to add the window count",
+                )
+            })?;
+
+        let mut sqlx_query = sqlx::query(&sql);
+        for value in Self::bind_values(&params)? {
+            sqlx_query = self.bind_param_raw(sqlx_query, value);
+        }
+        let rows = sqlx_query
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| StorehausError::query_execution(T::table_name(), &sql, e))?;
+
+        let total = match rows.first() {
+            Some(row) => row
+                .try_get::<i64, _>(TOTAL_COUNT_COLUMN)
+                .map_err(|e| StorehausError::query_execution(T::table_name(), &sql, e))?,
+            None if page == 1 => 0,
+            // Past the end: count the same rows, never from the query cache
+            None => {
+                let (count_sql, count_params) = Self::build_count_sql(&count_query)?;
+                self.fetch_count(&count_sql, &count_params).await?
+            }
+        };
+        let items = rows
+            .iter()
+            .map(T::from_row)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| StorehausError::query_execution(T::table_name(), &sql, e))?;
+
+        Ok(PagedResult::new(items, total, page, per_page))
+    }
+}
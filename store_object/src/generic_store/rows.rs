@@ -0,0 +1,198 @@
+//! Row queries for GenericStore
+//!
+//! This module executes complete `QueryBuilder` queries (SELECT fields, JOINs,
+//! GROUP BY and HAVING) and returns untyped JSON rows or user-defined row types.
+
+use super::core::GenericStore;
+use crate::errors::StorehausError;
+use crate::query_builder::{QueryBuilder, QueryParam, SelectField};
+use crate::table_metadata::TableMetadata;
+use crate::traits::table_metadata::DatabaseExecutor;
+use serde_json::{Map, Value};
+use sqlx::postgres::PgRow;
+use sqlx::{Column, Row, TypeInfo, ValueRef};
+
+/// Result row of `find_rows` / `aggregate`, keyed by column name or alias
+pub type JsonRow = Map<String, Value>;
+
+impl<T> GenericStore<T>
+where
+    T: TableMetadata
+        + DatabaseExecutor
+        + for<'r> sqlx::FromRow<'r, PgRow>
+        + serde::Serialize
+        + Unpin,
+{
+    /// Build the full SELECT statement for a query, honoring soft delete
+    fn build_rows_sql(
+        &self,
+        query: &QueryBuilder,
+    ) -> Result<(String, Vec<QueryParam>), StorehausError> {
+        let query = Self::checked_query(query)?;
+        let soft_delete_condition = if T::supports_soft_delete() {
+            T::soft_delete_field().map(|field| format!("{}.{} = TRUE", T::table_name(), field))
+        } else {
+            None
+        };
+
+        Ok(query.build_typed_select_sql(
+            T::table_name(),
+            soft_delete_condition.as_deref(),
+            &Self::column_types(),
+        ))
+    }
+
+    /// Execute the full query (SELECT fields, JOINs, GROUP BY, HAVING) and return JSON rows
+    ///
+    /// Unlike `find`, nothing in the query is dropped; each row is keyed by
+    /// column name or alias.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let query = QueryBuilder::new()
+    ///     .select_fields(vec![
+    ///         SelectField::field("users.name"),
+    ///         SelectField::count_all().with_alias("order_count"),
+    ///     ])
+    ///     .join(JoinClause::new_on(JoinType::Inner, "orders", "users.id", "orders.user_id"))
+    ///     .group_by(GroupBy::single("users.name"));
+    ///
+    /// for row in user_store.find_rows(query).await? {
+    ///     println!("{} -> {}", row["name"], row["order_count"]);
+    /// }
+    /// ```
+    pub async fn find_rows(&self, query: QueryBuilder) -> Result<Vec<JsonRow>, StorehausError> {
+        let (sql, params) = self.build_rows_sql(&query)?;
+
+        let mut sqlx_query = sqlx::query(&sql);
+        for value in Self::bind_values(&params)? {
+            sqlx_query = self.bind_param_raw(sqlx_query, value);
+        }
+
+        let rows = sqlx_query
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| StorehausError::query_execution(T::table_name(), &sql, e))?;
+
+        rows.iter()
+            .map(row_to_json)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| StorehausError::query_execution(T::table_name(), &sql, e))
+    }
+
+    /// Execute the full query and map each row into a user `FromRow` type
+    pub async fn find_rows_as<R>(&self, query: QueryBuilder) -> Result<Vec<R>, StorehausError>
+    where
+        R: for<'r> sqlx::FromRow<'r, PgRow> + Send + Unpin,
+    {
+        let (sql, params) = self.build_rows_sql(&query)?;
+
+        let mut sqlx_query = sqlx::query_as::<_, R>(&sql);
+        for value in Self::bind_values(&params)? {
+            sqlx_query = self.bind_param_as(sqlx_query, value);
+        }
+
+        sqlx_query
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| StorehausError::query_execution(T::table_name(), &sql, e))
+    }
+
+    /// Execute an ungrouped aggregation (e.g. `COUNT(*)`, `SUM(total)`) and return its single row
+    ///
+    /// The query must select at least one aggregate. Grouped aggregations
+    /// return one row per group; use `find_rows` for those. Queries that are
+    /// not aggregations or return several rows fail with a `ValidationError`.
+    pub async fn aggregate(&self, query: QueryBuilder) -> Result<JsonRow, StorehausError> {
+        if query.group_by.is_some() {
+            return Err(StorehausError::validation(
+                T::table_name(),
+                "group_by",
+                "aggregate returns a single row; use find_rows for grouped queries",
+            ));
+        }
+        if !query
+            .select_fields
+            .iter()
+            .any(|field| matches!(field, SelectField::Aggregate { .. }))
+        {
+            return Err(StorehausError::validation(
+                T::table_name(),
+                "select_fields",
+                "aggregate needs an aggregate SELECT field such as COUNT(*) or SUM(total)",
+            ));
+        }
+
+        let mut rows = self.find_rows(query).await?;
+        if rows.len() > 1 {
+            return Err(StorehausError::validation(
+                T::table_name(),
+                "select_fields",
+                "aggregate returned more than one row; use find_rows",
+            ));
+        }
+        Ok(rows.pop().unwrap_or_default())
+    }
+}
+
+/// Convert a row into a JSON object using each column's PostgreSQL type
+fn row_to_json(row: &PgRow) -> Result<JsonRow, sqlx::Error> {
+    let mut object = Map::with_capacity(row.len());
+
+    for (index, column) in row.columns().iter().enumerate() {
+        object.insert(column.name().to_string(), column_json(row, index)?);
+    }
+
+    Ok(object)
+}
+
+/// Value of one column, by index or name, as JSON using the column's PostgreSQL type
+pub(crate) fn column_json<I>(row: &PgRow, index: I) -> Result<Value, sqlx::Error>
+where
+    I: sqlx::ColumnIndex<PgRow>,
+{
+    let index = index.index(row)?;
+    if row.try_get_raw(index)?.is_null() {
+        return Ok(Value::Null);
+    }
+    column_to_json(row, index, row.column(index).type_info().name())
+}
+
+fn column_to_json(row: &PgRow, index: usize, type_name: &str) -> Result<Value, sqlx::Error> {
+    let value = match type_name {
+        "BOOL" => Value::from(row.try_get::<bool, _>(index)?),
+        "INT2" => Value::from(row.try_get::<i16, _>(index)?),
+        "INT4" => Value::from(row.try_get::<i32, _>(index)?),
+        "INT8" => Value::from(row.try_get::<i64, _>(index)?),
+        "FLOAT4" => Value::from(row.try_get::<f32, _>(index)?),
+        "FLOAT8" => Value::from(row.try_get::<f64, _>(index)?),
+        "NUMERIC" => {
+            // Keep integers exact; fractional values become JSON floats
+            let decimal = row.try_get::<sqlx::types::Decimal, _>(index)?.normalize();
+            serde_json::from_str::<serde_json::Number>(&decimal.to_string())
+                .map(Value::Number)
+                .unwrap_or_else(|_| Value::String(decimal.to_string()))
+        }
+        "UUID" => Value::from(row.try_get::<uuid::Uuid, _>(index)?.to_string()),
+        "TIMESTAMPTZ" => Value::from(
+            row.try_get::<chrono::DateTime<chrono::Utc>, _>(index)?
+                .to_rfc3339(),
+        ),
+        "TIMESTAMP" => Value::from(row.try_get::<chrono::NaiveDateTime, _>(index)?.to_string()),
+        "DATE" => Value::from(row.try_get::<chrono::NaiveDate, _>(index)?.to_string()),
+        "JSON" | "JSONB" => row.try_get::<Value, _>(index)?,
+        "TEXT[]" | "VARCHAR[]" => Value::from(row.try_get::<Vec<String>, _>(index)?),
+        "INT4[]" => Value::from(row.try_get::<Vec<i32>, _>(index)?),
+        "INT8[]" => Value::from(row.try_get::<Vec<i64>, _>(index)?),
+        "UUID[]" => Value::from(
+            row.try_get::<Vec<uuid::Uuid>, _>(index)?
+                .into_iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>(),
+        ),
+        // TEXT, VARCHAR, BPCHAR, NAME and anything else readable as text
+        _ => Value::from(row.try_get::<String, _>(index)?),
+    };
+
+    Ok(value)
+}
@@ -78,7 +78,13 @@ where
             .await
             .map_err(|e| StorehausError::database_operation(T::table_name(), "set_active", e))?;
 
-        Ok(result.rows_affected() > 0)
+        let changed = result.rows_affected() > 0;
+        if changed {
+            self.evict_record_cache(std::slice::from_ref(id)).await;
+            self.invalidate_query_cache().await;
+        }
+
+        Ok(changed)
     }
 
     async fn count_active(&self) -> Result<i64, StorehausError> {
@@ -2,13 +2,16 @@
 //!
 //! This module provides generic database store functionality.
 
+use super::binding::BindValue;
 use super::core::GenericStore;
+use super::upsert::{OnConflict, UpsertOutcome};
 use crate::errors::StorehausError;
 use crate::id_type::HasUniversalId;
 use crate::table_metadata::TableMetadata;
 use crate::traits::table_metadata::DatabaseExecutor;
 use crate::traits::StoreObject;
 use async_trait::async_trait;
+use cache_system::CacheParams;
 use sqlx::Row;
 
 /// Helper function to efficiently convert any ID to UniversalId and then to string
@@ -17,6 +20,16 @@ fn id_to_string<ID: HasUniversalId>(id: ID) -> String {
     id.universal_id().to_string_fast()
 }
 
+/// Cache key hash of a query: the generated SQL together with its bound parameters
+fn query_cache_hash(
+    cache_manager: &cache_system::CacheManager,
+    sql: &str,
+    params: &[crate::query_builder::QueryParam],
+) -> String {
+    let params = serde_json::to_string(params).unwrap_or_default();
+    cache_manager.hash_query(&(sql, params))
+}
+
 #[async_trait]
 impl<T> StoreObject for GenericStore<T>
 where
@@ -33,96 +46,137 @@ where
         data: Self::Model,
         tags: Option<Vec<String>>,
     ) -> Result<Self::Model, StorehausError> {
-        let created = data.execute_create(&self.db_pool).await?;
-
-        // Emit create signal if signal manager is present
-        if self.signal_manager.is_some() {
-            let event = if let Some(tags) = tags {
-                self.create_event_with_explicit_tags(
-                    signal_system::EventType::Create,
-                    T::table_name().to_string(),
-                    &created,
-                    None,
-                    tags,
-                )
-            } else {
-                self.create_event_with_tags(
-                    signal_system::EventType::Create,
-                    T::table_name().to_string(),
-                    &created,
-                    None,
-                )
-            };
-            self.emit_signal(event).await;
+        let mut tx = self.begin_outbox_write().await?;
+        let created = match tx.as_mut() {
+            Some(tx) => data.execute_create_tx(tx).await?,
+            None => data.execute_create(&self.db_pool).await?,
+        };
+
+        // Build create signal if signals are enabled
+        let mut events = Vec::new();
+        if self.has_event_sink() {
+            events.push(self.created_event(&created, tags));
+        }
+        let events = self.commit_write(tx, events).await?;
+
+        // Forget a cached miss for this id, then drop query caches since data changed
+        if self
+            .cache_params
+            .as_ref()
+            .is_some_and(|cp| cp.negative_ttl.is_some())
+        {
+            self.evict_record_cache(&[created.extract_id()]).await;
         }
+        self.invalidate_query_cache().await;
+
+        self.emit_signals(events).await;
 
         Ok(created)
     }
 
-    async fn get_by_id(&self, id: &Self::Id) -> Result<Option<Self::Model>, StorehausError> {
-        // Try cache first if cache manager is present
-        if let Some(cache_manager) = self.cache_manager() {
-            let cache_prefix = self.get_cache_prefix();
-
-            // Use a pre-allocated buffer to avoid repeated allocations
-            use std::fmt::Write;
-            let mut id_buffer = String::with_capacity(64); // Pre-allocate reasonable size
-            let _ = write!(id_buffer, "{:?}", id);
-
-            // Try to get from cache
-            match cache_manager
-                .get_record::<T>(cache_prefix, T::table_name(), &id_buffer)
-                .await
-            {
-                Ok(Some(cached)) => return Ok(Some(cached)),
-                Ok(None) => {} // Not in cache, continue to database
-                Err(e) => {
-                    return Err(StorehausError::cache_operation(
-                        "get_by_id",
-                        Some(&id_buffer),
-                        Box::new(e),
-                    ))
-                }
-            }
+    async fn create_many(
+        &self,
+        data: Vec<Self::Model>,
+    ) -> Result<Vec<Self::Model>, StorehausError> {
+        if data.is_empty() {
+            return Ok(Vec::new());
         }
 
-        // Get from database - use static SQL to avoid allocations
-        let result = sqlx::query_as::<_, T>(T::get_by_id_sql())
-            .bind(id)
-            .fetch_optional(&self.db_pool)
+        // Use transaction for batch creates
+        let mut tx = self
+            .db_pool
+            .begin()
             .await
-            .map_err(|e| StorehausError::query_execution(T::table_name(), T::get_by_id_sql(), e))?;
-
-        // Cache the result if found and cache manager is present
-        if let (Some(record), Some(cache_manager)) = (&result, self.cache_manager()) {
-            let cache_prefix = self.get_cache_prefix();
-            let cache_ttl = self.get_cache_ttl();
-
-            // Reuse the same buffer approach for consistency
-            use std::fmt::Write;
-            let mut id_buffer = String::with_capacity(64);
-            let _ = write!(id_buffer, "{:?}", id);
-
-            // Store in cache
-            if let Err(e) = cache_manager
-                .set_record_with_ttl(
-                    cache_prefix,
-                    T::table_name(),
-                    &id_buffer,
-                    record,
-                    cache_ttl,
-                )
+            .map_err(|e| StorehausError::transaction("begin", e))?;
+        let created = Self::execute_create_many(&mut tx, &data).await?;
+
+        // Build single batch create signal if signals are enabled
+        let mut events = Vec::new();
+        if self.has_event_sink() {
+            events.push(self.created_many_event(&created));
+        }
+        let events = self.commit_write(Some(tx), events).await?;
+
+        // Forget cached misses for these ids, then drop query caches since data changed
+        if self
+            .cache_params
+            .as_ref()
+            .is_some_and(|cp| cp.negative_ttl.is_some())
+        {
+            let ids: Vec<T::Id> = created.iter().map(|record| record.extract_id()).collect();
+            self.evict_record_cache(&ids).await;
+        }
+        self.invalidate_query_cache().await;
+
+        self.emit_signals(events).await;
+
+        Ok(created)
+    }
+
+    async fn get_by_id(&self, id: &Self::Id) -> Result<Option<Self::Model>, StorehausError> {
+        let Some(cache_params) = &self.cache_params else {
+            return self.fetch_by_id(id).await;
+        };
+        let cache_manager = &cache_params.manager;
+        let cache_prefix = self.get_cache_prefix();
+        let id_buffer = Self::record_cache_key(id);
+        let beta = cache_params.stampede.early_expiration_beta;
+
+        // Try cache first; hot records may be refreshed shortly before they expire.
+        // A cached `null` records an id that doesn't exist (negative caching).
+        let cached = if beta > 0.0 {
+            cache_manager
+                .get_record_with_ttl::<Option<T>>(cache_prefix, T::table_name(), &id_buffer)
+                .await
+                .map(|cached| {
+                    cached.filter(|(_, remaining)| {
+                        !cache_system::stampede::should_refresh_early(
+                            *remaining,
+                            self.get_cache_ttl(),
+                            beta,
+                        )
+                    })
+                })
+                .map(|cached| cached.map(|(record, _)| record))
+        } else {
+            cache_manager
+                .get_record::<Option<T>>(cache_prefix, T::table_name(), &id_buffer)
                 .await
-            {
+        };
+        match cached {
+            Ok(Some(cached)) => return Ok(cached),
+            Ok(None) => {} // Not in cache, continue to database
+            Err(e) => {
                 return Err(StorehausError::cache_operation(
-                    "get_by_id_cache_set",
+                    "get_by_id",
                     Some(&id_buffer),
                     Box::new(e),
-                ));
+                ))
             }
         }
 
-        Ok(result)
+        // Concurrent misses for the same record share one load
+        let loaded = if cache_params.stampede.single_flight {
+            cache_manager
+                .load_record_json(cache_prefix, T::table_name(), &id_buffer, || {
+                    self.load_record_json(cache_params, id, &id_buffer)
+                })
+                .await?
+        } else {
+            self.load_record_json(cache_params, id, &id_buffer).await?
+        };
+
+        loaded
+            .map(|json_str| serde_json::from_str::<Option<T>>(&json_str))
+            .transpose()
+            .map(Option::flatten)
+            .map_err(|e| {
+                StorehausError::cache_operation(
+                    "get_by_id",
+                    Some(&id_buffer),
+                    Box::new(cache_system::CacheError::from(e)),
+                )
+            })
     }
 
     async fn list_all(&self) -> Result<Vec<Self::Model>, StorehausError> {
@@ -140,80 +194,125 @@ where
         data: Self::Model,
         tags: Option<Vec<String>>,
     ) -> Result<Self::Model, StorehausError> {
-        let updated = data.execute_update(&self.db_pool).await?;
-
-        // Emit update signal if signal manager is present
-        if self.signal_manager.is_some() {
-            let mut event = if let Some(tags) = tags {
-                self.create_event_with_explicit_tags(
-                    signal_system::EventType::Update,
-                    T::table_name().to_string(),
-                    &updated,
-                    Some(id_to_string(id.clone())),
-                    tags,
-                )
-            } else {
-                self.create_event_with_tags(
-                    signal_system::EventType::Update,
-                    T::table_name().to_string(),
-                    &updated,
-                    Some(id_to_string(id.clone())),
-                )
-            };
-
-            // Add the full record from database as __record__ associative array
-            let full_record = signal_system::serialize_to_postgres_record(&updated);
-            event.add_payload("__record__".to_string(), full_record);
+        let mut tx = self.begin_outbox_write().await?;
+        let updated = match tx.as_mut() {
+            Some(tx) => data.execute_update_tx(tx).await?,
+            None => data.execute_update(&self.db_pool).await?,
+        };
 
-            self.emit_signal(event).await;
+        // Build update signal if signals are enabled
+        let mut events = Vec::new();
+        if self.has_event_sink() {
+            events.push(self.updated_event(id, &updated, tags));
         }
+        let events = self.commit_write(tx, events).await?;
+
+        // Refresh or evict the cached record, then drop query caches since data changed
+        self.refresh_record_cache(std::slice::from_ref(&updated)).await;
+        self.invalidate_query_cache().await;
 
-        // Invalidate cache after update
-        if let Some(cache_manager) = self.cache_manager() {
-            let cache_prefix = self.get_cache_prefix();
-            let id_str = id_to_string(id.clone());
+        self.emit_signals(events).await;
 
-            // Delete the specific record from cache
-            let _ = cache_manager
-                .delete_record(cache_prefix, T::table_name(), &id_str)
-                .await;
+        Ok(updated)
+    }
 
-            // Invalidate all query caches for this table since data changed
-            let _ = cache_manager
-                .invalidate_queries(cache_prefix, T::table_name())
-                .await;
+    async fn upsert(
+        &self,
+        data: Self::Model,
+        conflict_target: OnConflict,
+        tags: Option<Vec<String>>,
+    ) -> Result<UpsertOutcome<Self::Model>, StorehausError> {
+        let sql = conflict_target.to_sql::<T>()?;
+
+        let mut tx = self.begin_outbox_write().await?;
+        let action = conflict_target.action;
+        let outcome = match tx.as_mut() {
+            Some(tx) => Self::execute_upsert(&mut **tx, &data, &sql, action).await?,
+            None => Self::execute_upsert(&self.db_pool, &data, &sql, action).await?,
+        };
+
+        // Build create or update signal if signals are enabled
+        let mut events = Vec::new();
+        if self.has_event_sink() {
+            events.extend(self.upserted_event(&outcome, tags));
         }
+        let events = self.commit_write(tx, events).await?;
 
-        Ok(updated)
+        if let Some(record) = outcome.record() {
+            self.refresh_record_cache(std::slice::from_ref(record)).await;
+            self.invalidate_query_cache().await;
+        }
+
+        self.emit_signals(events).await;
+
+        Ok(outcome)
     }
 
-    async fn update_many(
+    async fn upsert_many(
         &self,
-        updates: Vec<(Self::Id, Self::Model)>,
-    ) -> Result<Vec<Self::Model>, StorehausError> {
-        let mut results = Vec::new();
-        let mut all_updated_data = Vec::new();
+        data: Vec<Self::Model>,
+        conflict_target: OnConflict,
+    ) -> Result<Vec<UpsertOutcome<Self::Model>>, StorehausError> {
+        let sql = conflict_target.to_sql::<T>()?;
 
-        // Use transaction for batch updates
+        // Use transaction for batch upserts
         let mut tx = self
             .db_pool
             .begin()
             .await
-            .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
+            .map_err(|e| StorehausError::transaction("begin", e))?;
 
-        for (id, data) in updates {
-            let updated = data.execute_update_tx(&mut tx).await?;
+        let mut outcomes = Vec::with_capacity(data.len());
+        for model in &data {
+            outcomes.push(
+                Self::execute_upsert(&mut *tx, model, &sql, conflict_target.action).await?,
+            );
+        }
 
-            results.push(updated.clone());
-            all_updated_data.push((id, data, updated));
+        // One create or update signal per stored record
+        let mut events = Vec::new();
+        if self.has_event_sink() {
+            events.extend(
+                outcomes
+                    .iter()
+                    .filter_map(|outcome| self.upserted_event(outcome, None)),
+            );
+        }
+        let events = self.commit_write(Some(tx), events).await?;
+
+        let records: Vec<T> = outcomes
+            .iter()
+            .filter_map(|outcome| outcome.record().cloned())
+            .collect();
+        if !records.is_empty() {
+            self.refresh_record_cache(&records).await;
+            self.invalidate_query_cache().await;
         }
 
-        tx.commit()
+        self.emit_signals(events).await;
+
+        Ok(outcomes)
+    }
+
+    async fn update_many(
+        &self,
+        updates: Vec<(Self::Id, Self::Model)>,
+    ) -> Result<Vec<Self::Model>, StorehausError> {
+        if updates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Use transaction for batch updates spanning several statements
+        let mut tx = self
+            .db_pool
+            .begin()
             .await
-            .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
+            .map_err(|e| StorehausError::transaction("begin", e))?;
+        let results = Self::execute_update_many(&mut tx, &updates).await?;
 
-        // Emit single batch update signal if signal manager is present
-        if self.signal_manager.is_some() && !all_updated_data.is_empty() {
+        // Build single batch update signal if signals are enabled
+        let mut events = Vec::new();
+        if self.has_event_sink() {
             let mut event = signal_system::DatabaseEvent::new(
                 signal_system::EventType::Update,
                 T::table_name().to_string(),
@@ -221,7 +320,7 @@ where
 
             // Combine all updated fields into unified payload
             let mut combined_payload = std::collections::HashMap::new();
-            for (id, data, _) in &all_updated_data {
+            for (id, data) in &updates {
                 let data_payload = signal_system::serialize_to_postgres_payload(&data);
                 for (key, value) in data_payload {
                     // Use field name with record ID to avoid conflicts
@@ -236,9 +335,9 @@ where
             }
 
             // Add all full records as __record__ array
-            let all_records: Vec<signal_system::PostgresValue> = all_updated_data
+            let all_records: Vec<signal_system::PostgresValue> = results
                 .iter()
-                .map(|(_, _, updated)| signal_system::serialize_to_postgres_record(updated))
+                .map(signal_system::serialize_to_postgres_record)
                 .collect();
 
             event.add_payload(
@@ -248,8 +347,14 @@ where
                 ),
             );
 
-            self.emit_signal(event).await;
+            events.push(event);
         }
+        let events = self.commit_write(Some(tx), events).await?;
+
+        self.refresh_record_cache(&results).await;
+        self.invalidate_query_cache().await;
+
+        self.emit_signals(events).await;
 
         Ok(results)
     }
@@ -262,108 +367,69 @@ where
         }
 
         // Hard delete for models without soft delete support
-        let result = sqlx::query(T::delete_by_id_sql())
-            .bind(id)
-            .execute(&self.db_pool)
-            .await
-            .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
+        let mut tx = self.begin_outbox_write().await?;
+        let query = sqlx::query(T::delete_by_id_sql()).bind(id);
+        let result = match tx.as_mut() {
+            Some(tx) => query.execute(&mut **tx).await,
+            None => query.execute(&self.db_pool).await,
+        }
+        .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
 
         let deleted = result.rows_affected() > 0;
 
-        // Emit delete signal if signal manager is present
-        if deleted && self.signal_manager.is_some() {
-            let event = signal_system::DatabaseEvent::new(
-                signal_system::EventType::Delete,
-                T::table_name().to_string(),
-            )
-            .with_record_id(id_to_string(id.clone()));
-            self.emit_signal(event).await;
+        // Build delete signal if signals are enabled
+        let mut events = Vec::new();
+        if deleted && self.has_event_sink() {
+            events.push(self.deleted_event(id));
         }
+        let events = self.commit_write(tx, events).await?;
+
+        if deleted {
+            self.evict_record_cache(std::slice::from_ref(id)).await;
+            self.invalidate_query_cache().await;
+        }
+
+        self.emit_signals(events).await;
 
         Ok(deleted)
     }
 
     async fn delete_many(&self, ids: Vec<Self::Id>) -> Result<Vec<Self::Id>, StorehausError> {
-        // If model supports soft delete, use soft delete for all IDs
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Models with soft delete support are deactivated by the same single statement
         if T::supports_soft_delete() {
-            use crate::traits::SoftDeletable;
-            let mut deleted_ids = Vec::new();
-            for id in ids {
-                if self.set_active(&id, false).await? {
-                    deleted_ids.push(id);
-                }
+            let deleted_ids = Self::execute_delete_many(&self.db_pool, &ids).await?;
+            if !deleted_ids.is_empty() {
+                self.evict_record_cache(&deleted_ids).await;
+                self.invalidate_query_cache().await;
             }
             return Ok(deleted_ids);
         }
 
         // Hard delete for models without soft delete support
-        let mut deleted_ids = Vec::new();
-
-        // Use transaction for batch deletes
-        let mut tx = self
-            .db_pool
-            .begin()
-            .await
-            .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
-
-        for id in ids {
-            let result = sqlx::query(T::delete_by_id_sql())
-                .bind(&id)
-                .execute(tx.as_mut())
-                .await
-                .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
-
-            let was_deleted = result.rows_affected() > 0;
+        let mut tx = self.begin_outbox_write().await?;
+        let deleted_ids = match tx.as_mut() {
+            Some(tx) => Self::execute_delete_many(&mut **tx, &ids).await?,
+            None => Self::execute_delete_many(&self.db_pool, &ids).await?,
+        };
 
-            if was_deleted {
-                deleted_ids.push(id);
-            }
+        // Build single batch delete signal if signals are enabled and records were deleted
+        let mut events = Vec::new();
+        if self.has_event_sink() && !deleted_ids.is_empty() {
+            events.push(self.deleted_many_event(&deleted_ids));
         }
+        let events = self.commit_write(tx, events).await?;
 
-        tx.commit()
-            .await
-            .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
-
-        // Emit single batch delete signal if signal manager is present and records were deleted
-        if self.signal_manager.is_some() && !deleted_ids.is_empty() {
-            let mut event = signal_system::DatabaseEvent::new(
-                signal_system::EventType::Delete,
-                T::table_name().to_string(),
-            );
-
-            // Get the primary key field name from TableMetadata
-            let primary_key_name = T::primary_key_field();
-
-            // Convert deleted IDs to PostgresValue array
-            let ids_as_postgres: Vec<signal_system::PostgresValue> = deleted_ids
-                .iter()
-                .map(|id| {
-                    // Try to convert ID to appropriate PostgresValue type
-                    let id_str = id_to_string(id.clone());
-                    if id_str.starts_with('"') && id_str.ends_with('"') {
-                        // String/UUID
-                        signal_system::PostgresValue::Text(id_str.trim_matches('"').to_string())
-                    } else if let Ok(int_id) = id_str.parse::<i32>() {
-                        signal_system::PostgresValue::Integer(int_id)
-                    } else if let Ok(bigint_id) = id_str.parse::<i64>() {
-                        signal_system::PostgresValue::BigInt(bigint_id)
-                    } else {
-                        // Fallback to text
-                        signal_system::PostgresValue::Text(id_str)
-                    }
-                })
-                .collect();
-
-            event.add_payload(
-                primary_key_name.to_string(),
-                signal_system::PostgresValue::Json(
-                    serde_json::to_value(ids_as_postgres).unwrap_or_default(),
-                ),
-            );
-
-            self.emit_signal(event).await;
+        if !deleted_ids.is_empty() {
+            self.evict_record_cache(&deleted_ids).await;
+            self.invalidate_query_cache().await;
         }
 
+        self.emit_signals(events).await;
+
         Ok(deleted_ids)
     }
 
@@ -378,35 +444,27 @@ where
     }
 
     async fn find(&self, query: crate::QueryBuilder) -> Result<Vec<Self::Model>, StorehausError> {
-        let (where_clause, order_clause, limit_clause, params) = query.build();
-        // Avoid format! allocation by building string directly
-        let base_sql = T::select_base_sql();
-        let mut full_sql = String::with_capacity(
-            base_sql.len() + where_clause.len() + order_clause.len() + limit_clause.len(),
-        );
-        full_sql.push_str(base_sql);
-        if !where_clause.is_empty() {
-            full_sql.push(' ');
-            // If base_sql already has WHERE (soft delete), replace WHERE with AND
-            if base_sql.contains(" WHERE ") && where_clause.starts_with("WHERE ") {
-                full_sql.push_str("AND ");
-                full_sql.push_str(&where_clause[6..]); // Skip "WHERE "
-            } else {
-                full_sql.push_str(&where_clause);
+        let (full_sql, params) = Self::build_find_sql(&query)?;
+        let values = Self::bind_values(&params)?;
+
+        // Try cached results first if query caching is enabled
+        // Locking reads always go to the database
+        let cache_hash = self
+            .query_cache_manager()
+            .filter(|_| query.get_lock().is_none())
+            .map(|cache_manager| query_cache_hash(cache_manager, &full_sql, &params));
+        let mut cache_key = None;
+        if let Some(hash) = &cache_hash {
+            let (cached, key) = self.get_cached_query::<T>("find", hash).await;
+            if let Some(cached) = cached {
+                return Ok(cached);
             }
-        }
-        if !order_clause.is_empty() {
-            full_sql.push(' ');
-            full_sql.push_str(&order_clause);
-        }
-        if !limit_clause.is_empty() {
-            full_sql.push(' ');
-            full_sql.push_str(&limit_clause);
+            cache_key = key; // Not in cache, continue to database
         }
 
         let mut sqlx_query = sqlx::query_as::<_, T>(&full_sql);
-        for param in params {
-            sqlx_query = self.bind_param(sqlx_query, param);
+        for value in values {
+            sqlx_query = self.bind_param(sqlx_query, value);
         }
 
         let results = sqlx_query
@@ -414,6 +472,10 @@ where
             .await
             .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
 
+        if let Some(key) = &cache_key {
+            self.set_cached_query("find", key, &results).await;
+        }
+
         Ok(results)
     }
 
@@ -432,29 +494,33 @@ where
         query: crate::QueryBuilder,
         data: Option<Self::Model>,
     ) -> Result<Vec<Self::Model>, StorehausError> {
-        // Build the WHERE clause from the query
-        let (where_clause, _, _, params) = query.build();
+        let query = Self::checked_query(&query)?;
+        let columns = Self::column_types();
 
         // Check if query has custom update operations
-        let (set_clause, update_values, num_update_params) = if let Some(updates) = query.get_updates() {
+        let (set_clause, update_params, num_update_params) = if let Some(updates) = query.get_updates() {
             // Use custom update operations (e.g., increment, decrement)
             let mut assignments = Vec::new();
-            let mut values = Vec::new();
-            let mut param_num = 1;
+            let mut params = Vec::new();
+            let mut param_num: usize = 1;
 
             // Sort operations by field name for consistent ordering
             let mut ops: Vec<_> = updates.operations.iter().collect();
             ops.sort_by(|a, b| a.0.cmp(b.0));
 
             for (field_name, operation) in ops {
-                let sql_expr = operation.to_sql(field_name, param_num);
-                assignments.push(sql_expr);
-                values.push(operation.value().clone());
+                let param = crate::query_builder::QueryParam::new(
+                    field_name,
+                    operation.value().clone(),
+                    columns.get(field_name),
+                );
+                assignments.push(operation.to_sql_with(field_name, &param.placeholder(param_num as i32)));
+                params.push(param);
                 param_num += 1;
             }
 
             let set_clause = assignments.join(", ");
-            (set_clause, values, param_num - 1)
+            (set_clause, params, param_num - 1)
         } else {
             // Use legacy approach: extract all update fields from the model
             let update_fields = T::update_fields();
@@ -474,19 +540,11 @@ where
             (set_clause, Vec::new(), update_fields.len())
         };
 
-        // Adjust WHERE clause parameter numbers to come after UPDATE parameters
-        let mut adjusted_where_clause = if !params.is_empty() {
-            let mut where_str = where_clause.clone();
-            // Replace $1, $2, ... in WHERE clause with correct numbers after UPDATE params
-            for i in (1..=params.len()).rev() {
-                let old_param = format!("${}", i);
-                let new_param = format!("${}", num_update_params + i);
-                where_str = where_str.replace(&old_param, &new_param);
-            }
-            where_str
-        } else {
-            where_clause.clone()
-        };
+        // WHERE parameters are numbered after the UPDATE parameters
+        let (mut adjusted_where_clause, _, _, params) =
+            query.build_typed(&columns, num_update_params as i32 + 1);
+        let update_values = Self::bind_values(&update_params)?;
+        let where_values = Self::bind_values(&params)?;
 
         // Add soft delete filter for models with soft delete support
         if T::supports_soft_delete() {
@@ -499,11 +557,17 @@ where
             }
         }
 
+        // Bump the version so concurrent readers of versioned records see a conflict
+        let version_bump = T::version_field()
+            .map(|field| format!(", {} = {} + 1", field, field))
+            .unwrap_or_default();
+
         // Build UPDATE statement with RETURNING clause to get updated records
         let sql = format!(
-            "UPDATE {} SET {}, __updated_at__ = NOW() {} RETURNING *",
+            "UPDATE {} SET {}{}, __updated_at__ = NOW() {} RETURNING *",
             T::table_name(),
             set_clause,
+            version_bump,
             adjusted_where_clause
         );
 
@@ -514,6 +578,7 @@ where
         tracing::debug!("[UPDATE_WHERE] Using custom updates: {}", query.has_updates());
 
         // Bind parameters and execute based on which mode we're using
+        let mut tx = self.begin_outbox_write().await?;
         let updated_records = if query.has_updates() {
             // Custom updates mode: bind the update values manually
             let mut q = sqlx::query_as::<_, T>(&sql);
@@ -523,15 +588,16 @@ where
 
             // Then bind WHERE clause parameters
             let mut sqlx_query = q;
-            for param in params {
-                sqlx_query = self.bind_param(sqlx_query, param);
+            for value in where_values {
+                sqlx_query = self.bind_param(sqlx_query, value);
             }
 
             // Execute the query
-            sqlx_query
-                .fetch_all(&self.db_pool)
-                .await
-                .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?
+            match tx.as_mut() {
+                Some(tx) => sqlx_query.fetch_all(&mut **tx).await,
+                None => sqlx_query.fetch_all(&self.db_pool).await,
+            }
+            .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?
         } else {
             // Legacy mode: use model's bind method
             let model = data.ok_or_else(|| {
@@ -543,62 +609,30 @@ where
             let mut sqlx_query = model.bind_update_params_owned(&sql);
 
             // Then bind WHERE clause parameters
-            for param in params {
-                sqlx_query = self.bind_param(sqlx_query, param);
+            for value in where_values {
+                sqlx_query = self.bind_param(sqlx_query, value);
             }
 
             // Execute the query
-            sqlx_query
-                .fetch_all(&self.db_pool)
-                .await
-                .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?
+            match tx.as_mut() {
+                Some(tx) => sqlx_query.fetch_all(&mut **tx).await,
+                None => sqlx_query.fetch_all(&self.db_pool).await,
+            }
+            .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?
         };
 
-        // Emit signals for updated records if signal manager is present
-        if self.signal_manager.is_some() && !updated_records.is_empty() {
-            let mut event = signal_system::DatabaseEvent::new(
-                signal_system::EventType::Update,
-                T::table_name().to_string(),
-            );
-
-            // Add all full records as __record__ array
-            let all_records: Vec<signal_system::PostgresValue> = updated_records
-                .iter()
-                .map(|record| signal_system::serialize_to_postgres_record(record))
-                .collect();
-
-            event.add_payload(
-                "__record__".to_string(),
-                signal_system::PostgresValue::Json(
-                    serde_json::to_value(all_records).unwrap_or_default(),
-                ),
-            );
-
-            // Add count of updated records
-            event.add_payload(
-                "updated_count".to_string(),
-                signal_system::PostgresValue::Integer(updated_records.len() as i32),
-            );
-
-            self.emit_signal(event).await;
+        // Build signal for updated records if signals are enabled
+        let mut events = Vec::new();
+        if self.has_event_sink() && !updated_records.is_empty() {
+            events.push(self.updated_where_event(&updated_records));
         }
+        let events = self.commit_write(tx, events).await?;
 
-        // Invalidate cache for all updated records
-        if let Some(cache_manager) = self.cache_manager() {
-            let cache_prefix = self.get_cache_prefix();
+        // Refresh or evict cached records for all updated rows
+        self.refresh_record_cache(&updated_records).await;
+        self.invalidate_query_cache().await;
 
-            for record in &updated_records {
-                let id_str = id_to_string(record.extract_id());
-                let _ = cache_manager
-                    .delete_record(cache_prefix, T::table_name(), &id_str)
-                    .await;
-            }
-
-            // Invalidate all query caches for this table since data changed
-            let _ = cache_manager
-                .invalidate_queries(cache_prefix, T::table_name())
-                .await;
-        }
+        self.emit_signals(events).await;
 
         Ok(updated_records)
     }
@@ -607,6 +641,8 @@ where
     ///
     /// This is identical to `update_where` but accepts any executor (Pool or Transaction).
     /// Use this when you need to execute updates within a database transaction.
+    /// Signals and cache updates are left to the caller; `GenericStoreTransaction::update_where`
+    /// handles them on commit.
     ///
     /// # Example
     /// ```ignore
@@ -629,29 +665,33 @@ where
     where
         E: sqlx::Executor<'e, Database = sqlx::Postgres>,
     {
-        // Build the WHERE clause from the query
-        let (where_clause, _, _, params) = query.build();
+        let query = Self::checked_query(&query)?;
+        let columns = Self::column_types();
 
         // Check if query has custom update operations
-        let (set_clause, update_values, num_update_params) = if let Some(updates) = query.get_updates() {
+        let (set_clause, update_params, num_update_params) = if let Some(updates) = query.get_updates() {
             // Use custom update operations (e.g., increment, decrement)
             let mut assignments = Vec::new();
-            let mut values = Vec::new();
-            let mut param_num = 1;
+            let mut params = Vec::new();
+            let mut param_num: usize = 1;
 
             // Sort operations by field name for consistent ordering
             let mut ops: Vec<_> = updates.operations.iter().collect();
             ops.sort_by(|a, b| a.0.cmp(b.0));
 
             for (field_name, operation) in ops {
-                let sql_expr = operation.to_sql(field_name, param_num);
-                assignments.push(sql_expr);
-                values.push(operation.value().clone());
+                let param = crate::query_builder::QueryParam::new(
+                    field_name,
+                    operation.value().clone(),
+                    columns.get(field_name),
+                );
+                assignments.push(operation.to_sql_with(field_name, &param.placeholder(param_num as i32)));
+                params.push(param);
                 param_num += 1;
             }
 
             let set_clause = assignments.join(", ");
-            (set_clause, values, param_num - 1)
+            (set_clause, params, param_num - 1)
         } else {
             // Use legacy approach: extract all update fields from the model
             let update_fields = T::update_fields();
@@ -671,19 +711,11 @@ where
             (set_clause, Vec::new(), update_fields.len())
         };
 
-        // Adjust WHERE clause parameter numbers to come after UPDATE parameters
-        let mut adjusted_where_clause = if !params.is_empty() {
-            let mut where_str = where_clause.clone();
-            // Replace $1, $2, ... in WHERE clause with correct numbers after UPDATE params
-            for i in (1..=params.len()).rev() {
-                let old_param = format!("${}", i);
-                let new_param = format!("${}", num_update_params + i);
-                where_str = where_str.replace(&old_param, &new_param);
-            }
-            where_str
-        } else {
-            where_clause.clone()
-        };
+        // WHERE parameters are numbered after the UPDATE parameters
+        let (mut adjusted_where_clause, _, _, params) =
+            query.build_typed(&columns, num_update_params as i32 + 1);
+        let update_values = Self::bind_values(&update_params)?;
+        let where_values = Self::bind_values(&params)?;
 
         // Add soft delete filter for models with soft delete support
         if T::supports_soft_delete() {
@@ -696,11 +728,17 @@ where
             }
         }
 
+        // Bump the version so concurrent readers of versioned records see a conflict
+        let version_bump = T::version_field()
+            .map(|field| format!(", {} = {} + 1", field, field))
+            .unwrap_or_default();
+
         // Build UPDATE statement with RETURNING clause to get updated records
         let sql = format!(
-            "UPDATE {} SET {}, __updated_at__ = NOW() {} RETURNING *",
+            "UPDATE {} SET {}{}, __updated_at__ = NOW() {} RETURNING *",
             T::table_name(),
             set_clause,
+            version_bump,
             adjusted_where_clause
         );
 
@@ -714,8 +752,8 @@ where
 
             // Then bind WHERE clause parameters
             let mut sqlx_query = q;
-            for param in params {
-                sqlx_query = self.bind_param(sqlx_query, param);
+            for value in where_values {
+                sqlx_query = self.bind_param(sqlx_query, value);
             }
 
             // Execute the query with provided executor
@@ -734,8 +772,8 @@ where
             let mut sqlx_query = model.bind_update_params_owned(&sql);
 
             // Then bind WHERE clause parameters
-            for param in params {
-                sqlx_query = self.bind_param(sqlx_query, param);
+            for value in where_values {
+                sqlx_query = self.bind_param(sqlx_query, value);
             }
 
             // Execute the query with provided executor
@@ -756,10 +794,13 @@ where
         query: crate::QueryBuilder,
     ) -> Result<Vec<Self::Id>, StorehausError> {
         // Build the WHERE clause from the query
-        let (where_clause, _, _, params) = query.build();
+        let query = Self::checked_query(&query)?;
+        let (where_clause, _, _, params) = query.build_typed(&Self::column_types(), 1);
+        let values = Self::bind_values(&params)?;
 
         // Check if table has primary key
         let has_primary_key = !T::primary_key_field().is_empty();
+        let mut tx = self.begin_outbox_write().await?;
 
         // For soft delete models, we need to UPDATE rather than DELETE
         if T::supports_soft_delete() {
@@ -792,75 +833,45 @@ where
 
             let deleted_ids = if has_primary_key {
                 let mut sqlx_query = sqlx::query_as::<_, (T::Id,)>(&sql);
-                for param in params {
-                    sqlx_query = self.bind_param_for_id_query(sqlx_query, param);
+                for value in values {
+                    sqlx_query = self.bind_param_for_id_query(sqlx_query, value);
                 }
 
-                let soft_deleted_ids: Vec<(T::Id,)> = sqlx_query
-                    .fetch_all(&self.db_pool)
-                    .await
-                    .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
+                let soft_deleted_ids: Vec<(T::Id,)> = match tx.as_mut() {
+                    Some(tx) => sqlx_query.fetch_all(&mut **tx).await,
+                    None => sqlx_query.fetch_all(&self.db_pool).await,
+                }
+                .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
 
                 soft_deleted_ids.into_iter().map(|(id,)| id).collect()
             } else {
                 // For tables without PK, execute and return empty vec
                 let mut sqlx_query = sqlx::query(&sql);
-                for param in params {
-                    sqlx_query = self.bind_param_raw(sqlx_query, param);
+                for value in values {
+                    sqlx_query = self.bind_param_raw(sqlx_query, value);
                 }
 
-                sqlx_query
-                    .execute(&self.db_pool)
-                    .await
-                    .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
+                match tx.as_mut() {
+                    Some(tx) => sqlx_query.execute(&mut **tx).await,
+                    None => sqlx_query.execute(&self.db_pool).await,
+                }
+                .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
 
                 Vec::new()
             };
 
-            // Emit delete signal if signal manager is present and records were deleted
-            if self.signal_manager.is_some() && !deleted_ids.is_empty() {
-                let mut event = signal_system::DatabaseEvent::new(
-                    signal_system::EventType::Delete,
-                    T::table_name().to_string(),
-                );
-
-                // Add the primary key field name from TableMetadata
-                let primary_key_name = T::primary_key_field();
-
-                // Convert deleted IDs to PostgresValue array
-                let ids_as_postgres: Vec<signal_system::PostgresValue> = deleted_ids
-                    .iter()
-                    .map(|id| {
-                        let id_str = id_to_string(id.clone());
-                        if id_str.starts_with('\"') && id_str.ends_with('\"') {
-                            signal_system::PostgresValue::Text(
-                                id_str.trim_matches('\"').to_string(),
-                            )
-                        } else if let Ok(int_id) = id_str.parse::<i32>() {
-                            signal_system::PostgresValue::Integer(int_id)
-                        } else if let Ok(bigint_id) = id_str.parse::<i64>() {
-                            signal_system::PostgresValue::BigInt(bigint_id)
-                        } else {
-                            signal_system::PostgresValue::Text(id_str)
-                        }
-                    })
-                    .collect();
+            // Build delete signal if signals are enabled and records were deleted
+            let mut events = Vec::new();
+            if self.has_event_sink() && !deleted_ids.is_empty() {
+                events.push(self.deleted_where_event(&deleted_ids));
+            }
+            let events = self.commit_write(tx, events).await?;
 
-                event.add_payload(
-                    primary_key_name.to_string(),
-                    signal_system::PostgresValue::Json(
-                        serde_json::to_value(ids_as_postgres).unwrap_or_default(),
-                    ),
-                );
+            // Rows without a primary key are not reported, so always invalidate queries
+            self.evict_record_cache(&deleted_ids).await;
+            self.invalidate_query_cache().await;
 
-                // Add count of deleted records
-                event.add_payload(
-                    "deleted_count".to_string(),
-                    signal_system::PostgresValue::Integer(deleted_ids.len() as i32),
-                );
-
-                self.emit_signal(event).await;
-            }
+            self.emit_signals(events).await;
 
             Ok(deleted_ids)
         } else {
@@ -883,71 +894,45 @@ where
 
             let deleted_ids = if has_primary_key {
                 let mut sqlx_query = sqlx::query_as::<_, (T::Id,)>(&sql);
-                for param in params {
-                    sqlx_query = self.bind_param_for_id_query(sqlx_query, param);
+                for value in values {
+                    sqlx_query = self.bind_param_for_id_query(sqlx_query, value);
                 }
 
-                let hard_deleted_ids: Vec<(T::Id,)> = sqlx_query
-                    .fetch_all(&self.db_pool)
-                    .await
-                    .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
+                let hard_deleted_ids: Vec<(T::Id,)> = match tx.as_mut() {
+                    Some(tx) => sqlx_query.fetch_all(&mut **tx).await,
+                    None => sqlx_query.fetch_all(&self.db_pool).await,
+                }
+                .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
 
                 hard_deleted_ids.into_iter().map(|(id,)| id).collect()
             } else {
                 // For tables without PK, execute and return empty vec
                 let mut sqlx_query = sqlx::query(&sql);
-                for param in params {
-                    sqlx_query = self.bind_param_raw(sqlx_query, param);
+                for value in values {
+                    sqlx_query = self.bind_param_raw(sqlx_query, value);
                 }
 
-                sqlx_query
-                    .execute(&self.db_pool)
-                    .await
-                    .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
+                match tx.as_mut() {
+                    Some(tx) => sqlx_query.execute(&mut **tx).await,
+                    None => sqlx_query.execute(&self.db_pool).await,
+                }
+                .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
 
                 Vec::new()
             };
 
-            // Emit delete signal if signal manager is present and records were deleted
-            if self.signal_manager.is_some() && !deleted_ids.is_empty() {
-                let mut event = signal_system::DatabaseEvent::new(
-                    signal_system::EventType::Delete,
-                    T::table_name().to_string(),
-                );
-
-                let primary_key_name = T::primary_key_field();
-                let ids_as_postgres: Vec<signal_system::PostgresValue> = deleted_ids
-                    .iter()
-                    .map(|id| {
-                        let id_str = id_to_string(id.clone());
-                        if id_str.starts_with('\"') && id_str.ends_with('\"') {
-                            signal_system::PostgresValue::Text(
-                                id_str.trim_matches('\"').to_string(),
-                            )
-                        } else if let Ok(int_id) = id_str.parse::<i32>() {
-                            signal_system::PostgresValue::Integer(int_id)
-                        } else if let Ok(bigint_id) = id_str.parse::<i64>() {
-                            signal_system::PostgresValue::BigInt(bigint_id)
-                        } else {
-                            signal_system::PostgresValue::Text(id_str)
-                        }
-                    })
-                    .collect();
-
-                event.add_payload(
-                    primary_key_name.to_string(),
-                    signal_system::PostgresValue::Json(
-                        serde_json::to_value(ids_as_postgres).unwrap_or_default(),
-                    ),
-                );
+            // Build delete signal if signals are enabled and records were deleted
+            let mut events = Vec::new();
+            if self.has_event_sink() && !deleted_ids.is_empty() {
+                events.push(self.deleted_where_event(&deleted_ids));
+            }
+            let events = self.commit_write(tx, events).await?;
 
-                event.add_payload(
-                    "deleted_count".to_string(),
-                    signal_system::PostgresValue::Integer(deleted_ids.len() as i32),
-                );
+            // Rows without a primary key are not reported, so always invalidate queries
+            self.evict_record_cache(&deleted_ids).await;
+            self.invalidate_query_cache().await;
 
-                self.emit_signal(event).await;
-            }
+            self.emit_signals(events).await;
 
             Ok(deleted_ids)
         }
@@ -969,6 +954,7 @@ where
     ///   commits successfully, not during this method call. This ensures signals are only sent
     ///   for committed changes.
     /// - **Cache**: Similarly, cache invalidation should happen after transaction commit.
+    /// - `GenericStoreTransaction::delete_where` does both on commit.
     ///
     /// # Example
     ///
@@ -1015,7 +1001,9 @@ where
         E: sqlx::Executor<'e, Database = sqlx::Postgres>,
     {
         // Build the WHERE clause from the query
-        let (where_clause, _, _, params) = query.build();
+        let query = Self::checked_query(&query)?;
+        let (where_clause, _, _, params) = query.build_typed(&Self::column_types(), 1);
+        let values = Self::bind_values(&params)?;
 
         // Check if table has primary key
         let has_primary_key = !T::primary_key_field().is_empty();
@@ -1051,8 +1039,8 @@ where
 
             let deleted_ids = if has_primary_key {
                 let mut sqlx_query = sqlx::query_as::<_, (Self::Id,)>(&sql);
-                for param in params {
-                    sqlx_query = self.bind_param_for_id_query(sqlx_query, param);
+                for value in values {
+                    sqlx_query = self.bind_param_for_id_query(sqlx_query, value);
                 }
 
                 let soft_deleted_ids: Vec<(Self::Id,)> = sqlx_query
@@ -1064,8 +1052,8 @@ where
             } else {
                 // For tables without PK, execute and return empty vec
                 let mut sqlx_query = sqlx::query(&sql);
-                for param in params {
-                    sqlx_query = self.bind_param_raw(sqlx_query, param);
+                for value in values {
+                    sqlx_query = self.bind_param_raw(sqlx_query, value);
                 }
 
                 sqlx_query
@@ -1094,8 +1082,8 @@ where
 
             let deleted_ids = if has_primary_key {
                 let mut sqlx_query = sqlx::query_as::<_, (Self::Id,)>(&sql);
-                for param in params {
-                    sqlx_query = self.bind_param_for_id_query(sqlx_query, param);
+                for value in values {
+                    sqlx_query = self.bind_param_for_id_query(sqlx_query, value);
                 }
 
                 let hard_deleted_ids: Vec<(Self::Id,)> = sqlx_query
@@ -1106,8 +1094,8 @@ where
                 hard_deleted_ids.into_iter().map(|(id,)| id).collect()
             } else {
                 let mut sqlx_query = sqlx::query(&sql);
-                for param in params {
-                    sqlx_query = self.bind_param_raw(sqlx_query, param);
+                for value in values {
+                    sqlx_query = self.bind_param_raw(sqlx_query, value);
                 }
 
                 sqlx_query
@@ -1125,69 +1113,48 @@ where
     }
 
     async fn count_where(&self, query: crate::QueryBuilder) -> Result<i64, StorehausError> {
-        let (where_clause, _, _, params) = query.build(); // No ORDER BY or LIMIT for COUNT
-                                                          // Avoid format! allocation by building string directly
-        let base_sql = T::count_base_sql();
-        let mut full_sql = String::with_capacity(base_sql.len() + where_clause.len());
-        full_sql.push_str(base_sql);
-        if !where_clause.is_empty() {
-            // If base_sql already has WHERE (soft delete), replace WHERE with AND
-            if base_sql.contains(" WHERE ") && where_clause.starts_with("WHERE ") {
-                full_sql.push_str(" AND ");
-                full_sql.push_str(&where_clause[6..]); // Skip "WHERE "
-            } else {
-                full_sql.push_str(&where_clause);
+        let (full_sql, params) = Self::build_count_sql(&query)?;
+
+        // Counts are cached as a single-element query result
+        let cache_hash = self
+            .query_cache_manager()
+            .map(|cache_manager| query_cache_hash(cache_manager, &full_sql, &params));
+        let mut cache_key = None;
+        if let Some(hash) = &cache_hash {
+            let (cached, key) = self.get_cached_query::<i64>("count_where", hash).await;
+            if let Some([total]) = cached.as_deref() {
+                return Ok(*total);
             }
+            cache_key = key; // Not in cache, continue to database
         }
 
-        let mut sqlx_query = sqlx::query(&full_sql);
-        for param in params {
-            sqlx_query = self.bind_param_raw(sqlx_query, param);
-        }
+        let total = self.fetch_count(&full_sql, &params).await?;
 
-        let result = sqlx_query
-            .fetch_one(&self.db_pool)
-            .await
-            .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
+        if let Some(key) = &cache_key {
+            self.set_cached_query("count_where", key, &[total]).await;
+        }
 
-        let total: i64 = result.get("total");
         Ok(total)
     }
 }
 
 // Macro for the shared parameter binding logic
-macro_rules! bind_json_param {
-    ($query:expr, $param:expr) => {
-        match $param {
-            serde_json::Value::String(s) => {
-                // Try to parse as RFC3339 timestamp first
-                if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(&s) {
-                    $query.bind(dt.with_timezone(&chrono::Utc))
-                // Try to parse as UUID
-                } else if let Ok(uuid) = uuid::Uuid::parse_str(&s) {
-                    $query.bind(uuid)
-                } else {
-                    $query.bind(s)
-                }
-            },
-            serde_json::Value::Number(n) => {
-                if let Some(i) = n.as_i64() {
-                    if i >= i32::MIN as i64 && i <= i32::MAX as i64 {
-                        $query.bind(i as i32)
-                    } else {
-                        $query.bind(i)
-                    }
-                } else if let Some(f) = n.as_f64() {
-                    $query.bind(f)
-                } else {
-                    $query.bind(n.to_string())
-                }
-            }
-            serde_json::Value::Bool(b) => $query.bind(b),
-            serde_json::Value::Null => $query.bind(Option::<String>::None),
-            // For Array and Object, bind as serde_json::Value directly
+macro_rules! bind_value {
+    ($query:expr, $value:expr) => {
+        match $value {
+            BindValue::Null => $query.bind(Option::<String>::None),
+            BindValue::Bool(b) => $query.bind(b),
+            BindValue::SmallInt(i) => $query.bind(i),
+            BindValue::Integer(i) => $query.bind(i),
+            BindValue::BigInt(i) => $query.bind(i),
+            BindValue::Double(f) => $query.bind(f),
+            BindValue::Text(s) => $query.bind(s),
+            BindValue::Uuid(u) => $query.bind(u),
+            BindValue::Timestamp(ts) => $query.bind(ts),
+            BindValue::Date(d) => $query.bind(d),
             // sqlx with 'json' feature handles JSONB serialization
-            other => $query.bind(other),
+            BindValue::Json(v) => $query.bind(v),
+            BindValue::TextArray(items) => $query.bind(items),
         }
     };
 }
@@ -1201,27 +1168,223 @@ where
         + serde::Serialize
         + Unpin,
 {
-    fn bind_param<'q>(
+    /// Build the COUNT statement and its parameters for `count_where`
+    pub(crate) fn build_count_sql(
+        query: &crate::QueryBuilder,
+    ) -> Result<(String, Vec<crate::query_builder::QueryParam>), StorehausError> {
+        let query = Self::checked_query(query)?;
+        let (where_clause, _, _, params) = query.build_typed(&Self::column_types(), 1); // No ORDER BY or LIMIT for COUNT
+        // Avoid format! allocation by building string directly
+        let base_sql = T::count_base_sql();
+        let mut full_sql = String::with_capacity(base_sql.len() + where_clause.len());
+        full_sql.push_str(base_sql);
+        if !where_clause.is_empty() {
+            // If base_sql already has WHERE (soft delete), replace WHERE with AND
+            match where_clause.strip_prefix("WHERE ") {
+                Some(conditions) if base_sql.contains(" WHERE ") => {
+                    full_sql.push_str(" AND ");
+                    full_sql.push_str(conditions);
+                }
+                _ => full_sql.push_str(&where_clause),
+            }
+        }
+
+        Ok((full_sql, params))
+    }
+
+    /// Run a statement built by `build_count_sql` against the database, bypassing the cache
+    pub(crate) async fn fetch_count(
+        &self,
+        sql: &str,
+        params: &[crate::query_builder::QueryParam],
+    ) -> Result<i64, StorehausError> {
+        let mut sqlx_query = sqlx::query(sql);
+        for value in Self::bind_values(params)? {
+            sqlx_query = self.bind_param_raw(sqlx_query, value);
+        }
+
+        let result = sqlx_query
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))?;
+
+        Ok(result.get("total"))
+    }
+
+    /// Build the SELECT statement and its parameters for `find`
+    pub(crate) fn build_find_sql(
+        query: &crate::QueryBuilder,
+    ) -> Result<(String, Vec<crate::query_builder::QueryParam>), StorehausError> {
+        let query = Self::checked_query(query)?;
+        let (where_clause, order_clause, limit_clause, params) =
+            query.build_typed(&Self::column_types(), 1);
+        // Avoid format! allocation by building string directly
+        let base_sql = T::select_base_sql();
+        let mut full_sql = String::with_capacity(
+            base_sql.len() + where_clause.len() + order_clause.len() + limit_clause.len(),
+        );
+        full_sql.push_str(base_sql);
+        if !where_clause.is_empty() {
+            full_sql.push(' ');
+            // If base_sql already has WHERE (soft delete), replace WHERE with AND
+            match where_clause.strip_prefix("WHERE ") {
+                Some(conditions) if base_sql.contains(" WHERE ") => {
+                    full_sql.push_str("AND ");
+                    full_sql.push_str(conditions);
+                }
+                _ => full_sql.push_str(&where_clause),
+            }
+        }
+        if !order_clause.is_empty() {
+            full_sql.push(' ');
+            full_sql.push_str(&order_clause);
+        }
+        if !limit_clause.is_empty() {
+            full_sql.push(' ');
+            full_sql.push_str(&limit_clause);
+        }
+        if let Some(lock) = query.get_lock() {
+            full_sql.push(' ');
+            full_sql.push_str(&lock.to_sql());
+        }
+
+        Ok((full_sql, params))
+    }
+
+    pub(crate) fn bind_param<'q>(
         &self,
         query: sqlx::query::QueryAs<'q, sqlx::Postgres, T, sqlx::postgres::PgArguments>,
-        param: serde_json::Value,
+        param: BindValue,
     ) -> sqlx::query::QueryAs<'q, sqlx::Postgres, T, sqlx::postgres::PgArguments> {
-        bind_json_param!(query, param)
+        bind_value!(query, param)
+    }
+
+    pub(crate) fn bind_param_as<'q, R>(
+        &self,
+        query: sqlx::query::QueryAs<'q, sqlx::Postgres, R, sqlx::postgres::PgArguments>,
+        param: BindValue,
+    ) -> sqlx::query::QueryAs<'q, sqlx::Postgres, R, sqlx::postgres::PgArguments> {
+        bind_value!(query, param)
     }
 
     fn bind_param_for_id_query<'q>(
         &self,
         query: sqlx::query::QueryAs<'q, sqlx::Postgres, (T::Id,), sqlx::postgres::PgArguments>,
-        param: serde_json::Value,
+        param: BindValue,
     ) -> sqlx::query::QueryAs<'q, sqlx::Postgres, (T::Id,), sqlx::postgres::PgArguments> {
-        bind_json_param!(query, param)
+        bind_value!(query, param)
     }
 
-    fn bind_param_raw<'q>(
+    pub(crate) fn bind_param_raw<'q>(
         &self,
         query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
-        param: serde_json::Value,
+        param: BindValue,
     ) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
-        bind_json_param!(query, param)
+        bind_value!(query, param)
+    }
+}
+
+impl<T> GenericStore<T>
+where
+    T: TableMetadata
+        + DatabaseExecutor
+        + for<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow>
+        + serde::Serialize
+        + Unpin,
+{
+    /// Fetch a record from the database, bypassing the cache
+    async fn fetch_by_id(&self, id: &T::Id) -> Result<Option<T>, StorehausError> {
+        // Use static SQL to avoid allocations
+        sqlx::query_as::<_, T>(T::get_by_id_sql())
+            .bind(id)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(|e| StorehausError::query_execution(T::table_name(), T::get_by_id_sql(), e))
+    }
+
+    /// Reload a record after a cache miss and store it in the cache
+    ///
+    /// With a stampede lock configured, only the lock holder queries the database;
+    /// others wait up to `max_wait` for it to fill the cache and then load it themselves.
+    async fn load_record_json(
+        &self,
+        cache_params: &CacheParams,
+        id: &T::Id,
+        id_buffer: &str,
+    ) -> Result<Option<String>, StorehausError> {
+        let cache_manager = &cache_params.manager;
+        let cache_prefix = self.get_cache_prefix();
+        let cache_error = |operation, e| {
+            StorehausError::cache_operation(operation, Some(id_buffer), Box::new(e))
+        };
+
+        let lock = match cache_params.stampede.lock {
+            Some(settings) => {
+                let lock = cache_manager
+                    .try_lock_record(cache_prefix, T::table_name(), id_buffer, settings.ttl)
+                    .await
+                    .map_err(|e| cache_error("get_by_id_lock", e))?;
+                if lock.is_none() {
+                    let filled = cache_manager
+                        .wait_for_record_json(
+                            cache_prefix,
+                            T::table_name(),
+                            id_buffer,
+                            settings.max_wait,
+                        )
+                        .await
+                        .map_err(|e| cache_error("get_by_id_lock", e))?;
+                    if filled.is_some() {
+                        return Ok(filled);
+                    }
+                }
+                lock
+            }
+            None => None,
+        };
+
+        let result = self.fetch_and_cache_json(cache_params, id, id_buffer).await;
+
+        if let Some(lock) = &lock {
+            // An expired lock is released by its TTL
+            let _ = cache_manager.unlock_record(lock).await;
+        }
+        result
+    }
+
+    /// Load a record from the database and store its serialized form in the cache
+    ///
+    /// Missing records are cached as `null` for `negative_ttl` when negative caching is enabled.
+    async fn fetch_and_cache_json(
+        &self,
+        cache_params: &CacheParams,
+        id: &T::Id,
+        id_buffer: &str,
+    ) -> Result<Option<String>, StorehausError> {
+        let record = self.fetch_by_id(id).await?;
+        let ttl = match (&record, cache_params.negative_ttl) {
+            (Some(_), _) => self.get_cache_ttl(),
+            (None, Some(negative_ttl)) => negative_ttl,
+            (None, None) => return Ok(None),
+        };
+        let cache_error = |e| {
+            StorehausError::cache_operation("get_by_id_cache_set", Some(id_buffer), Box::new(e))
+        };
+
+        let json_str = serde_json::to_string(&record)
+            .map_err(|e| cache_error(cache_system::CacheError::from(e)))?;
+        cache_params
+            .manager
+            .set_record_json(
+                self.get_cache_prefix(),
+                T::table_name(),
+                id_buffer,
+                &json_str,
+                ttl,
+            )
+            .await
+            .map_err(cache_error)?;
+
+        Ok(Some(json_str))
     }
 }
@@ -0,0 +1,197 @@
+//! Streaming reads for GenericStore
+//!
+//! This module returns query results as a `Stream` instead of a `Vec`, so
+//! exports and backfills can walk millions of rows in constant memory. Rows
+//! are read either from a single query (sqlx `fetch`) or in batches through a
+//! server-side cursor.
+
+use super::binding::BindValue;
+use super::core::GenericStore;
+use crate::errors::StorehausError;
+use crate::query_builder::QueryBuilder;
+use crate::table_metadata::TableMetadata;
+use crate::traits::table_metadata::DatabaseExecutor;
+use futures::channel::mpsc;
+use futures::{stream, SinkExt, Stream, StreamExt, TryStreamExt};
+use sqlx::postgres::PgRow;
+
+/// Rows fetched per round trip by cursor streams unless configured otherwise
+pub const DEFAULT_STREAM_BATCH_SIZE: u32 = 1000;
+
+/// Records buffered between the database reader and the consumer
+const STREAM_BUFFER: usize = 256;
+
+/// Name of the cursor declared by cursor streams, unique within their transaction
+const STREAM_CURSOR: &str = "storehaus_stream";
+
+/// How `find_stream_with` reads rows from PostgreSQL
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StreamMode {
+    /// One query whose rows are decoded as they arrive (sqlx `fetch`)
+    #[default]
+    Fetch,
+    /// A server-side cursor in a read-only transaction, read `batch_size` rows per `FETCH`
+    ///
+    /// Keeps memory bounded on the server side too and lets very large tables
+    /// be read without one long-running result transfer.
+    Cursor { batch_size: u32 },
+}
+
+impl StreamMode {
+    /// Server-side cursor with the default batch size
+    pub fn cursor() -> Self {
+        Self::Cursor {
+            batch_size: DEFAULT_STREAM_BATCH_SIZE,
+        }
+    }
+}
+
+impl<T> GenericStore<T>
+where
+    T: TableMetadata
+        + DatabaseExecutor
+        + for<'r> sqlx::FromRow<'r, PgRow>
+        + serde::Serialize
+        + Send
+        + Unpin
+        + 'static,
+{
+    /// Stream every record, like `list_all`, without loading them into memory
+    pub fn list_all_stream(&self) -> impl Stream<Item = Result<T, StorehausError>> + Send + '_ {
+        sqlx::query_as::<_, T>(T::list_all_sql())
+            .fetch(&self.db_pool)
+            .map_err(|e| StorehausError::database_operation(T::table_name(), "list_all", e))
+    }
+
+    /// Stream the records matching a query, like `find`, without loading them into memory
+    ///
+    /// Rows are read with sqlx `fetch` on one pooled connection, which stays
+    /// checked out until the stream ends or is dropped. Streams bypass the
+    /// query cache.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut users = user_store.find_stream(QueryBuilder::new().order_by("id", SortOrder::Asc));
+    /// while let Some(user) = users.try_next().await? {
+    ///     export(&user)?;
+    /// }
+    /// ```
+    pub fn find_stream(
+        &self,
+        query: QueryBuilder,
+    ) -> impl Stream<Item = Result<T, StorehausError>> + Send + 'static {
+        self.find_stream_with(query, StreamMode::Fetch)
+    }
+
+    /// Stream the records matching a query using the given `StreamMode`
+    ///
+    /// Records are read by a background task and buffered in a bounded
+    /// channel, so a slow consumer holds back the reader. Dropping the stream
+    /// stops the reader and releases its connection. The task is spawned on
+    /// the first poll, so the stream can be built anywhere but must be polled
+    /// on a Tokio runtime.
+    pub fn find_stream_with(
+        &self,
+        query: QueryBuilder,
+        mode: StreamMode,
+    ) -> impl Stream<Item = Result<T, StorehausError>> + Send + 'static {
+        let statement = Self::build_find_sql(&query)
+            .and_then(|(sql, params)| Ok((sql, Self::bind_values(&params)?)));
+        let store = self.clone();
+
+        stream::once(async move {
+            let (mut sender, receiver) = mpsc::channel(STREAM_BUFFER);
+            tokio::spawn(async move {
+                let result = match (statement, mode) {
+                    (Err(e), _) => Err(e),
+                    (Ok((sql, values)), StreamMode::Fetch) => {
+                        store.fetch_into(&sql, values, &mut sender).await
+                    }
+                    (Ok((sql, values)), StreamMode::Cursor { batch_size }) => {
+                        store
+                            .cursor_into(&sql, values, batch_size.max(1), &mut sender)
+                            .await
+                    }
+                };
+                if let Err(e) = result {
+                    let _ = sender.send(Err(e)).await;
+                }
+            });
+            receiver
+        })
+        .flatten()
+    }
+
+    /// Send the rows of one query as they arrive; stops early once the consumer is gone
+    async fn fetch_into(
+        &self,
+        sql: &str,
+        values: Vec<BindValue>,
+        sender: &mut mpsc::Sender<Result<T, StorehausError>>,
+    ) -> Result<(), StorehausError> {
+        let mut sqlx_query = sqlx::query_as::<_, T>(sql);
+        for value in values {
+            sqlx_query = self.bind_param(sqlx_query, value);
+        }
+
+        let mut rows = sqlx_query.fetch(&self.db_pool);
+        while let Some(record) = rows
+            .try_next()
+            .await
+            .map_err(|e| StorehausError::database_operation(T::table_name(), "find_stream", e))?
+        {
+            if sender.send(Ok(record)).await.is_err() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Send the rows of a server-side cursor batch by batch
+    async fn cursor_into(
+        &self,
+        sql: &str,
+        values: Vec<BindValue>,
+        batch_size: u32,
+        sender: &mut mpsc::Sender<Result<T, StorehausError>>,
+    ) -> Result<(), StorehausError> {
+        let mut tx = self
+            .db_pool
+            .begin_with("BEGIN READ ONLY")
+            .await
+            .map_err(|e| StorehausError::transaction("begin", e))?;
+
+        let declare_sql = format!("DECLARE {} NO SCROLL CURSOR FOR {}", STREAM_CURSOR, sql);
+        let mut declare = sqlx::query(&declare_sql);
+        for value in values {
+            declare = self.bind_param_raw(declare, value);
+        }
+        declare
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| StorehausError::query_execution(T::table_name(), &declare_sql, e))?;
+
+        let fetch_sql = format!("FETCH {} FROM {}", batch_size, STREAM_CURSOR);
+        loop {
+            let batch = sqlx::query_as::<_, T>(&fetch_sql)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(|e| StorehausError::database_operation(T::table_name(), "find_stream", e))?;
+            let exhausted = batch.len() < batch_size as usize;
+
+            for record in batch {
+                if sender.send(Ok(record)).await.is_err() {
+                    // Dropping the transaction rolls back and closes the cursor
+                    return Ok(());
+                }
+            }
+            if exhausted {
+                break;
+            }
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| StorehausError::transaction("commit", e))
+    }
+}
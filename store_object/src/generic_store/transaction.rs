@@ -3,41 +3,542 @@
 //! This module provides database transaction functionality for GenericStore,
 //! allowing multiple operations to be executed atomically.
 
+use super::upsert::{OnConflict, UpsertOutcome};
 use super::GenericStore;
-use crate::table_metadata::TableMetadata;
 use crate::errors::StorehausError;
+use crate::outbox::EventOutbox;
+use crate::table_metadata::TableMetadata;
+use crate::traits::table_metadata::DatabaseExecutor;
+use crate::traits::StoreObject;
+use crate::DbPool;
+use signal_system::DatabaseEvent;
 use sqlx::{Postgres, Transaction};
-use std::marker::PhantomData;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Effect applied once the transaction has committed
+type AfterCommit<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+
+/// Cache change of a single write
+enum CacheOp<T: TableMetadata> {
+    Refresh(Vec<T>),
+    Evict(Vec<T::Id>),
+}
+
+/// Transaction isolation level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsolationLevel {
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    /// SQL keywords of the level
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            Self::ReadCommitted => "READ COMMITTED",
+            Self::RepeatableRead => "REPEATABLE READ",
+            Self::Serializable => "SERIALIZABLE",
+        }
+    }
+}
+
+/// Settings applied when a transaction begins
+///
+/// Without an isolation level the server default (usually READ COMMITTED)
+/// is used. `deferrable` only has an effect on SERIALIZABLE READ ONLY
+/// transactions, which then wait for a safe snapshot instead of risking a
+/// serialization failure.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransactionOptions {
+    pub isolation_level: Option<IsolationLevel>,
+    pub read_only: bool,
+    pub deferrable: bool,
+}
+
+impl TransactionOptions {
+    /// Server defaults: no explicit isolation level, read-write
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the isolation level
+    pub fn with_isolation_level(mut self, isolation_level: IsolationLevel) -> Self {
+        self.isolation_level = Some(isolation_level);
+        self
+    }
+
+    /// Shorthand for the SERIALIZABLE isolation level
+    pub fn serializable() -> Self {
+        Self::new().with_isolation_level(IsolationLevel::Serializable)
+    }
+
+    /// Reject writes in the transaction
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Make a SERIALIZABLE READ ONLY transaction wait for a safe snapshot
+    pub fn deferrable(mut self) -> Self {
+        self.deferrable = true;
+        self
+    }
+
+    /// Statement beginning a transaction with these settings
+    pub fn begin_sql(&self) -> String {
+        let mut sql = String::from("BEGIN");
+        if let Some(isolation_level) = self.isolation_level {
+            sql.push_str(" ISOLATION LEVEL ");
+            sql.push_str(isolation_level.as_sql());
+        }
+        if self.read_only {
+            sql.push_str(" READ ONLY");
+        }
+        if self.deferrable {
+            sql.push_str(" DEFERRABLE");
+        }
+        sql
+    }
+
+    /// Begin a transaction with these settings
+    pub async fn begin(
+        &self,
+        pool: &DbPool,
+    ) -> Result<Transaction<'static, Postgres>, StorehausError> {
+        let result = if *self == Self::default() {
+            pool.begin().await
+        } else {
+            pool.begin_with(self.begin_sql()).await
+        };
+        result.map_err(|e| StorehausError::transaction("begin", e))
+    }
+}
+
+/// Savepoint within a transaction
+///
+/// Rolling back to it undoes the changes made since it was created, together
+/// with their buffered signals and cache updates.
+#[derive(Debug)]
+pub struct Savepoint {
+    name: String,
+    outbox_len: usize,
+    after_commit_len: usize,
+}
+
+impl Savepoint {
+    /// Name of the savepoint in the transaction
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Side effects of writes made inside a transaction
+///
+/// Events of stores with an outbox are written to it right before the
+/// transaction commits. Cache updates and directly emitted signals run after
+/// the commit, in the order of the writes. Dropping the effects discards them.
+#[derive(Default)]
+pub struct PendingEffects<'a> {
+    outbox_events: Vec<(Arc<EventOutbox>, Vec<DatabaseEvent>)>,
+    after_commit: Vec<AfterCommit<'a>>,
+    savepoints: usize,
+}
+
+impl std::fmt::Debug for PendingEffects<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PendingEffects")
+            .field("outbox_batches", &self.outbox_events.len())
+            .field("after_commit", &self.after_commit.len())
+            .finish()
+    }
+}
+
+impl PendingEffects<'_> {
+    /// Create an empty set of effects
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if no write has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.outbox_events.is_empty() && self.after_commit.is_empty()
+    }
+
+    /// Create a savepoint in the transaction these effects belong to
+    pub async fn savepoint(
+        &mut self,
+        tx: &mut Transaction<'static, Postgres>,
+    ) -> Result<Savepoint, StorehausError> {
+        self.savepoints += 1;
+        let savepoint = Savepoint {
+            name: format!("storehaus_sp_{}", self.savepoints),
+            outbox_len: self.outbox_events.len(),
+            after_commit_len: self.after_commit.len(),
+        };
+        sqlx::query(&format!("SAVEPOINT {}", savepoint.name))
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| StorehausError::transaction("savepoint", e))?;
+        Ok(savepoint)
+    }
+
+    /// Undo the changes and effects recorded since the savepoint
+    ///
+    /// The savepoint stays valid, so the transaction can roll back to it again.
+    pub async fn rollback_to(
+        &mut self,
+        tx: &mut Transaction<'static, Postgres>,
+        savepoint: &Savepoint,
+    ) -> Result<(), StorehausError> {
+        sqlx::query(&format!("ROLLBACK TO SAVEPOINT {}", savepoint.name))
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| StorehausError::transaction("rollback_to_savepoint", e))?;
+        self.outbox_events.truncate(savepoint.outbox_len);
+        self.after_commit.truncate(savepoint.after_commit_len);
+        Ok(())
+    }
+
+    /// Keep the changes made since the savepoint and forget the savepoint
+    pub async fn release(
+        &mut self,
+        tx: &mut Transaction<'static, Postgres>,
+        savepoint: Savepoint,
+    ) -> Result<(), StorehausError> {
+        sqlx::query(&format!("RELEASE SAVEPOINT {}", savepoint.name))
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| StorehausError::transaction("release_savepoint", e))?;
+        Ok(())
+    }
+
+    /// Commit the transaction, then apply the effects
+    ///
+    /// Nothing is applied if the commit fails.
+    pub async fn commit(self, mut tx: Transaction<'_, Postgres>) -> Result<(), StorehausError> {
+        for (outbox, events) in &self.outbox_events {
+            outbox.enqueue(&mut tx, events).await?;
+        }
+        tx.commit()
+            .await
+            .map_err(|e| StorehausError::transaction("commit", e))?;
+
+        for (outbox, _) in &self.outbox_events {
+            outbox.notify();
+        }
+        for effect in self.after_commit {
+            effect.await;
+        }
+        Ok(())
+    }
+}
+
+/// A store bound to a running transaction
+///
+/// Operations run inside the transaction; their signals and cache updates are
+/// recorded in the transaction's `PendingEffects`. Obtained from
+/// `GenericStoreTransaction::store()` or a `StoreHaus` unit of work.
+pub struct TransactionalStore<'t, 'a, T: TableMetadata> {
+    store: &'a GenericStore<T>,
+    tx: &'t mut Transaction<'static, Postgres>,
+    effects: &'t mut PendingEffects<'a>,
+}
+
+impl<T: TableMetadata> std::fmt::Debug for TransactionalStore<'_, '_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransactionalStore")
+            .field("table", &T::table_name())
+            .field("effects", &self.effects)
+            .finish()
+    }
+}
+
+impl<'t, 'a, T: TableMetadata> TransactionalStore<'t, 'a, T> {
+    /// Bind a store to a transaction and the effects buffer committed with it
+    pub fn new(
+        store: &'a GenericStore<T>,
+        tx: &'t mut Transaction<'static, Postgres>,
+        effects: &'t mut PendingEffects<'a>,
+    ) -> Self {
+        Self { store, tx, effects }
+    }
+
+    /// Record the effects of a write that changed the table
+    fn record_write(&mut self, event: Option<DatabaseEvent>, cache_op: Option<CacheOp<T>>) {
+        let store = self.store;
+        let mut events: Vec<DatabaseEvent> = event.into_iter().collect();
+        if let Some(outbox) = &store.outbox {
+            if !events.is_empty() {
+                self.effects
+                    .outbox_events
+                    .push((outbox.clone(), std::mem::take(&mut events)));
+            }
+        }
+
+        self.effects.after_commit.push(Box::pin(async move {
+            match cache_op {
+                Some(CacheOp::Refresh(records)) => store.refresh_record_cache(&records).await,
+                Some(CacheOp::Evict(ids)) => store.evict_record_cache(&ids).await,
+                None => {}
+            }
+            store.invalidate_query_cache().await;
+            store.emit_signals(events).await;
+        }));
+    }
+}
+
+impl<T> TransactionalStore<'_, '_, T>
+where
+    T: TableMetadata
+        + DatabaseExecutor
+        + for<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow>
+        + serde::Serialize
+        + Unpin,
+{
+    /// Create a record within the transaction
+    pub async fn create(
+        &mut self,
+        data: T,
+        tags: Option<Vec<String>>,
+    ) -> Result<T, StorehausError> {
+        let created = data.execute_create_tx(self.tx).await?;
+
+        // Forget a cached miss for this id once the row is visible
+        let evict_miss = self
+            .store
+            .cache_params
+            .as_ref()
+            .is_some_and(|cp| cp.negative_ttl.is_some())
+            .then(|| CacheOp::Evict(vec![created.extract_id()]));
+        let event = self
+            .store
+            .has_event_sink()
+            .then(|| self.store.created_event(&created, tags));
+        self.record_write(event, evict_miss);
+
+        Ok(created)
+    }
+
+    /// Get a record by id within the transaction, bypassing the cache
+    pub async fn get_by_id(&mut self, id: &T::Id) -> Result<Option<T>, StorehausError> {
+        sqlx::query_as::<_, T>(T::get_by_id_sql())
+            .bind(id)
+            .fetch_optional(&mut **self.tx)
+            .await
+            .map_err(|e| StorehausError::query_execution(T::table_name(), T::get_by_id_sql(), e))
+    }
+
+    /// Update a record within the transaction
+    pub async fn update(
+        &mut self,
+        id: &T::Id,
+        data: T,
+        tags: Option<Vec<String>>,
+    ) -> Result<T, StorehausError> {
+        let updated = data.execute_update_tx(self.tx).await?;
+
+        let event = self
+            .store
+            .has_event_sink()
+            .then(|| self.store.updated_event(id, &updated, tags));
+        self.record_write(event, Some(CacheOp::Refresh(vec![updated.clone()])));
+
+        Ok(updated)
+    }
+
+    /// Insert a record or update the row it conflicts with, within the transaction
+    pub async fn upsert(
+        &mut self,
+        data: T,
+        conflict_target: OnConflict,
+        tags: Option<Vec<String>>,
+    ) -> Result<UpsertOutcome<T>, StorehausError> {
+        let sql = conflict_target.to_sql::<T>()?;
+        let outcome =
+            GenericStore::<T>::execute_upsert(&mut **self.tx, &data, &sql, conflict_target.action)
+                .await?;
+
+        if let Some(record) = outcome.record() {
+            let event = self
+                .store
+                .has_event_sink()
+                .then(|| self.store.upserted_event(&outcome, tags))
+                .flatten();
+            self.record_write(event, Some(CacheOp::Refresh(vec![record.clone()])));
+        }
+
+        Ok(outcome)
+    }
+
+    /// Delete a record within the transaction
+    ///
+    /// Like `GenericStore::delete`, models with soft delete support are
+    /// deactivated instead, which emits no signal.
+    pub async fn delete(&mut self, id: &T::Id) -> Result<bool, StorehausError> {
+        let (sql, event) = match T::soft_delete_field() {
+            Some(soft_delete_field) if T::supports_soft_delete() => (
+                format!(
+                    "UPDATE {} SET {} = false, __updated_at__ = NOW() WHERE {} = $1",
+                    T::table_name(),
+                    soft_delete_field,
+                    T::primary_key_field()
+                ),
+                None,
+            ),
+            _ => (
+                T::delete_by_id_sql().to_string(),
+                self.store
+                    .has_event_sink()
+                    .then(|| self.store.deleted_event(id)),
+            ),
+        };
+
+        let result = sqlx::query(&sql)
+            .bind(id)
+            .execute(&mut **self.tx)
+            .await
+            .map_err(|e| StorehausError::database_operation(T::table_name(), "delete", e))?;
+
+        let deleted = result.rows_affected() > 0;
+        if deleted {
+            self.record_write(event, Some(CacheOp::Evict(vec![id.clone()])));
+        }
+
+        Ok(deleted)
+    }
+
+    /// Find records within the transaction, bypassing the query cache
+    pub async fn find(&mut self, query: crate::QueryBuilder) -> Result<Vec<T>, StorehausError> {
+        let (full_sql, params) = GenericStore::<T>::build_find_sql(&query)?;
+        let values = GenericStore::<T>::bind_values(&params)?;
+
+        let mut sqlx_query = sqlx::query_as::<_, T>(&full_sql);
+        for value in values {
+            sqlx_query = self.store.bind_param(sqlx_query, value);
+        }
+
+        sqlx_query
+            .fetch_all(&mut **self.tx)
+            .await
+            .map_err(|e| StorehausError::database_operation(T::table_name(), "query", e))
+    }
+
+    /// Find the first matching record within the transaction
+    pub async fn find_one(
+        &mut self,
+        query: crate::QueryBuilder,
+    ) -> Result<Option<T>, StorehausError> {
+        let mut results = self.find(query.limit(1)).await?;
+        Ok(results.pop())
+    }
+
+    /// Update records matching the query within the transaction
+    pub async fn update_where(
+        &mut self,
+        query: crate::QueryBuilder,
+        data: Option<T>,
+    ) -> Result<Vec<T>, StorehausError> {
+        let updated = self
+            .store
+            .update_where_with_executor(&mut **self.tx, query, data)
+            .await?;
+
+        let event = (self.store.has_event_sink() && !updated.is_empty())
+            .then(|| self.store.updated_where_event(&updated));
+        self.record_write(event, Some(CacheOp::Refresh(updated.clone())));
+
+        Ok(updated)
+    }
+
+    /// Delete records matching the query within the transaction
+    pub async fn delete_where(
+        &mut self,
+        query: crate::QueryBuilder,
+    ) -> Result<Vec<T::Id>, StorehausError> {
+        let deleted_ids = self
+            .store
+            .delete_where_with_executor(&mut **self.tx, query)
+            .await?;
+
+        // Rows without a primary key are not reported, so queries are invalidated regardless
+        let event = (self.store.has_event_sink() && !deleted_ids.is_empty())
+            .then(|| self.store.deleted_where_event(&deleted_ids));
+        self.record_write(event, Some(CacheOp::Evict(deleted_ids.clone())));
+
+        Ok(deleted_ids)
+    }
+}
 
 /// A transactional context for GenericStore operations
 ///
-/// This struct wraps a sqlx transaction and provides commit/rollback functionality.
-/// The underlying transaction can be accessed via `as_mut()` for executing queries.
+/// Store operations called on the transaction run inside it. Their signals and
+/// cache updates are buffered and applied only by `commit`; `rollback` (or
+/// dropping the transaction) discards them together with the changes. With an
+/// outbox configured, the buffered events are written to it in this transaction.
+///
+/// The underlying transaction can be accessed via `as_mut()` for executing
+/// other queries.
 ///
 /// # Example
 /// ```ignore
 /// let mut tx = store.begin_transaction().await?;
 ///
-/// // Use repository methods that accept &mut Transaction
-/// wallet_repo.update_balance_tx(tx.as_mut(), wallet_id_1, -100).await?;
-/// wallet_repo.update_balance_tx(tx.as_mut(), wallet_id_2, 100).await?;
+/// let order = tx.create(Order::new(customer_id, total), None).await?;
+/// tx.update_where(
+///     QueryBuilder::new()
+///         .filter(QueryFilter::eq("id", json!(customer_id)))
+///         .update(UpdateSet::new().increment("orders", json!(1))),
+///     None,
+/// )
+/// .await?;
 ///
-/// // Commit the transaction
+/// // Signals are emitted and caches updated only now
 /// tx.commit().await?;
 /// ```
 pub struct GenericStoreTransaction<'a, T: TableMetadata> {
-    tx: Transaction<'a, Postgres>,
-    _phantom: PhantomData<T>,
+    tx: Transaction<'static, Postgres>,
+    store: &'a GenericStore<T>,
+    effects: PendingEffects<'a>,
+}
+
+impl<T: TableMetadata> std::fmt::Debug for GenericStoreTransaction<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GenericStoreTransaction")
+            .field("table", &T::table_name())
+            .field("effects", &self.effects)
+            .finish()
+    }
 }
 
 impl<T: TableMetadata> GenericStore<T> {
     /// Begin a new database transaction
-    pub async fn begin_transaction(&self) -> Result<GenericStoreTransaction<'_, T>, StorehausError> {
-        let tx = self.db_pool.begin().await
-            .map_err(|e| StorehausError::DatabaseError(format!("Failed to begin transaction: {}", e)))?;
+    pub async fn begin_transaction(
+        &self,
+    ) -> Result<GenericStoreTransaction<'_, T>, StorehausError> {
+        self.begin_transaction_with(TransactionOptions::default())
+            .await
+    }
+
+    /// Begin a new database transaction with an isolation level or access mode
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut tx = store
+    ///     .begin_transaction_with(TransactionOptions::serializable())
+    ///     .await?;
+    /// ```
+    pub async fn begin_transaction_with(
+        &self,
+        options: TransactionOptions,
+    ) -> Result<GenericStoreTransaction<'_, T>, StorehausError> {
+        let tx = options.begin(&self.db_pool).await?;
         Ok(GenericStoreTransaction {
             tx,
-            _phantom: PhantomData,
+            store: self,
+            effects: PendingEffects::new(),
         })
     }
 
@@ -49,23 +550,161 @@ impl<T: TableMetadata> GenericStore<T> {
 }
 
 impl<'a, T: TableMetadata> GenericStoreTransaction<'a, T> {
-    /// Commit the transaction
+    /// Commit the transaction, then apply buffered cache updates and emit buffered signals
     pub async fn commit(self) -> Result<(), StorehausError> {
-        self.tx.commit().await
-            .map_err(|e| StorehausError::DatabaseError(format!("Failed to commit transaction: {}", e)))?;
-        Ok(())
+        self.effects.commit(self.tx).await
     }
 
-    /// Rollback the transaction
+    /// Rollback the transaction, discarding buffered signals and cache updates
     pub async fn rollback(self) -> Result<(), StorehausError> {
-        self.tx.rollback().await
-            .map_err(|e| StorehausError::DatabaseError(format!("Failed to rollback transaction: {}", e)))?;
+        self.tx
+            .rollback()
+            .await
+            .map_err(|e| StorehausError::transaction("rollback", e))?;
         Ok(())
     }
 
     /// Get a mutable reference to the underlying transaction
     /// Use this to execute queries within the transaction
-    pub fn as_mut(&mut self) -> &mut Transaction<'a, Postgres> {
+    #[allow(clippy::should_implement_trait)]
+    pub fn as_mut(&mut self) -> &mut Transaction<'static, Postgres> {
         &mut self.tx
     }
-}
\ No newline at end of file
+
+    /// Create a savepoint
+    ///
+    /// # Example
+    /// ```ignore
+    /// for row in rows {
+    ///     let savepoint = tx.savepoint().await?;
+    ///     if tx.create(row, None).await.is_err() {
+    ///         // Skip the row, keep the rest of the import
+    ///         tx.rollback_to(&savepoint).await?;
+    ///     }
+    /// }
+    /// tx.commit().await?;
+    /// ```
+    pub async fn savepoint(&mut self) -> Result<Savepoint, StorehausError> {
+        self.effects.savepoint(&mut self.tx).await
+    }
+
+    /// Undo the changes made since the savepoint, with their signals and cache updates
+    pub async fn rollback_to(&mut self, savepoint: &Savepoint) -> Result<(), StorehausError> {
+        self.effects.rollback_to(&mut self.tx, savepoint).await
+    }
+
+    /// Keep the changes made since the savepoint and forget the savepoint
+    pub async fn release(&mut self, savepoint: Savepoint) -> Result<(), StorehausError> {
+        self.effects.release(&mut self.tx, savepoint).await
+    }
+
+    /// The store bound to this transaction
+    pub fn store(&mut self) -> TransactionalStore<'_, 'a, T> {
+        TransactionalStore::new(self.store, &mut self.tx, &mut self.effects)
+    }
+}
+
+impl<T> GenericStoreTransaction<'_, T>
+where
+    T: TableMetadata
+        + DatabaseExecutor
+        + for<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow>
+        + serde::Serialize
+        + Unpin,
+{
+    /// Create a record within the transaction
+    pub async fn create(
+        &mut self,
+        data: T,
+        tags: Option<Vec<String>>,
+    ) -> Result<T, StorehausError> {
+        self.store().create(data, tags).await
+    }
+
+    /// Get a record by id within the transaction, bypassing the cache
+    pub async fn get_by_id(&mut self, id: &T::Id) -> Result<Option<T>, StorehausError> {
+        self.store().get_by_id(id).await
+    }
+
+    /// Update a record within the transaction
+    pub async fn update(
+        &mut self,
+        id: &T::Id,
+        data: T,
+        tags: Option<Vec<String>>,
+    ) -> Result<T, StorehausError> {
+        self.store().update(id, data, tags).await
+    }
+
+    /// Insert a record or update the row it conflicts with, within the transaction
+    pub async fn upsert(
+        &mut self,
+        data: T,
+        conflict_target: OnConflict,
+        tags: Option<Vec<String>>,
+    ) -> Result<UpsertOutcome<T>, StorehausError> {
+        self.store().upsert(data, conflict_target, tags).await
+    }
+
+    /// Delete a record within the transaction
+    pub async fn delete(&mut self, id: &T::Id) -> Result<bool, StorehausError> {
+        self.store().delete(id).await
+    }
+
+    /// Find records within the transaction, bypassing the query cache
+    pub async fn find(&mut self, query: crate::QueryBuilder) -> Result<Vec<T>, StorehausError> {
+        self.store().find(query).await
+    }
+
+    /// Find the first matching record within the transaction
+    pub async fn find_one(
+        &mut self,
+        query: crate::QueryBuilder,
+    ) -> Result<Option<T>, StorehausError> {
+        self.store().find_one(query).await
+    }
+
+    /// Update records matching the query within the transaction
+    pub async fn update_where(
+        &mut self,
+        query: crate::QueryBuilder,
+        data: Option<T>,
+    ) -> Result<Vec<T>, StorehausError> {
+        self.store().update_where(query, data).await
+    }
+
+    /// Delete records matching the query within the transaction
+    pub async fn delete_where(
+        &mut self,
+        query: crate::QueryBuilder,
+    ) -> Result<Vec<T::Id>, StorehausError> {
+        self.store().delete_where(query).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_begin_sql() {
+        assert_eq!(TransactionOptions::new().begin_sql(), "BEGIN");
+        assert_eq!(
+            TransactionOptions::serializable().begin_sql(),
+            "BEGIN ISOLATION LEVEL SERIALIZABLE"
+        );
+        assert_eq!(
+            TransactionOptions::serializable()
+                .read_only()
+                .deferrable()
+                .begin_sql(),
+            "BEGIN ISOLATION LEVEL SERIALIZABLE READ ONLY DEFERRABLE"
+        );
+        assert_eq!(
+            TransactionOptions::new()
+                .with_isolation_level(IsolationLevel::RepeatableRead)
+                .begin_sql(),
+            "BEGIN ISOLATION LEVEL REPEATABLE READ"
+        );
+    }
+}
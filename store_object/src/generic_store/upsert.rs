@@ -0,0 +1,280 @@
+//! Upserts for GenericStore
+//!
+//! This module builds `INSERT ... ON CONFLICT` statements for a conflict target
+//! (the primary key or a `#[unique]` field/composite) and executes them,
+//! reporting whether each row was created or updated.
+
+use super::binding::quoted;
+use super::core::GenericStore;
+use crate::errors::StorehausError;
+use crate::table_metadata::TableMetadata;
+use crate::traits::table_metadata::DatabaseExecutor;
+use sqlx::postgres::PgRow;
+use sqlx::Row;
+
+/// Columns whose unique constraint decides whether an upsert updates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictTarget {
+    /// The model's primary key
+    PrimaryKey,
+    /// A `#[unique]` field or `#[unique(...)]` composite
+    Columns(Vec<String>),
+}
+
+/// What an upsert does with an existing row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictAction {
+    /// Overwrite the row's UPDATE fields with the new values
+    DoUpdate,
+    /// Keep the row unchanged
+    DoNothing,
+}
+
+/// Conflict handling of an upsert
+///
+/// # Example
+/// ```ignore
+/// // Insert or update by primary key
+/// store.upsert(user, OnConflict::primary_key(), None).await?;
+///
+/// // Insert unless a user with the same email exists
+/// store.upsert(user, OnConflict::columns(["email"]).do_nothing(), None).await?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OnConflict {
+    pub target: ConflictTarget,
+    pub action: ConflictAction,
+}
+
+impl Default for OnConflict {
+    fn default() -> Self {
+        Self::primary_key()
+    }
+}
+
+impl OnConflict {
+    /// Conflict on the primary key, updating the existing row
+    pub fn primary_key() -> Self {
+        Self {
+            target: ConflictTarget::PrimaryKey,
+            action: ConflictAction::DoUpdate,
+        }
+    }
+
+    /// Conflict on a `#[unique]` field or composite, updating the existing row
+    pub fn columns<I, S>(columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            target: ConflictTarget::Columns(columns.into_iter().map(Into::into).collect()),
+            action: ConflictAction::DoUpdate,
+        }
+    }
+
+    /// Keep existing rows unchanged instead of updating them
+    pub fn do_nothing(mut self) -> Self {
+        self.action = ConflictAction::DoNothing;
+        self
+    }
+
+    /// Build the upsert statement for `T`
+    ///
+    /// The conflict target must be the primary key or match a unique key of the
+    /// model, otherwise a validation error is returned. For `#[version]` models
+    /// DO UPDATE only matches the version the record was read with, bound after
+    /// the upsert fields.
+    pub fn to_sql<T: TableMetadata>(&self) -> Result<String, StorehausError> {
+        let upsert_fields = T::upsert_fields();
+        let target = match &self.target {
+            ConflictTarget::PrimaryKey => {
+                let pk = T::primary_key_field();
+                if pk.is_empty() || !upsert_fields.contains(&pk) {
+                    return Err(StorehausError::validation(
+                        T::table_name(),
+                        "primary_key",
+                        "Primary key is missing or generated by the database; use a unique column as conflict target",
+                    ));
+                }
+                vec![pk.to_string()]
+            }
+            ConflictTarget::Columns(columns) => {
+                let is_key = |key: &[&str]| {
+                    key.len() == columns.len() && key.iter().all(|k| columns.iter().any(|c| c == k))
+                };
+                if !is_key(&[T::primary_key_field()])
+                    && !T::unique_keys().iter().any(|key| is_key(key))
+                {
+                    return Err(StorehausError::validation(
+                        T::table_name(),
+                        &columns.join(", "),
+                        "Conflict target must be the primary key or a #[unique] field or composite",
+                    ));
+                }
+                columns.clone()
+            }
+        };
+
+        let table = quoted::<T>(T::table_name())?;
+        let columns = upsert_fields
+            .iter()
+            .map(|f| quoted::<T>(f))
+            .collect::<Result<Vec<_>, _>>()?;
+        let placeholders: Vec<_> = (1..=upsert_fields.len())
+            .map(|i| format!("${}", i))
+            .collect();
+        let target = target
+            .iter()
+            .map(|f| quoted::<T>(f))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let action = match self.action {
+            ConflictAction::DoNothing => "DO NOTHING".to_string(),
+            ConflictAction::DoUpdate => {
+                let mut assignments = T::update_fields()
+                    .into_iter()
+                    .filter(|field| upsert_fields.contains(field))
+                    .map(|field| quoted::<T>(field).map(|f| format!("{} = EXCLUDED.{}", f, f)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let version_field = T::version_field().map(quoted::<T>).transpose()?;
+                if let Some(version_field) = &version_field {
+                    assignments.push(format!(
+                        "{} = {}.{} + 1",
+                        version_field, table, version_field
+                    ));
+                }
+                assignments.push("__updated_at__ = NOW()".to_string());
+                let mut action = format!("DO UPDATE SET {}", assignments.join(", "));
+                // Versioned rows are only updated at the version the record was read with
+                if let Some(version_field) = &version_field {
+                    action.push_str(&format!(
+                        " WHERE {}.{} = ${}",
+                        table,
+                        version_field,
+                        upsert_fields.len() + 1
+                    ));
+                }
+                action
+            }
+        };
+
+        Ok(format!(
+            "INSERT INTO {} ({}, __created_at__, __updated_at__) VALUES ({}, NOW(), NOW()) ON CONFLICT ({}) {} RETURNING *, (xmax = 0) AS __inserted__",
+            table,
+            columns.join(", "),
+            placeholders.join(", "),
+            target.join(", "),
+            action
+        ))
+    }
+}
+
+/// Result of an upsert for one record
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpsertOutcome<T> {
+    /// No conflicting row existed; the record was inserted
+    Created(T),
+    /// A conflicting row existed and was updated
+    Updated(T),
+    /// A conflicting row existed and `do_nothing()` kept it unchanged
+    Skipped,
+}
+
+impl<T> UpsertOutcome<T> {
+    /// The stored record, unless the upsert was skipped
+    pub fn record(&self) -> Option<&T> {
+        match self {
+            Self::Created(record) | Self::Updated(record) => Some(record),
+            Self::Skipped => None,
+        }
+    }
+
+    /// Take the stored record, unless the upsert was skipped
+    pub fn into_record(self) -> Option<T> {
+        match self {
+            Self::Created(record) | Self::Updated(record) => Some(record),
+            Self::Skipped => None,
+        }
+    }
+
+    pub fn is_created(&self) -> bool {
+        matches!(self, Self::Created(_))
+    }
+
+    pub fn is_updated(&self) -> bool {
+        matches!(self, Self::Updated(_))
+    }
+}
+
+impl<T> GenericStore<T>
+where
+    T: TableMetadata
+        + DatabaseExecutor
+        + for<'r> sqlx::FromRow<'r, PgRow>
+        + serde::Serialize
+        + Unpin,
+{
+    /// Execute an upsert statement built by `OnConflict::to_sql`
+    ///
+    /// A versioned DO UPDATE that returns no row conflicted with a row at
+    /// another version and fails with `ConcurrencyConflict`.
+    pub(crate) async fn execute_upsert<'e, E>(
+        executor: E,
+        data: &T,
+        sql: &str,
+        action: ConflictAction,
+    ) -> Result<UpsertOutcome<T>, StorehausError>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    {
+        let versioned = action == ConflictAction::DoUpdate && T::version_field().is_some();
+        let query = data.bind_upsert_params(sql);
+        let query = if versioned {
+            query.bind(data.version())
+        } else {
+            query
+        };
+        let row = query
+            .fetch_optional(executor)
+            .await
+            .map_err(|e| StorehausError::database_operation(T::table_name(), "upsert", e))?;
+
+        let Some(row) = row else {
+            if versioned {
+                return Err(StorehausError::concurrency_conflict(
+                    T::table_name(),
+                    &Self::record_cache_key(&data.extract_id()),
+                    data.version().unwrap_or_default(),
+                ));
+            }
+            return Ok(UpsertOutcome::Skipped);
+        };
+        let record = T::from_row(&row)
+            .map_err(|e| StorehausError::database_operation(T::table_name(), "upsert", e))?;
+        let inserted: bool = row
+            .try_get("__inserted__")
+            .map_err(|e| StorehausError::database_operation(T::table_name(), "upsert", e))?;
+
+        Ok(if inserted {
+            UpsertOutcome::Created(record)
+        } else {
+            UpsertOutcome::Updated(record)
+        })
+    }
+
+    /// Signal for an upsert: Create for inserted rows, Update for updated ones
+    pub(crate) fn upserted_event(
+        &self,
+        outcome: &UpsertOutcome<T>,
+        tags: Option<Vec<String>>,
+    ) -> Option<signal_system::DatabaseEvent> {
+        match outcome {
+            UpsertOutcome::Created(record) => Some(self.created_event(record, tags)),
+            UpsertOutcome::Updated(record) => {
+                Some(self.updated_event(&record.extract_id(), record, tags))
+            }
+            UpsertOutcome::Skipped => None,
+        }
+    }
+}
@@ -156,6 +156,12 @@ impl sqlx::Type<sqlx::Postgres> for NoId {
     }
 }
 
+impl sqlx::postgres::PgHasArrayType for NoId {
+    fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+        <String as sqlx::postgres::PgHasArrayType>::array_type_info()
+    }
+}
+
 impl<'q> sqlx::Encode<'q, sqlx::Postgres> for NoId {
     fn encode_by_ref(&self, buf: &mut sqlx::postgres::PgArgumentBuffer) -> Result<sqlx::encode::IsNull, Box<dyn std::error::Error + Send + Sync>> {
         <&str as sqlx::Encode<sqlx::Postgres>>::encode_by_ref(&"", buf)
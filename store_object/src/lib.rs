@@ -6,19 +6,21 @@
 pub mod errors;
 pub mod generic_store;
 pub mod id_type;
+pub mod outbox;
 pub mod prelude;
 pub mod query_builder;
 pub mod tagged_data;
 pub mod traits;
 pub mod validation;
 
-pub use cache_system::CacheParams;
+pub use cache_system::{CacheParams, CacheWriteMode, StampedeProtection};
 pub use errors::StorehausError;
 pub use generic_store::GenericStore;
 pub use id_type::{HasUniversalId, NoId, UniversalId};
+pub use outbox::{EventOutbox, OutboxDispatcher};
 pub use query_builder::{QueryBuilder, QueryFilter, QueryOperator, SortOrder};
 pub use tagged_data::TaggedData;
-pub use traits::table_metadata::DatabaseExecutor;
+pub use traits::table_metadata::{ColumnDefinition, DatabaseExecutor};
 pub use traits::*;
 pub use validation::{ValidatedFieldName, ValidatedTableName, ValidationError};
 
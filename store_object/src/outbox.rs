@@ -0,0 +1,376 @@
+//! Transactional outbox for signals
+//!
+//! In outbox mode stores write their events into the `storehaus_events` table
+//! in the same transaction as the data change. A dispatcher delivers committed
+//! events to `SignalManager` callbacks, so signals are never emitted for
+//! rolled-back changes and are not lost if the process stops after commit.
+
+use crate::errors::StorehausError;
+use signal_system::{DatabaseEvent, SignalManager};
+use sqlx::{PgConnection, PgPool, Row};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+/// Table holding outbox events
+pub const OUTBOX_TABLE: &str = "storehaus_events";
+
+/// Default number of events claimed per dispatch
+const DEFAULT_BATCH_SIZE: i64 = 100;
+
+/// Default number of delivery attempts before an event is dead-lettered
+const DEFAULT_MAX_ATTEMPTS: i32 = 10;
+
+/// Default time a dispatcher may hold claimed events before others take them over
+const DEFAULT_CLAIM_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Durable event queue delivered to a `SignalManager` after commit
+///
+/// Delivery is at-least-once: an event is marked dispatched only after every
+/// callback succeeded, and it is retried otherwise. Callbacks can deduplicate
+/// with `DatabaseEvent::id`, which holds the outbox row id. An event whose
+/// callbacks still fail after `max_attempts` deliveries is dead-lettered: it
+/// gets `failed_at` and `last_error` set and no longer holds back later events.
+pub struct EventOutbox {
+    pool: PgPool,
+    signal_manager: Arc<SignalManager>,
+    batch_size: i64,
+    max_attempts: i32,
+    claim_timeout: Duration,
+    wake: Notify,
+}
+
+impl std::fmt::Debug for EventOutbox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventOutbox")
+            .field("table", &OUTBOX_TABLE)
+            .field("batch_size", &self.batch_size)
+            .field("max_attempts", &self.max_attempts)
+            .field("claim_timeout", &self.claim_timeout)
+            .finish()
+    }
+}
+
+impl EventOutbox {
+    /// Create an outbox delivering to the given signal manager
+    pub fn new(pool: PgPool, signal_manager: Arc<SignalManager>) -> Self {
+        Self {
+            pool,
+            signal_manager,
+            batch_size: DEFAULT_BATCH_SIZE,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            claim_timeout: DEFAULT_CLAIM_TIMEOUT,
+            wake: Notify::new(),
+        }
+    }
+
+    /// Set how many events are claimed per dispatch
+    pub fn with_batch_size(mut self, batch_size: i64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Set how many deliveries are attempted before an event is dead-lettered
+    pub fn with_max_attempts(mut self, max_attempts: i32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set how long claimed events stay reserved for one dispatcher
+    ///
+    /// Events of a dispatcher that stopped mid-batch are delivered again by
+    /// others once the claim expires. It should exceed the time callbacks take
+    /// for a whole batch.
+    pub fn with_claim_timeout(mut self, claim_timeout: Duration) -> Self {
+        self.claim_timeout = claim_timeout;
+        self
+    }
+
+    /// Signal manager receiving the events
+    pub fn signal_manager(&self) -> &Arc<SignalManager> {
+        &self.signal_manager
+    }
+
+    /// Create the outbox table if it does not exist
+    pub async fn ensure_table(&self) -> Result<(), StorehausError> {
+        let statements = [
+            format!(
+                "CREATE TABLE IF NOT EXISTS {} (
+                    id BIGSERIAL PRIMARY KEY,
+                    table_name TEXT NOT NULL,
+                    event JSONB NOT NULL,
+                    attempts INTEGER NOT NULL DEFAULT 0,
+                    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+                    dispatched_at TIMESTAMP WITH TIME ZONE,
+                    claimed_until TIMESTAMP WITH TIME ZONE,
+                    failed_at TIMESTAMP WITH TIME ZONE,
+                    last_error TEXT
+                )",
+                OUTBOX_TABLE
+            ),
+            // Dead-lettered events leave the index like dispatched ones
+            format!(
+                "CREATE INDEX IF NOT EXISTS idx_{0}_pending ON {0} (id)
+                    WHERE dispatched_at IS NULL AND failed_at IS NULL",
+                OUTBOX_TABLE
+            ),
+        ];
+
+        for sql in statements {
+            sqlx::query(&sql)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| StorehausError::database_operation(OUTBOX_TABLE, "ensure_table", e))?;
+        }
+        Ok(())
+    }
+
+    /// Store events on the connection of the surrounding transaction
+    pub async fn enqueue(
+        &self,
+        conn: &mut PgConnection,
+        events: &[DatabaseEvent],
+    ) -> Result<(), StorehausError> {
+        let sql = format!(
+            "INSERT INTO {} (table_name, event) VALUES ($1, $2)",
+            OUTBOX_TABLE
+        );
+
+        for event in events {
+            let payload = serde_json::to_value(event)
+                .map_err(|e| StorehausError::serialization("DatabaseEvent", e))?;
+            sqlx::query(&sql)
+                .bind(&event.table_name)
+                .bind(payload)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| StorehausError::database_operation(OUTBOX_TABLE, "enqueue", e))?;
+        }
+        Ok(())
+    }
+
+    /// Wake the dispatcher after a commit added events
+    pub fn notify(&self) {
+        self.wake.notify_one();
+    }
+
+    /// Deliver one batch of pending events in order
+    ///
+    /// Returns the number of delivered events. The batch is claimed and the
+    /// claim committed before any callback runs, so no transaction stays open
+    /// during delivery. Delivery stops at the first event whose callbacks fail;
+    /// it and the rest of the batch are released and retried by the next call.
+    /// An event that reaches `max_attempts` is dead-lettered and skipped.
+    ///
+    /// Several dispatchers can run at once, each claiming different events.
+    /// Events are delivered in order per dispatcher only: batches of
+    /// concurrent dispatchers interleave, so run a single dispatcher when
+    /// callbacks depend on the global order.
+    pub async fn dispatch_pending(&self) -> Result<usize, StorehausError> {
+        let mut rows = sqlx::query(&format!(
+            "UPDATE {0} SET claimed_until = NOW() + make_interval(secs => $2)
+             WHERE id IN (
+                 SELECT id FROM {0}
+                 WHERE dispatched_at IS NULL AND failed_at IS NULL
+                   AND (claimed_until IS NULL OR claimed_until < NOW())
+                 ORDER BY id LIMIT $1 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING id, event, attempts",
+            OUTBOX_TABLE
+        ))
+        .bind(self.batch_size)
+        .bind(self.claim_timeout.as_secs_f64())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| StorehausError::database_operation(OUTBOX_TABLE, "dispatch_pending", e))?;
+        rows.sort_by_key(|row| row.get::<i64, _>("id"));
+
+        let mut delivered = Vec::new();
+        let mut dead_letters = Vec::new();
+        let mut retry = None;
+        let mut unattempted = Vec::new();
+
+        for row in rows {
+            let id: i64 = row.get("id");
+            if retry.is_some() {
+                unattempted.push(id);
+                continue;
+            }
+            let attempts: i32 = row.get::<i32, _>("attempts") + 1;
+            let payload: serde_json::Value = row.get("event");
+
+            let mut event = match serde_json::from_value::<DatabaseEvent>(payload) {
+                Ok(event) => event,
+                Err(e) => {
+                    // Undecodable events can never be delivered; don't let them block the queue
+                    tracing::error!(event_id = id, error = %e, "Dead-lettering undecodable outbox event");
+                    dead_letters.push((id, format!("Undecodable event: {}", e)));
+                    continue;
+                }
+            };
+            event.id = Some(id);
+
+            if self.signal_manager.try_emit(event).await {
+                delivered.push(id);
+            } else if attempts >= self.max_attempts {
+                tracing::error!(event_id = id, attempts, "Dead-lettering outbox event");
+                dead_letters.push((id, format!("Callbacks failed after {} attempts", attempts)));
+            } else {
+                retry = Some(id);
+            }
+        }
+
+        self.release(
+            &delivered,
+            "dispatched_at = NOW(), attempts = attempts + 1",
+            None,
+        )
+        .await?;
+        for (id, error) in &dead_letters {
+            self.release(
+                &[*id],
+                "failed_at = NOW(), attempts = attempts + 1, last_error = $2",
+                Some(error),
+            )
+            .await?;
+        }
+        if let Some(id) = retry {
+            self.release(
+                &[id],
+                "attempts = attempts + 1, last_error = $2",
+                Some("Callbacks failed"),
+            )
+            .await?;
+        }
+        self.release(&unattempted, "", None).await?;
+
+        Ok(delivered.len())
+    }
+
+    /// Release the claim on the given events and apply `assignments`
+    ///
+    /// `$2` in the assignments is bound to `error`.
+    async fn release(
+        &self,
+        ids: &[i64],
+        assignments: &str,
+        error: Option<&str>,
+    ) -> Result<(), StorehausError> {
+        if ids.is_empty() {
+            return Ok(());
+        }
+        let separator = if assignments.is_empty() { "" } else { ", " };
+        let sql = format!(
+            "UPDATE {} SET claimed_until = NULL{}{} WHERE id = ANY($1)",
+            OUTBOX_TABLE, separator, assignments
+        );
+        let mut query = sqlx::query(&sql).bind(ids);
+        if let Some(error) = error {
+            query = query.bind(error);
+        }
+        query
+            .execute(&self.pool)
+            .await
+            .map_err(|e| StorehausError::database_operation(OUTBOX_TABLE, "dispatch_pending", e))?;
+        Ok(())
+    }
+
+    /// Number of events not delivered yet, excluding dead-lettered ones
+    pub async fn pending_count(&self) -> Result<i64, StorehausError> {
+        self.count("dispatched_at IS NULL AND failed_at IS NULL")
+            .await
+    }
+
+    /// Number of dead-lettered events
+    pub async fn failed_count(&self) -> Result<i64, StorehausError> {
+        self.count("failed_at IS NOT NULL").await
+    }
+
+    async fn count(&self, condition: &str) -> Result<i64, StorehausError> {
+        let row = sqlx::query(&format!(
+            "SELECT COUNT(*) AS total FROM {} WHERE {}",
+            OUTBOX_TABLE, condition
+        ))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| StorehausError::database_operation(OUTBOX_TABLE, "count", e))?;
+        Ok(row.get("total"))
+    }
+
+    /// Put dead-lettered events back in the queue with fresh attempts
+    ///
+    /// Returns the number of requeued events.
+    pub async fn requeue_failed(&self) -> Result<u64, StorehausError> {
+        let result = sqlx::query(&format!(
+            "UPDATE {} SET failed_at = NULL, attempts = 0 WHERE failed_at IS NOT NULL",
+            OUTBOX_TABLE
+        ))
+        .execute(&self.pool)
+        .await
+        .map_err(|e| StorehausError::database_operation(OUTBOX_TABLE, "requeue_failed", e))?;
+        Ok(result.rows_affected())
+    }
+
+    /// Delete delivered events older than `older_than`; returns the number of deleted rows
+    pub async fn purge_dispatched(&self, older_than: Duration) -> Result<u64, StorehausError> {
+        let result = sqlx::query(&format!(
+            "DELETE FROM {} WHERE dispatched_at < NOW() - make_interval(secs => $1)",
+            OUTBOX_TABLE
+        ))
+        .bind(older_than.as_secs_f64())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| StorehausError::database_operation(OUTBOX_TABLE, "purge_dispatched", e))?;
+        Ok(result.rows_affected())
+    }
+
+    /// Start a background task delivering events
+    ///
+    /// The task wakes up when a store commits events through this outbox and
+    /// otherwise every `poll_interval`, which also picks up events written by
+    /// other processes and retries failed deliveries.
+    pub fn spawn_dispatcher(self: &Arc<Self>, poll_interval: Duration) -> OutboxDispatcher {
+        let outbox = Arc::clone(self);
+        let handle = tokio::spawn(async move {
+            loop {
+                loop {
+                    match outbox.dispatch_pending().await {
+                        Ok(delivered) if delivered as i64 == outbox.batch_size => continue,
+                        Ok(_) => break,
+                        Err(e) => {
+                            tracing::warn!(error = %e, "Outbox dispatch failed");
+                            break;
+                        }
+                    }
+                }
+
+                tokio::select! {
+                    _ = outbox.wake.notified() => {}
+                    _ = tokio::time::sleep(poll_interval) => {}
+                }
+            }
+        });
+
+        OutboxDispatcher { handle }
+    }
+}
+
+/// Handle of a running dispatcher task; the task stops when the handle is dropped
+#[derive(Debug)]
+pub struct OutboxDispatcher {
+    handle: JoinHandle<()>,
+}
+
+impl OutboxDispatcher {
+    /// Stop the dispatcher
+    pub fn stop(self) {
+        self.handle.abort();
+    }
+}
+
+impl Drop for OutboxDispatcher {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
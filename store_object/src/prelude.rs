@@ -10,7 +10,14 @@ pub use crate::traits::table_metadata::DatabaseExecutor;
 pub use crate::errors::StorehausError;
 
 // Core store functionality
-pub use crate::generic_store::{GenericStore, GenericStoreTransaction};
+pub use crate::generic_store::{
+    ConflictAction, ConflictTarget, GenericStore, GenericStoreTransaction, IsolationLevel,
+    OnConflict, PendingEffects, Savepoint, StreamMode, TransactionOptions, TransactionalStore,
+    UpsertOutcome,
+};
+
+// Transactional outbox for signals
+pub use crate::outbox::{EventOutbox, OutboxDispatcher};
 
 // ID type - use what's actually available
 pub use crate::id_type::{HasUniversalId, UniversalId};
@@ -22,10 +29,10 @@ pub use crate::validation::{ValidatedFieldName, ValidatedTableName, ValidationEr
 pub use crate::tagged_data::TaggedData;
 
 // Query building
-pub use crate::query_builder::{GroupBy, JoinClause, JoinType, QueryBuilder, QueryFilter, SelectField, SortOrder, UpdateOperation, UpdateSet};
+pub use crate::query_builder::{Column, GroupBy, JoinClause, JoinType, Page, PagedResult, QueryBuilder, QueryFilter, QueryOperator, SelectField, SortOrder, UpdateOperation, UpdateSet};
 
 // Cache params (re-exported from cache_system)
-pub use crate::CacheParams;
+pub use crate::{CacheParams, CacheWriteMode, StampedeProtection};
 
 // Common external dependencies that are frequently used
 pub use async_trait;
@@ -231,7 +231,7 @@ mod tests {
             SelectField::Aggregate {
                 function,
                 field,
-                alias,
+                alias: _,
             } => {
                 assert_eq!(function, AggregateFunction::CountDistinct);
                 assert_eq!(field, Some("user_id".to_string()));
@@ -6,7 +6,10 @@ use crate::query_builder::aggregation::SelectField;
 use crate::query_builder::filter::QueryFilter;
 use crate::query_builder::grouping::GroupBy;
 use crate::query_builder::join::JoinClause;
+use crate::query_builder::locking::{LockStrength, LockWait, RowLock};
 use crate::query_builder::ordering::SortOrder;
+use crate::query_builder::pagination::{CursorDirection, PageCursor};
+use crate::query_builder::params::{ColumnTypes, QueryParam};
 use crate::query_builder::sql_generation::SqlGenerator;
 use crate::query_builder::update::UpdateSet;
 use serde_json::Value;
@@ -22,6 +25,8 @@ pub struct QueryBuilder {
     pub(crate) limit: Option<i64>,
     pub(crate) offset: Option<i64>,
     pub(crate) updates: Option<UpdateSet>,
+    pub(crate) lock: Option<RowLock>,
+    pub(crate) cursor: Option<PageCursor>,
 }
 
 impl QueryBuilder {
@@ -35,6 +40,8 @@ impl QueryBuilder {
             limit: None,
             offset: None,
             updates: None,
+            lock: None,
+            cursor: None,
         }
     }
 
@@ -98,6 +105,44 @@ impl QueryBuilder {
         self
     }
 
+    /// Read the page following a cursor (a `Page::next_cursor`)
+    ///
+    /// Cursors are keyed on the `order_by` fields plus the primary key and are
+    /// applied by `GenericStore::paginate`, which replaces LIMIT/OFFSET paging.
+    ///
+    /// Example:
+    /// ```ignore
+    /// let page = user_store
+    ///     .paginate(
+    ///         QueryBuilder::new()
+    ///             .order_by("__created_at__", SortOrder::Desc)
+    ///             .after(next_cursor),
+    ///         20,
+    ///     )
+    ///     .await?;
+    /// ```
+    pub fn after(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(PageCursor {
+            direction: CursorDirection::After,
+            token: cursor.into(),
+        });
+        self
+    }
+
+    /// Read the page preceding a cursor (a `Page::prev_cursor`)
+    pub fn before(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(PageCursor {
+            direction: CursorDirection::Before,
+            token: cursor.into(),
+        });
+        self
+    }
+
+    /// Get the page cursor, if any
+    pub fn get_cursor(&self) -> Option<&PageCursor> {
+        self.cursor.as_ref()
+    }
+
     /// Filter by records that have any of the specified tags
     pub fn filter_by_any_tag(self, tags: Vec<String>) -> Self {
         self.filter(QueryFilter::has_any_tag(tags))
@@ -127,6 +172,62 @@ impl QueryBuilder {
         self
     }
 
+    /// Lock the selected rows with `FOR UPDATE` until the transaction ends
+    ///
+    /// Only meaningful inside a transaction; outside one the lock is released
+    /// as soon as the query finishes.
+    ///
+    /// Example:
+    /// ```ignore
+    /// let jobs = tx
+    ///     .find(
+    ///         QueryBuilder::new()
+    ///             .filter(QueryFilter::eq("status", json!("pending")))
+    ///             .order_by("created_at", SortOrder::Asc)
+    ///             .limit(10)
+    ///             .for_update()
+    ///             .skip_locked(),
+    ///     )
+    ///     .await?;
+    /// ```
+    pub fn for_update(mut self) -> Self {
+        self.lock = Some(RowLock::new(LockStrength::Update).with_wait(self.lock_wait()));
+        self
+    }
+
+    /// Lock the selected rows with `FOR SHARE` until the transaction ends
+    pub fn for_share(mut self) -> Self {
+        self.lock = Some(RowLock::new(LockStrength::Share).with_wait(self.lock_wait()));
+        self
+    }
+
+    /// Skip rows locked by other transactions instead of waiting for them
+    /// Implies `for_update()` unless a lock strength is already set
+    pub fn skip_locked(self) -> Self {
+        self.with_lock_wait(LockWait::SkipLocked)
+    }
+
+    /// Fail instead of waiting when a selected row is locked
+    /// Implies `for_update()` unless a lock strength is already set
+    pub fn nowait(self) -> Self {
+        self.with_lock_wait(LockWait::NoWait)
+    }
+
+    fn with_lock_wait(mut self, wait: LockWait) -> Self {
+        let lock = self.lock.unwrap_or(RowLock::new(LockStrength::Update));
+        self.lock = Some(lock.with_wait(wait));
+        self
+    }
+
+    fn lock_wait(&self) -> LockWait {
+        self.lock.map(|lock| lock.wait).unwrap_or_default()
+    }
+
+    /// Get the row locking clause, if any
+    pub fn get_lock(&self) -> Option<&RowLock> {
+        self.lock.as_ref()
+    }
+
     /// Check if this query has update operations defined
     pub fn has_updates(&self) -> bool {
         self.updates.as_ref().map(|u| !u.is_empty()).unwrap_or(false)
@@ -172,6 +273,11 @@ impl QueryBuilder {
         SqlGenerator::build_limit_clause(self.limit, self.offset)
     }
 
+    /// Build FOR UPDATE / FOR SHARE clause
+    pub fn build_lock_clause(&self) -> String {
+        SqlGenerator::build_lock_clause(self.lock.as_ref())
+    }
+
     /// Build complete query parts (WHERE, ORDER BY, LIMIT, Values)
     /// Returns: (where_clause, order_clause, limit_clause, values)
     ///
@@ -185,14 +291,41 @@ impl QueryBuilder {
         (where_clause, order_clause, limit_clause, values)
     }
 
+    /// Build query parts like `build`, with values typed by `columns`
+    /// Returns: (where_clause, order_clause, limit_clause, params)
+    ///
+    /// Placeholders are numbered from `first_param` and cast to the declared
+    /// type of the column each value is compared with.
+    pub fn build_typed(
+        &self,
+        columns: &ColumnTypes,
+        first_param: i32,
+    ) -> (String, String, String, Vec<QueryParam>) {
+        let (where_clause, params) =
+            SqlGenerator::build_typed_where_clause(&self.conditions, Some(columns), first_param);
+
+        (
+            where_clause,
+            self.build_order_clause(),
+            self.build_limit_clause(),
+            params,
+        )
+    }
+
     /// Build complete query with all clauses including SELECT, JOIN, GROUP BY, and HAVING
     /// Returns: (select_clause, join_clause, where_clause, group_by_clause, having_clause, order_clause, limit_clause, where_values, having_values)
+    ///
+    /// HAVING placeholders are numbered after WHERE placeholders, so `where_values`
+    /// followed by `having_values` can be bound in order.
     pub fn build_full(&self) -> (String, String, String, String, String, String, String, Vec<Value>, Vec<Value>) {
         let select_clause = self.build_select_clause();
         let join_clause = self.build_join_clause();
         let (where_clause, where_values) = self.build_where_clause();
         let group_by_clause = self.build_group_by_clause();
-        let (having_clause, having_values) = self.build_having_clause();
+        let (having_clause, having_values) = SqlGenerator::build_having_clause_from(
+            self.group_by.as_ref(),
+            where_values.len() as i32 + 1,
+        );
         let order_clause = self.build_order_clause();
         let limit_clause = self.build_limit_clause();
 
@@ -208,6 +341,69 @@ impl QueryBuilder {
             having_values,
         )
     }
+
+    /// Build a complete SELECT statement for `table_name` using every query part
+    /// `extra_condition` is ANDed into the WHERE clause (e.g. a soft delete filter)
+    /// Returns: (sql, values) with WHERE values followed by HAVING values
+    pub fn build_select_sql(
+        &self,
+        table_name: &str,
+        extra_condition: Option<&str>,
+    ) -> (String, Vec<Value>) {
+        let (sql, params) = self.build_select_sql_with(table_name, extra_condition, None);
+        (sql, SqlGenerator::json_values(params))
+    }
+
+    /// Build a complete SELECT statement like `build_select_sql`, with values typed by `columns`
+    pub fn build_typed_select_sql(
+        &self,
+        table_name: &str,
+        extra_condition: Option<&str>,
+        columns: &ColumnTypes,
+    ) -> (String, Vec<QueryParam>) {
+        self.build_select_sql_with(table_name, extra_condition, Some(columns))
+    }
+
+    fn build_select_sql_with(
+        &self,
+        table_name: &str,
+        extra_condition: Option<&str>,
+        columns: Option<&ColumnTypes>,
+    ) -> (String, Vec<QueryParam>) {
+        let (where_clause, mut params) =
+            SqlGenerator::build_typed_where_clause(&self.conditions, columns, 1);
+        let (having_clause, having_params) = SqlGenerator::build_typed_having_clause(
+            self.group_by.as_ref(),
+            columns,
+            params.len() as i32 + 1,
+        );
+
+        let where_clause = match extra_condition {
+            Some(condition) => match where_clause.strip_prefix("WHERE ") {
+                Some(conditions) => format!("WHERE ({}) AND {}", conditions, condition),
+                None => format!("WHERE {}", condition),
+            },
+            None => where_clause,
+        };
+
+        let sql = [
+            format!("SELECT {} FROM {}", self.build_select_clause(), table_name),
+            self.build_join_clause(),
+            where_clause,
+            self.build_group_by_clause(),
+            having_clause,
+            self.build_order_clause(),
+            self.build_limit_clause(),
+            self.build_lock_clause(),
+        ]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+        params.extend(having_params);
+        (sql, params)
+    }
 }
 
 impl Default for QueryBuilder {
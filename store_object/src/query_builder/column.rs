@@ -0,0 +1,160 @@
+//! Typed column references
+//!
+//! `#[model]` generates a `Column<Model, RustType>` for every field, reached
+//! through `Model::columns()`. Filters built from a column carry its name and
+//! only accept values of the field's Rust type:
+//!
+//! ```ignore
+//! let columns = User::columns();
+//! let query = QueryBuilder::new()
+//!     .filter(columns.email.eq("ann@example.com"))
+//!     .filter(columns.age.gte(18))
+//!     .filter(columns.id.in_(ids));
+//! ```
+
+use super::filter::QueryFilter;
+use crate::traits::table_metadata::TableMetadata;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde_json::Value;
+use sqlx::types::Decimal;
+use std::fmt;
+use std::marker::PhantomData;
+use type_mapping::PostgresValue;
+use uuid::Uuid;
+
+/// Rust types a column can hold, with the type its values are compared as
+///
+/// Nullable fields compare with the inner type: an `Option<String>` column
+/// takes `String` values and is tested for NULL with `is_null`.
+pub trait ColumnType {
+    type Value: Into<PostgresValue>;
+}
+
+macro_rules! column_types {
+    ($($ty:ty),* $(,)?) => {
+        $(impl ColumnType for $ty {
+            type Value = $ty;
+        })*
+    };
+}
+
+column_types!(
+    String,
+    i16,
+    i32,
+    i64,
+    f32,
+    f64,
+    bool,
+    Uuid,
+    DateTime<Utc>,
+    NaiveDate,
+    NaiveDateTime,
+    Decimal,
+    Value,
+);
+
+impl<T: ColumnType> ColumnType for Option<T> {
+    type Value = T::Value;
+}
+
+impl<T: ColumnType> ColumnType for Vec<T> {
+    type Value = Vec<T::Value>;
+}
+
+/// A column of model `M` holding Rust type `T`
+pub struct Column<M, T> {
+    name: &'static str,
+    _marker: PhantomData<fn() -> (M, T)>,
+}
+
+impl<M, T> Column<M, T> {
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Column name as used in queries
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Column name qualified with the model's table, for queries with joins
+    pub fn qualified(&self) -> String
+    where
+        M: TableMetadata,
+    {
+        format!("{}.{}", M::table_name(), self.name)
+    }
+}
+
+impl<M, T: ColumnType> Column<M, T> {
+    pub fn eq(&self, value: impl Into<T::Value>) -> QueryFilter {
+        QueryFilter::eq(self.name, value.into())
+    }
+
+    pub fn ne(&self, value: impl Into<T::Value>) -> QueryFilter {
+        QueryFilter::ne(self.name, value.into())
+    }
+
+    pub fn gt(&self, value: impl Into<T::Value>) -> QueryFilter {
+        QueryFilter::gt(self.name, value.into())
+    }
+
+    pub fn gte(&self, value: impl Into<T::Value>) -> QueryFilter {
+        QueryFilter::gte(self.name, value.into())
+    }
+
+    pub fn lt(&self, value: impl Into<T::Value>) -> QueryFilter {
+        QueryFilter::lt(self.name, value.into())
+    }
+
+    pub fn lte(&self, value: impl Into<T::Value>) -> QueryFilter {
+        QueryFilter::lte(self.name, value.into())
+    }
+
+    pub fn in_<V: Into<T::Value>>(&self, values: impl IntoIterator<Item = V>) -> QueryFilter {
+        QueryFilter::in_list(self.name, values.into_iter().map(Into::into).collect())
+    }
+
+    pub fn not_in<V: Into<T::Value>>(&self, values: impl IntoIterator<Item = V>) -> QueryFilter {
+        QueryFilter::not_in_list(self.name, values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<M, T: ColumnType<Value = String>> Column<M, T> {
+    pub fn like(&self, pattern: &str) -> QueryFilter {
+        QueryFilter::like(self.name, pattern)
+    }
+
+    pub fn ilike(&self, pattern: &str) -> QueryFilter {
+        QueryFilter::ilike(self.name, pattern)
+    }
+}
+
+impl<M, T> Column<M, Option<T>> {
+    pub fn is_null(&self) -> QueryFilter {
+        QueryFilter::is_null(self.name)
+    }
+
+    pub fn is_not_null(&self) -> QueryFilter {
+        QueryFilter::is_not_null(self.name)
+    }
+}
+
+// Manual impls: derives would require `M` and `T` to implement the traits too
+impl<M, T> Clone for Column<M, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M, T> Copy for Column<M, T> {}
+
+impl<M, T> fmt::Debug for Column<M, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Column").field(&self.name).finish()
+    }
+}
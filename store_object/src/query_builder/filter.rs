@@ -3,6 +3,7 @@
 //! This module provides SQL query construction utilities.
 
 use serde_json::Value;
+use type_mapping::PostgresValue;
 
 /// Query condition operators
 #[derive(Debug, Clone, PartialEq)]
@@ -26,8 +27,10 @@ pub enum QueryOperator {
 #[derive(Debug, Clone, PartialEq)]
 pub struct QueryCondition {
     pub field: String,
+    /// Keys into the JSON column `field`; empty for plain column conditions
+    pub json_path: Vec<String>,
     pub operator: QueryOperator,
-    pub value: Option<Value>, // None for IS NULL/IS NOT NULL
+    pub value: Option<PostgresValue>, // None for IS NULL/IS NOT NULL
 }
 
 /// Logical operators for combining conditions
@@ -50,8 +53,18 @@ pub enum QueryFilter {
 impl QueryFilter {
     /// Create a simple condition
     pub fn condition(field: &str, operator: QueryOperator, value: Option<Value>) -> Self {
+        Self::typed_condition(field, operator, value.map(PostgresValue::Json))
+    }
+
+    /// Create a simple condition from a typed value
+    pub fn typed_condition(
+        field: &str,
+        operator: QueryOperator,
+        value: Option<PostgresValue>,
+    ) -> Self {
         Self::Condition(QueryCondition {
             field: field.to_string(),
+            json_path: Vec::new(),
             operator,
             value,
         })
@@ -74,61 +87,91 @@ impl QueryFilter {
     }
 
     /// Equal condition
-    pub fn eq(field: &str, value: Value) -> Self {
-        Self::condition(field, QueryOperator::Eq, Some(value))
+    pub fn eq(field: &str, value: impl Into<PostgresValue>) -> Self {
+        Self::typed_condition(field, QueryOperator::Eq, Some(value.into()))
     }
 
     /// Not equal condition
-    pub fn ne(field: &str, value: Value) -> Self {
-        Self::condition(field, QueryOperator::Ne, Some(value))
+    pub fn ne(field: &str, value: impl Into<PostgresValue>) -> Self {
+        Self::typed_condition(field, QueryOperator::Ne, Some(value.into()))
     }
 
     /// Greater than condition
-    pub fn gt(field: &str, value: Value) -> Self {
-        Self::condition(field, QueryOperator::Gt, Some(value))
+    pub fn gt(field: &str, value: impl Into<PostgresValue>) -> Self {
+        Self::typed_condition(field, QueryOperator::Gt, Some(value.into()))
     }
 
     /// Greater than or equal condition
-    pub fn gte(field: &str, value: Value) -> Self {
-        Self::condition(field, QueryOperator::Gte, Some(value))
+    pub fn gte(field: &str, value: impl Into<PostgresValue>) -> Self {
+        Self::typed_condition(field, QueryOperator::Gte, Some(value.into()))
     }
 
     /// Less than condition
-    pub fn lt(field: &str, value: Value) -> Self {
-        Self::condition(field, QueryOperator::Lt, Some(value))
+    pub fn lt(field: &str, value: impl Into<PostgresValue>) -> Self {
+        Self::typed_condition(field, QueryOperator::Lt, Some(value.into()))
     }
 
     /// Less than or equal condition
-    pub fn lte(field: &str, value: Value) -> Self {
-        Self::condition(field, QueryOperator::Lte, Some(value))
+    pub fn lte(field: &str, value: impl Into<PostgresValue>) -> Self {
+        Self::typed_condition(field, QueryOperator::Lte, Some(value.into()))
     }
 
     /// LIKE condition
     pub fn like(field: &str, pattern: &str) -> Self {
-        Self::condition(
+        Self::typed_condition(
             field,
             QueryOperator::Like,
-            Some(Value::String(pattern.to_string())),
+            Some(PostgresValue::Text(pattern.to_string())),
         )
     }
 
     /// ILIKE condition (case insensitive)
     pub fn ilike(field: &str, pattern: &str) -> Self {
-        Self::condition(
+        Self::typed_condition(
             field,
             QueryOperator::ILike,
-            Some(Value::String(pattern.to_string())),
+            Some(PostgresValue::Text(pattern.to_string())),
         )
     }
 
     /// IN condition
     pub fn in_values(field: &str, values: Vec<Value>) -> Self {
-        Self::condition(field, QueryOperator::In, Some(Value::Array(values)))
+        Self::in_list(field, values)
     }
 
     /// NOT IN condition
     pub fn not_in_values(field: &str, values: Vec<Value>) -> Self {
-        Self::condition(field, QueryOperator::NotIn, Some(Value::Array(values)))
+        Self::not_in_list(field, values)
+    }
+
+    /// IN condition from typed values
+    pub fn in_list<V: Into<PostgresValue>>(field: &str, values: Vec<V>) -> Self {
+        Self::typed_condition(field, QueryOperator::In, Some(PostgresValue::from(values)))
+    }
+
+    /// NOT IN condition from typed values
+    pub fn not_in_list<V: Into<PostgresValue>>(field: &str, values: Vec<V>) -> Self {
+        Self::typed_condition(field, QueryOperator::NotIn, Some(PostgresValue::from(values)))
+    }
+
+    /// Condition on a value inside a JSON column, like `metadata->>'type' = 'premium'`
+    ///
+    /// Only `column` is written into the SQL and checked like any other
+    /// column; the keys are bound as one parameter (`metadata #>> $1`). The
+    /// extracted value is text, so values are compared as text and a JSON
+    /// `null` value tests for a missing key.
+    pub fn json_path(
+        column: &str,
+        path: &[&str],
+        operator: QueryOperator,
+        value: Option<Value>,
+    ) -> Self {
+        Self::Condition(QueryCondition {
+            field: column.to_string(),
+            json_path: path.iter().map(|key| key.to_string()).collect(),
+            operator,
+            value: value.and_then(json_text),
+        })
     }
 
     /// IS NULL condition
@@ -143,11 +186,10 @@ impl QueryFilter {
 
     /// Filter by records that have any of the specified tags
     pub fn has_any_tag(tags: Vec<String>) -> Self {
-        let tag_values: Vec<Value> = tags.into_iter().map(Value::String).collect();
-        Self::condition(
+        Self::typed_condition(
             "__tags__",
             QueryOperator::ArrayOverlap,
-            Some(Value::Array(tag_values)),
+            Some(PostgresValue::from(tags)),
         )
     }
 
@@ -156,10 +198,10 @@ impl QueryFilter {
         let conditions: Vec<QueryFilter> = tags
             .into_iter()
             .map(|tag| {
-                Self::condition(
+                Self::typed_condition(
                     "__tags__",
                     QueryOperator::Like,
-                    Some(Value::String(format!("%{}%", tag))),
+                    Some(PostgresValue::Text(format!("%{}%", tag))),
                 )
             })
             .collect();
@@ -172,3 +214,15 @@ impl QueryFilter {
         Self::has_any_tag(vec![tag])
     }
 }
+
+/// Text a JSON value compares as after `#>>` extraction
+fn json_text(value: Value) -> Option<PostgresValue> {
+    match value {
+        Value::Null => None,
+        Value::String(text) => Some(PostgresValue::Text(text)),
+        Value::Array(items) => Some(PostgresValue::Array(
+            items.into_iter().filter_map(json_text).collect(),
+        )),
+        other => Some(PostgresValue::Text(other.to_string())),
+    }
+}
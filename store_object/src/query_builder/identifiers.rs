@@ -0,0 +1,339 @@
+//! Identifier checks for queries
+//!
+//! Field names, join tables and aliases in a `QueryBuilder` are written into
+//! the SQL text, so a name taken from an API parameter could inject SQL.
+//! `QueryBuilder::check_identifiers` accepts only the model's columns, the
+//! tables and aliases the query joins explicitly, and its own SELECT aliases,
+//! and returns a copy of the query with every identifier quoted.
+
+use crate::query_builder::aggregation::SelectField;
+use crate::query_builder::builder::QueryBuilder;
+use crate::query_builder::filter::{QueryCondition, QueryFilter};
+use crate::query_builder::join::{JoinClause, JoinCondition};
+use crate::query_builder::params::ColumnTypes;
+use crate::query_builder::update::UpdateSet;
+use crate::validation::{ValidatedFieldName, ValidationError};
+
+/// Aggregate functions accepted in HAVING and ORDER BY expressions
+const AGGREGATE_FUNCTIONS: &[&str] = &["COUNT", "SUM", "AVG", "MIN", "MAX"];
+
+/// A name in a query that is not a known column or not a valid identifier
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidIdentifier {
+    /// The name as written in the query
+    pub field: String,
+    pub error: ValidationError,
+}
+
+/// Names a query may reference, resolved to quoted SQL
+struct Identifiers<'a> {
+    columns: &'a ColumnTypes,
+    /// Tables and aliases introduced by the query's JOINs
+    joined: Vec<String>,
+    /// Output names of the query's SELECT fields
+    aliases: Vec<String>,
+}
+
+impl Identifiers<'_> {
+    /// A single quoted or bare identifier, quoted
+    fn name(name: &str) -> Result<String, ValidationError> {
+        let bare = match name.strip_prefix('"').and_then(|n| n.strip_suffix('"')) {
+            Some(bare) => bare,
+            None => name,
+        };
+        ValidatedFieldName::referenced(bare).map(|name| name.quoted())
+    }
+
+    /// A joined table, optionally qualified with its schema
+    fn table(table: &str) -> Result<String, ValidationError> {
+        table
+            .split('.')
+            .map(Self::name)
+            .collect::<Result<Vec<_>, _>>()
+            .map(|parts| parts.join("."))
+    }
+
+    /// A column of the model, or of a joined table when qualified with its name or alias
+    fn column(&self, field: &str) -> Result<String, ValidationError> {
+        // Without generated metadata there is nothing to check names against
+        if self.columns.is_empty() {
+            return Err(ValidationError::NoColumnMetadata(field.to_string()));
+        }
+        let unknown = || ValidationError::UnknownColumn(field.to_string());
+        let bare = |name: &str| name.trim_matches('"').to_string();
+
+        match field.split_once('.') {
+            None => {
+                if !self.columns.contains(&bare(field)) {
+                    return Err(unknown());
+                }
+                Self::name(field)
+            }
+            Some((table, column)) => {
+                let table = bare(table);
+                if table == self.columns.table() {
+                    if !self.columns.contains(&bare(column)) {
+                        return Err(unknown());
+                    }
+                } else if !self.joined.contains(&table) {
+                    return Err(unknown());
+                }
+                Ok(format!("{}.{}", Self::name(&table)?, Self::name(column)?))
+            }
+        }
+    }
+
+    /// A SELECT target: a column, `*` or `table.*`
+    fn select_target(&self, field: &str) -> Result<String, ValidationError> {
+        match field.strip_suffix(".*") {
+            _ if field == "*" => Ok(field.to_string()),
+            Some(table)
+                if table == self.columns.table() || self.joined.contains(&table.to_string()) =>
+            {
+                Ok(format!("{}.*", Self::name(table)?))
+            }
+            Some(_) => Err(ValidationError::UnknownColumn(field.to_string())),
+            None => self.column(field),
+        }
+    }
+
+    /// A column or an aggregate over one, such as `COUNT(*)` or `SUM(orders.total)`
+    fn expression(&self, field: &str) -> Result<String, ValidationError> {
+        let Some((function, argument)) = field
+            .strip_suffix(')')
+            .and_then(|call| call.split_once('('))
+        else {
+            return self.column(field);
+        };
+
+        let function = function.trim().to_ascii_uppercase();
+        if !AGGREGATE_FUNCTIONS.contains(&function.as_str()) {
+            return Err(ValidationError::InvalidCharacters(field.to_string()));
+        }
+        let argument = argument.trim();
+        let argument = if argument == "*" {
+            argument.to_string()
+        } else if let Some(column) = argument
+            .get(..9)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("DISTINCT "))
+            .map(|_| argument[9..].trim())
+        {
+            format!("DISTINCT {}", self.column(column)?)
+        } else {
+            self.column(argument)?
+        };
+        Ok(format!("{}({})", function, argument))
+    }
+
+    /// An ORDER BY target: a SELECT alias or an expression
+    fn order_target(&self, field: &str) -> Result<String, ValidationError> {
+        if self.aliases.iter().any(|alias| alias == field) {
+            return Self::name(field);
+        }
+        self.expression(field)
+    }
+
+    fn filter(
+        &self,
+        filter: &QueryFilter,
+        resolve: &dyn Fn(&str) -> Result<String, ValidationError>,
+    ) -> Result<QueryFilter, InvalidIdentifier> {
+        match filter {
+            QueryFilter::Condition(condition) => Ok(QueryFilter::Condition(QueryCondition {
+                field: checked(&condition.field, resolve)?,
+                ..condition.clone()
+            })),
+            QueryFilter::Group { operator, filters } => Ok(QueryFilter::Group {
+                operator: operator.clone(),
+                filters: filters
+                    .iter()
+                    .map(|filter| self.filter(filter, resolve))
+                    .collect::<Result<_, _>>()?,
+            }),
+        }
+    }
+
+    fn select_field(&self, field: &SelectField) -> Result<SelectField, InvalidIdentifier> {
+        Ok(match field {
+            SelectField::All => SelectField::All,
+            SelectField::Field(name) => {
+                SelectField::Field(checked(name, &|name| self.select_target(name))?)
+            }
+            SelectField::FieldWithAlias { field, alias } => SelectField::FieldWithAlias {
+                field: checked(field, &|name| self.column(name))?,
+                alias: checked(alias, &Self::name)?,
+            },
+            SelectField::Aggregate {
+                function,
+                field,
+                alias,
+            } => SelectField::Aggregate {
+                function: function.clone(),
+                field: field
+                    .as_deref()
+                    .map(|field| checked(field, &|name| self.column(name)))
+                    .transpose()?,
+                alias: alias
+                    .as_deref()
+                    .map(|alias| checked(alias, &Self::name))
+                    .transpose()?,
+            },
+        })
+    }
+
+    fn join(&self, join: &JoinClause) -> Result<JoinClause, InvalidIdentifier> {
+        let condition = match &join.condition {
+            JoinCondition::On {
+                left_field,
+                right_field,
+            } => JoinCondition::On {
+                left_field: checked(left_field, &|name| self.column(name))?,
+                right_field: checked(right_field, &|name| self.column(name))?,
+            },
+            // USING columns exist in both tables, so they must be model columns
+            JoinCondition::Using(columns) => JoinCondition::Using(
+                columns
+                    .iter()
+                    .map(|column| {
+                        checked(column, &|name| {
+                            if name.contains('.') {
+                                return Err(ValidationError::InvalidCharacters(name.to_string()));
+                            }
+                            self.column(name)
+                        })
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+        };
+
+        Ok(JoinClause {
+            join_type: join.join_type.clone(),
+            table: checked(&join.table, &Self::table)?,
+            alias: join
+                .alias
+                .as_deref()
+                .map(|alias| checked(alias, &Self::name))
+                .transpose()?,
+            condition,
+        })
+    }
+
+    fn updates(&self, updates: &UpdateSet) -> Result<UpdateSet, InvalidIdentifier> {
+        let mut checked_updates = UpdateSet::new();
+        for (field, operation) in &updates.operations {
+            // SET targets are never qualified
+            let column = checked(field, &|name| {
+                if name.contains('.') {
+                    return Err(ValidationError::UnknownColumn(name.to_string()));
+                }
+                self.column(name)
+            })?;
+            checked_updates.operations.insert(column, operation.clone());
+        }
+        Ok(checked_updates)
+    }
+}
+
+/// Resolve one name, keeping the name as written for the error
+fn checked(
+    field: &str,
+    resolve: &dyn Fn(&str) -> Result<String, ValidationError>,
+) -> Result<String, InvalidIdentifier> {
+    resolve(field).map_err(|error| InvalidIdentifier {
+        field: field.to_string(),
+        error,
+    })
+}
+
+impl QueryBuilder {
+    /// Copy of the query with every identifier checked against `columns` and quoted
+    ///
+    /// Unqualified names must be columns of the model. Qualified names may also
+    /// refer to tables or aliases the query joins, whose columns are checked
+    /// for valid characters only. Without any `columns` every column name is
+    /// rejected. Filters on JSON values use `QueryFilter::json_path`, whose
+    /// column is checked and whose keys are bound. ORDER BY may use SELECT
+    /// aliases, and HAVING and ORDER BY may use `COUNT`, `SUM`, `AVG`, `MIN`
+    /// and `MAX` over columns. Quoted identifiers are case-sensitive.
+    pub fn check_identifiers(
+        &self,
+        columns: &ColumnTypes,
+    ) -> Result<QueryBuilder, InvalidIdentifier> {
+        let mut joined = Vec::new();
+        for join in &self.joins {
+            // Columns of `schema.table` are qualified with the table name alone
+            let table = join.table.rsplit('.').next().unwrap_or(&join.table);
+            joined.push(table.trim_matches('"').to_string());
+            if let Some(alias) = &join.alias {
+                joined.push(alias.trim_matches('"').to_string());
+            }
+        }
+        let aliases = self
+            .select_fields
+            .iter()
+            .filter_map(|field| match field {
+                SelectField::FieldWithAlias { alias, .. } => Some(alias.clone()),
+                SelectField::Aggregate { alias, .. } => alias.clone(),
+                _ => None,
+            })
+            .collect();
+        let identifiers = Identifiers {
+            columns,
+            joined,
+            aliases,
+        };
+        let column = |name: &str| identifiers.column(name);
+        let expression = |name: &str| identifiers.expression(name);
+
+        let mut query = self.clone();
+        query.select_fields = self
+            .select_fields
+            .iter()
+            .map(|field| identifiers.select_field(field))
+            .collect::<Result<_, _>>()?;
+        query.joins = self
+            .joins
+            .iter()
+            .map(|join| identifiers.join(join))
+            .collect::<Result<_, _>>()?;
+        query.conditions = self
+            .conditions
+            .iter()
+            .map(|filter| identifiers.filter(filter, &column))
+            .collect::<Result<_, _>>()?;
+        if let Some(group_by) = &self.group_by {
+            let mut checked_group = group_by.clone();
+            checked_group.fields = group_by
+                .fields
+                .iter()
+                .map(|field| checked(field, &column))
+                .collect::<Result<_, _>>()?;
+            checked_group.having = group_by
+                .having
+                .as_ref()
+                .map(|having| {
+                    having
+                        .iter()
+                        .map(|filter| identifiers.filter(filter, &expression))
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?;
+            query.group_by = Some(checked_group);
+        }
+        query.order_by = self
+            .order_by
+            .iter()
+            .map(|(field, order)| {
+                checked(field, &|name| identifiers.order_target(name))
+                    .map(|field| (field, order.clone()))
+            })
+            .collect::<Result<_, _>>()?;
+        query.updates = self
+            .updates
+            .as_ref()
+            .map(|updates| identifiers.updates(updates))
+            .transpose()?;
+
+        Ok(query)
+    }
+}
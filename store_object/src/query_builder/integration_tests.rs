@@ -3,6 +3,7 @@
 //! This module provides SQL query construction utilities.
 
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod integration_tests {
     use crate::query_builder::{QueryBuilder, QueryFilter, QueryOperator, SortOrder};
     use serde_json::{json, Value};
@@ -364,8 +365,9 @@ mod integration_tests {
                 Some(json!("%{tag1}%")),
             ))
             // JSON operations
-            .filter(QueryFilter::condition(
-                "metadata->>'type'",
+            .filter(QueryFilter::json_path(
+                "metadata",
+                &["type"],
                 QueryOperator::Eq,
                 Some(json!("premium")),
             ))
@@ -383,6 +385,7 @@ mod integration_tests {
         let (where_clause, _, _, values) = builder.build();
 
         assert!(where_clause.contains("LIKE"));
+        assert!(where_clause.contains("(metadata #>> $2::TEXT[]) = $3"));
         assert!(where_clause.contains("ILIKE"));
         assert!(where_clause.contains("IS NOT NULL"));
         assert!(!values.is_empty());
@@ -0,0 +1,74 @@
+//! Row locking clauses for SELECT queries
+//!
+//! `QueryBuilder::for_update` and `for_share` append `FOR UPDATE` or
+//! `FOR SHARE`, which lock the selected rows until the transaction ends.
+//! `nowait` fails the query when a row is already locked, and `skip_locked`
+//! leaves locked rows out, e.g. for job queues with several workers.
+
+/// Strength of the row lock taken by a SELECT
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockStrength {
+    /// `FOR UPDATE`: blocks other writers and lockers
+    Update,
+    /// `FOR SHARE`: blocks writers but allows other shared locks
+    Share,
+}
+
+impl LockStrength {
+    pub fn to_sql(&self) -> &'static str {
+        match self {
+            LockStrength::Update => "FOR UPDATE",
+            LockStrength::Share => "FOR SHARE",
+        }
+    }
+}
+
+/// What a locking SELECT does when a row is already locked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LockWait {
+    /// Wait until the lock is released
+    #[default]
+    Wait,
+    /// Fail immediately with a lock-not-available error
+    NoWait,
+    /// Leave locked rows out of the result
+    SkipLocked,
+}
+
+impl LockWait {
+    pub fn to_sql(&self) -> &'static str {
+        match self {
+            LockWait::Wait => "",
+            LockWait::NoWait => "NOWAIT",
+            LockWait::SkipLocked => "SKIP LOCKED",
+        }
+    }
+}
+
+/// Row locking clause of a SELECT
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowLock {
+    pub strength: LockStrength,
+    pub wait: LockWait,
+}
+
+impl RowLock {
+    pub fn new(strength: LockStrength) -> Self {
+        Self {
+            strength,
+            wait: LockWait::Wait,
+        }
+    }
+
+    pub fn with_wait(mut self, wait: LockWait) -> Self {
+        self.wait = wait;
+        self
+    }
+
+    pub fn to_sql(&self) -> String {
+        match self.wait {
+            LockWait::Wait => self.strength.to_sql().to_string(),
+            wait => format!("{} {}", self.strength.to_sql(), wait.to_sql()),
+        }
+    }
+}
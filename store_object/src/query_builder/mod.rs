@@ -4,11 +4,15 @@
 
 pub mod aggregation;
 pub mod builder;
+pub mod column;
 pub mod filter;
 pub mod grouping;
+pub mod identifiers;
 pub mod join;
+pub mod locking;
 pub mod ordering;
 pub mod pagination;
+pub mod params;
 pub mod sql_generation;
 pub mod update;
 
@@ -21,8 +25,13 @@ mod integration_tests;
 // Re-export main types for backward compatibility
 pub use aggregation::{AggregateFunction, SelectField};
 pub use builder::QueryBuilder;
+pub use column::{Column, ColumnType};
 pub use filter::{QueryFilter, QueryOperator};
 pub use grouping::GroupBy;
+pub use identifiers::InvalidIdentifier;
 pub use join::{JoinClause, JoinCondition, JoinType};
+pub use locking::{LockStrength, LockWait, RowLock};
 pub use ordering::SortOrder;
+pub use pagination::{CursorCodec, CursorDirection, CursorPosition, Page, PageCursor, PagedResult};
+pub use params::{ColumnTypes, QueryParam};
 pub use update::{UpdateOperation, UpdateSet};
@@ -15,4 +15,12 @@ impl SortOrder {
             SortOrder::Desc => "DESC",
         }
     }
+
+    /// The opposite direction
+    pub fn reverse(&self) -> SortOrder {
+        match self {
+            SortOrder::Asc => SortOrder::Desc,
+            SortOrder::Desc => SortOrder::Asc,
+        }
+    }
 }
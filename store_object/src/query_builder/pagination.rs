@@ -2,6 +2,14 @@
 //!
 //! This module provides SQL query construction utilities.
 
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use std::sync::{Arc, Once, OnceLock};
+
 /// Pagination configuration
 #[derive(Debug, Clone)]
 pub struct Pagination {
@@ -47,3 +55,175 @@ impl Pagination {
         clauses.join(" ")
     }
 }
+
+/// Side of a cursor a keyset page is read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorDirection {
+    /// Rows following the cursor in the query's order
+    After,
+    /// Rows preceding the cursor in the query's order
+    Before,
+}
+
+/// Encoded cursor set with `QueryBuilder::after` / `QueryBuilder::before`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageCursor {
+    pub direction: CursorDirection,
+    pub token: String,
+}
+
+/// One page of a keyset (cursor) paginated query
+///
+/// `next_cursor` continues with `QueryBuilder::after`, `prev_cursor` goes back
+/// with `QueryBuilder::before`. `has_more` tells whether further rows exist in
+/// the direction the page was read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+/// One numbered page of an offset paginated query, with the total row count
+///
+/// Pages are numbered from 1. `total` counts every row matching the query,
+/// read in the same statement as `items` so the two always agree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PagedResult<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+    pub page: i64,
+    pub per_page: i64,
+    pub total_pages: i64,
+}
+
+impl<T> PagedResult<T> {
+    /// Assemble a page, deriving `total_pages` from `total` and `per_page`
+    pub fn new(items: Vec<T>, total: i64, page: i64, per_page: i64) -> Self {
+        let total_pages = if per_page > 0 {
+            (total + per_page - 1) / per_page
+        } else {
+            0
+        };
+        Self {
+            items,
+            total,
+            page,
+            per_page,
+            total_pages,
+        }
+    }
+
+    /// Check if a following page exists
+    pub fn has_next_page(&self) -> bool {
+        self.page < self.total_pages
+    }
+
+    /// Check if a preceding page exists
+    pub fn has_prev_page(&self) -> bool {
+        self.page > 1
+    }
+}
+
+/// Position of a row within an ordering, as carried by a cursor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CursorPosition {
+    /// Table the cursor was issued for
+    #[serde(rename = "t")]
+    pub table: String,
+    /// Ordering the cursor was issued for, as `field ASC|DESC`
+    #[serde(rename = "o")]
+    pub ordering: Vec<String>,
+    /// Values of the ordering fields in the row the cursor points at
+    #[serde(rename = "v")]
+    pub values: Vec<Value>,
+}
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Encodes cursor positions as opaque, tamper-evident strings
+///
+/// A cursor is the base64url JSON position followed by its HMAC-SHA256
+/// signature. Stores share one random key per process by default, and the
+/// first cursor signed or checked with it logs a warning; deployments with
+/// several nodes set a common secret so cursors work on every node.
+#[derive(Clone)]
+pub struct CursorCodec {
+    key: Arc<[u8]>,
+    /// Signing with the random per-process key of `Default`
+    process_key: bool,
+}
+
+impl std::fmt::Debug for CursorCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CursorCodec").finish_non_exhaustive()
+    }
+}
+
+impl Default for CursorCodec {
+    fn default() -> Self {
+        static PROCESS_KEY: OnceLock<Arc<[u8]>> = OnceLock::new();
+        let key = PROCESS_KEY.get_or_init(|| {
+            let mut key = Vec::with_capacity(32);
+            key.extend_from_slice(uuid::Uuid::new_v4().as_bytes());
+            key.extend_from_slice(uuid::Uuid::new_v4().as_bytes());
+            key.into()
+        });
+        Self {
+            key: key.clone(),
+            process_key: true,
+        }
+    }
+}
+
+impl CursorCodec {
+    /// Codec signing cursors with `secret`
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            key: secret.as_ref().into(),
+            process_key: false,
+        }
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        static WARNED: Once = Once::new();
+        if self.process_key {
+            WARNED.call_once(|| {
+                tracing::warn!(
+                    "Page cursors are signed with a random per-process key and stop verifying after a restart or on another node; set a shared secret with GenericStore::set_cursor_secret"
+                )
+            });
+        }
+
+        // HMAC accepts keys of any length
+        HmacSha256::new_from_slice(&self.key).expect("HMAC key of any length")
+    }
+
+    /// Encode and sign a position
+    pub fn encode(&self, position: &CursorPosition) -> String {
+        let payload = serde_json::to_vec(position).unwrap_or_default();
+        let mut mac = self.mac();
+        mac.update(&payload);
+        let signature = mac.finalize().into_bytes();
+
+        format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(&payload),
+            URL_SAFE_NO_PAD.encode(signature)
+        )
+    }
+
+    /// Verify and decode a cursor; `None` if it is malformed or was not signed with this key
+    pub fn decode(&self, cursor: &str) -> Option<CursorPosition> {
+        let (payload, signature) = cursor.split_once('.')?;
+        let payload = URL_SAFE_NO_PAD.decode(payload).ok()?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).ok()?;
+
+        let mut mac = self.mac();
+        mac.update(&payload);
+        mac.verify_slice(&signature).ok()?;
+
+        serde_json::from_slice(&payload).ok()
+    }
+}
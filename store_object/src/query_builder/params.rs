@@ -0,0 +1,130 @@
+//! Typed query parameters
+//!
+//! Filter and update values are carried as `PostgresValue` and bound by the
+//! declared type of the column they refer to. The generated SQL casts each
+//! placeholder to that type, so PostgreSQL never has to guess it.
+
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use type_mapping::PostgresValue;
+
+/// Declared PostgreSQL types of a table's columns
+///
+/// Built from `TableMetadata::get_table_fields()`; fields may be given bare
+/// (`email`) or qualified with the table name (`users.email`).
+#[derive(Debug, Clone, Default)]
+pub struct ColumnTypes {
+    table: String,
+    types: HashMap<&'static str, &'static str>,
+}
+
+impl ColumnTypes {
+    pub fn new(
+        table: &str,
+        fields: impl IntoIterator<Item = (&'static str, &'static str)>,
+    ) -> Self {
+        Self {
+            table: table.to_string(),
+            types: fields.into_iter().collect(),
+        }
+    }
+
+    /// Table the columns belong to
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// Whether `column` (unqualified) is a column of the table
+    pub fn contains(&self, column: &str) -> bool {
+        self.types.contains_key(column)
+    }
+
+    /// Whether no columns are known, e.g. for models without generated metadata
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// Declared type of `field`, or `None` for expressions and other tables' columns
+    ///
+    /// Quoted references (`"users"."email"`) are looked up like bare ones.
+    pub fn get(&self, field: &str) -> Option<&'static str> {
+        let column = match field.split_once('.') {
+            Some((table, column)) if unquote(table) == self.table => unquote(column),
+            Some(_) => return None,
+            None => unquote(field),
+        };
+        self.types.get(column).copied()
+    }
+}
+
+/// A value to bind together with the declared type of the column it refers to
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueryParam {
+    /// Field the value is compared with or assigned to, as written in the query
+    pub field: String,
+    pub value: PostgresValue,
+    /// Declared column type; `None` when the field is not a column of the model
+    pub pg_type: Option<&'static str>,
+}
+
+impl QueryParam {
+    pub fn new(field: &str, value: PostgresValue, pg_type: Option<&'static str>) -> Self {
+        Self {
+            field: field.to_string(),
+            value,
+            pg_type,
+        }
+    }
+
+    /// Type the placeholder is cast to, if any
+    ///
+    /// Decimals are bound as text to keep every digit, so they are cast to
+    /// NUMERIC even without a declared column type.
+    pub fn cast(&self) -> Option<Cow<'static, str>> {
+        match (self.pg_type, &self.value) {
+            (Some(pg_type), _) => Some(cast_type(pg_type)),
+            (None, PostgresValue::Decimal(_)) => Some(Cow::Borrowed("NUMERIC")),
+            (None, _) => None,
+        }
+    }
+
+    /// Placeholder `$n` for this parameter, cast to its type when known
+    pub fn placeholder(&self, number: i32) -> String {
+        match self.cast() {
+            Some(cast) => format!("${}::{}", number, cast),
+            None => format!("${}", number),
+        }
+    }
+}
+
+/// Identifier without its surrounding double quotes
+fn unquote(name: &str) -> &str {
+    name.trim_matches('"')
+}
+
+/// Type usable in a cast for a declared column type
+///
+/// Serial types are not usable in casts, and type modifiers are dropped
+/// because a cast to `VARCHAR(5)` or `NUMERIC(28,10)` would truncate or round
+/// the value before it is compared (`VARCHAR(255)[]` -> `VARCHAR[]`).
+pub fn cast_type(pg_type: &str) -> Cow<'_, str> {
+    match pg_type {
+        "SMALLSERIAL" => Cow::Borrowed("SMALLINT"),
+        "SERIAL" => Cow::Borrowed("INTEGER"),
+        "BIGSERIAL" => Cow::Borrowed("BIGINT"),
+        other => match (other.find('('), other.rfind(')')) {
+            (Some(open), Some(close)) if open < close => Cow::Owned(format!(
+                "{}{}",
+                other[..open].trim_end(),
+                &other[close + 1..]
+            )),
+            _ => Cow::Borrowed(other),
+        },
+    }
+}
+
+/// Element type of an array column type (`TEXT[]` -> `TEXT`)
+pub fn element_type(pg_type: &'static str) -> Option<&'static str> {
+    pg_type.strip_suffix("[]")
+}
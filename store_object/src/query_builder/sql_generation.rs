@@ -6,42 +6,66 @@ use crate::query_builder::aggregation::SelectField;
 use crate::query_builder::filter::{LogicalOperator, QueryCondition, QueryFilter, QueryOperator};
 use crate::query_builder::grouping::GroupBy;
 use crate::query_builder::join::{JoinClause, JoinCondition};
+use crate::query_builder::locking::RowLock;
 use crate::query_builder::ordering::SortOrder;
+use crate::query_builder::params::{element_type, ColumnTypes, QueryParam};
 use serde_json::Value;
+use type_mapping::PostgresValue;
 
 pub struct SqlGenerator;
 
 impl SqlGenerator {
     /// Build WHERE clause from conditions
     pub fn build_where_clause(conditions: &[QueryFilter]) -> (String, Vec<Value>) {
+        let (where_clause, params) = Self::build_typed_where_clause(conditions, None, 1);
+        (where_clause, Self::json_values(params))
+    }
+
+    /// Build WHERE clause with placeholders numbered from `first_param`
+    ///
+    /// With `columns`, each value is paired with the declared type of its
+    /// column and its placeholder is cast to that type.
+    pub fn build_typed_where_clause(
+        conditions: &[QueryFilter],
+        columns: Option<&ColumnTypes>,
+        first_param: i32,
+    ) -> (String, Vec<QueryParam>) {
         if conditions.is_empty() {
             return ("".to_string(), Vec::new());
         }
 
-        let mut values = Vec::new();
-        let mut param_counter = 1;
+        let mut params = Vec::new();
+        let mut param_counter = first_param;
 
         let conditions_sql = conditions
             .iter()
-            .map(|condition| Self::build_condition_sql(condition, &mut values, &mut param_counter))
+            .map(|condition| {
+                Self::build_condition_sql(condition, columns, &mut params, &mut param_counter)
+            })
             .collect::<Vec<_>>()
             .join(" AND ");
 
         if conditions_sql.is_empty() {
-            ("".to_string(), values)
+            ("".to_string(), params)
         } else {
-            (format!("WHERE {}", conditions_sql), values)
+            (format!("WHERE {}", conditions_sql), params)
         }
     }
 
+    /// JSON values of untyped parameters, for callers of the untyped builders
+    pub(crate) fn json_values(params: Vec<QueryParam>) -> Vec<Value> {
+        params.iter().map(|param| param.value.to_json()).collect()
+    }
+
     fn build_condition_sql(
         filter: &QueryFilter,
-        values: &mut Vec<Value>,
+        columns: Option<&ColumnTypes>,
+        params: &mut Vec<QueryParam>,
         param_counter: &mut i32,
     ) -> String {
         match filter {
             QueryFilter::Condition(condition) => {
-                Self::build_single_condition_sql(condition, values, param_counter)
+                Self::build_single_condition_sql(condition, columns, params, param_counter)
             }
             QueryFilter::Group { operator, filters } => {
                 let operator_str = match operator {
@@ -51,7 +75,7 @@ impl SqlGenerator {
 
                 let group_conditions = filters
                     .iter()
-                    .map(|f| Self::build_condition_sql(f, values, param_counter))
+                    .map(|f| Self::build_condition_sql(f, columns, params, param_counter))
                     .collect::<Vec<_>>()
                     .join(operator_str);
 
@@ -60,158 +84,128 @@ impl SqlGenerator {
         }
     }
 
+    /// Record one parameter and return its placeholder
+    fn push_param(
+        field: &str,
+        value: PostgresValue,
+        pg_type: Option<&'static str>,
+        params: &mut Vec<QueryParam>,
+        param_counter: &mut i32,
+    ) -> String {
+        let param = QueryParam::new(field, value, pg_type);
+        let placeholder = param.placeholder(*param_counter);
+        params.push(param);
+        *param_counter += 1;
+        placeholder
+    }
+
+    /// Elements of a list value (IN, NOT IN, array overlap)
+    fn list_values(value: &Option<PostgresValue>) -> Option<Vec<PostgresValue>> {
+        match value {
+            Some(PostgresValue::Array(items)) => Some(items.clone()),
+            Some(PostgresValue::Json(Value::Array(items))) => {
+                Some(items.iter().cloned().map(PostgresValue::Json).collect())
+            }
+            _ => None,
+        }
+    }
+
     fn build_single_condition_sql(
         condition: &QueryCondition,
-        values: &mut Vec<Value>,
+        columns: Option<&ColumnTypes>,
+        params: &mut Vec<QueryParam>,
         param_counter: &mut i32,
     ) -> String {
         let field = &condition.field;
+        let mut pg_type = columns.and_then(|columns| columns.get(field));
+
+        // Values inside a JSON column are extracted as text, with the path bound
+        let target = if condition.json_path.is_empty() {
+            field.clone()
+        } else {
+            let path = condition
+                .json_path
+                .iter()
+                .cloned()
+                .map(PostgresValue::Text)
+                .collect();
+            let placeholder = Self::push_param(
+                field,
+                PostgresValue::Array(path),
+                Some("TEXT[]"),
+                params,
+                param_counter,
+            );
+            pg_type = columns.map(|_| "TEXT");
+            format!("({} #>> {})", field, placeholder)
+        };
+
+        let comparison = |sql_operator: &str,
+                              pg_type: Option<&'static str>,
+                              params: &mut Vec<QueryParam>,
+                              param_counter: &mut i32| {
+            condition.value.as_ref().map(|value| {
+                let placeholder =
+                    Self::push_param(field, value.clone(), pg_type, params, param_counter);
+                format!("{} {} {}", target, sql_operator, placeholder)
+            })
+        };
+        // Patterns are text whatever the column type
+        let pattern_type = columns.map(|_| "TEXT");
 
         match &condition.operator {
-            QueryOperator::Eq => {
-                if let Some(value) = &condition.value {
-                    values.push(value.clone());
-                    let param = format!("${}", param_counter);
-                    *param_counter += 1;
-                    format!("{} = {}", field, param)
-                } else {
-                    format!("{} IS NULL", field)
-                }
-            }
-            QueryOperator::Ne => {
-                if let Some(value) = &condition.value {
-                    values.push(value.clone());
-                    let param = format!("${}", param_counter);
-                    *param_counter += 1;
-                    format!("{} != {}", field, param)
-                } else {
-                    format!("{} IS NOT NULL", field)
-                }
-            }
-            QueryOperator::Gt => {
-                if let Some(value) = &condition.value {
-                    values.push(value.clone());
-                    let param = format!("${}", param_counter);
-                    *param_counter += 1;
-                    format!("{} > {}", field, param)
-                } else {
-                    "1=0".to_string() // Invalid condition
-                }
-            }
-            QueryOperator::Gte => {
-                if let Some(value) = &condition.value {
-                    values.push(value.clone());
-                    let param = format!("${}", param_counter);
-                    *param_counter += 1;
-                    format!("{} >= {}", field, param)
-                } else {
-                    "1=0".to_string()
-                }
-            }
-            QueryOperator::Lt => {
-                if let Some(value) = &condition.value {
-                    values.push(value.clone());
-                    let param = format!("${}", param_counter);
-                    *param_counter += 1;
-                    format!("{} < {}", field, param)
-                } else {
-                    "1=0".to_string()
-                }
-            }
-            QueryOperator::Lte => {
-                if let Some(value) = &condition.value {
-                    values.push(value.clone());
-                    let param = format!("${}", param_counter);
-                    *param_counter += 1;
-                    format!("{} <= {}", field, param)
-                } else {
-                    "1=0".to_string()
-                }
-            }
-            QueryOperator::Like => {
-                if let Some(value) = &condition.value {
-                    values.push(value.clone());
-                    let param = format!("${}", param_counter);
-                    *param_counter += 1;
-                    format!("{} LIKE {}", field, param)
-                } else {
-                    "1=0".to_string()
-                }
-            }
-            QueryOperator::ILike => {
-                if let Some(value) = &condition.value {
-                    values.push(value.clone());
-                    let param = format!("${}", param_counter);
-                    *param_counter += 1;
-                    format!("{} ILIKE {}", field, param)
-                } else {
-                    "1=0".to_string()
-                }
-            }
-            QueryOperator::In => {
-                if let Some(Value::Array(array_values)) = &condition.value {
-                    if array_values.is_empty() {
-                        return "1=0".to_string(); // Empty IN clause
+            QueryOperator::Eq => comparison("=", pg_type, params, param_counter)
+                .unwrap_or_else(|| format!("{} IS NULL", target)),
+            QueryOperator::Ne => comparison("!=", pg_type, params, param_counter)
+                .unwrap_or_else(|| format!("{} IS NOT NULL", target)),
+            // A missing value makes the remaining comparisons an invalid condition
+            QueryOperator::Gt => comparison(">", pg_type, params, param_counter)
+                .unwrap_or_else(|| "1=0".to_string()),
+            QueryOperator::Gte => comparison(">=", pg_type, params, param_counter)
+                .unwrap_or_else(|| "1=0".to_string()),
+            QueryOperator::Lt => comparison("<", pg_type, params, param_counter)
+                .unwrap_or_else(|| "1=0".to_string()),
+            QueryOperator::Lte => comparison("<=", pg_type, params, param_counter)
+                .unwrap_or_else(|| "1=0".to_string()),
+            QueryOperator::Like => comparison("LIKE", pattern_type, params, param_counter)
+                .unwrap_or_else(|| "1=0".to_string()),
+            QueryOperator::ILike => comparison("ILIKE", pattern_type, params, param_counter)
+                .unwrap_or_else(|| "1=0".to_string()),
+            QueryOperator::In | QueryOperator::NotIn => {
+                let negated = condition.operator == QueryOperator::NotIn;
+                match Self::list_values(&condition.value) {
+                    // Empty IN matches nothing, empty NOT IN matches everything
+                    Some(items) if items.is_empty() => {
+                        if negated { "1=1" } else { "1=0" }.to_string()
                     }
-
-                    let placeholders: Vec<String> = array_values
-                        .iter()
-                        .map(|_| {
-                            let param = format!("${}", param_counter);
-                            *param_counter += 1;
-                            param
-                        })
-                        .collect();
-
-                    values.extend(array_values.clone());
-                    format!("{} IN ({})", field, placeholders.join(", "))
-                } else {
-                    "1=0".to_string()
-                }
-            }
-            QueryOperator::NotIn => {
-                if let Some(Value::Array(array_values)) = &condition.value {
-                    if array_values.is_empty() {
-                        return "1=1".to_string(); // Empty NOT IN clause
+                    Some(items) => {
+                        let placeholders: Vec<String> = items
+                            .into_iter()
+                            .map(|item| {
+                                Self::push_param(field, item, pg_type, params, param_counter)
+                            })
+                            .collect();
+                        let sql_operator = if negated { "NOT IN" } else { "IN" };
+                        format!("{} {} ({})", target, sql_operator, placeholders.join(", "))
                     }
-
-                    let placeholders: Vec<String> = array_values
-                        .iter()
-                        .map(|_| {
-                            let param = format!("${}", param_counter);
-                            *param_counter += 1;
-                            param
-                        })
-                        .collect();
-
-                    values.extend(array_values.clone());
-                    format!("{} NOT IN ({})", field, placeholders.join(", "))
-                } else {
-                    "1=1".to_string()
+                    None => if negated { "1=1" } else { "1=0" }.to_string(),
                 }
             }
-            QueryOperator::IsNull => format!("{} IS NULL", field),
-            QueryOperator::IsNotNull => format!("{} IS NOT NULL", field),
-            QueryOperator::ArrayOverlap => {
-                if let Some(Value::Array(array_values)) = &condition.value {
-                    if array_values.is_empty() {
-                        return "1=0".to_string(); // Empty array overlap
-                    }
-
-                    let placeholders: Vec<String> = array_values
-                        .iter()
-                        .map(|_| {
-                            let param = format!("${}", param_counter);
-                            *param_counter += 1;
-                            param
+            QueryOperator::IsNull => format!("{} IS NULL", target),
+            QueryOperator::IsNotNull => format!("{} IS NOT NULL", target),
+            QueryOperator::ArrayOverlap => match Self::list_values(&condition.value) {
+                Some(items) if !items.is_empty() => {
+                    let element_type = pg_type.and_then(element_type);
+                    let placeholders: Vec<String> = items
+                        .into_iter()
+                        .map(|item| {
+                            Self::push_param(field, item, element_type, params, param_counter)
                         })
                         .collect();
-
-                    values.extend(array_values.clone());
-                    format!("{} && ARRAY[{}]", field, placeholders.join(", "))
-                } else {
-                    "1=0".to_string()
+                    format!("{} && ARRAY[{}]", target, placeholders.join(", "))
                 }
+                // Empty or missing array overlaps nothing
+                _ => "1=0".to_string(),
             },
         }
     }
@@ -245,13 +239,18 @@ impl SqlGenerator {
         clauses.join(" ")
     }
 
+    /// Build row locking clause (FOR UPDATE / FOR SHARE, NOWAIT / SKIP LOCKED)
+    pub fn build_lock_clause(lock: Option<&RowLock>) -> String {
+        lock.map(RowLock::to_sql).unwrap_or_default()
+    }
+
     /// Build SELECT clause from fields
     pub fn build_select_clause(fields: &[SelectField]) -> String {
         if fields.is_empty() {
             return "*".to_string();
         }
 
-        let field_parts: Vec<String> = fields.iter().map(|field| Self::build_select_field(field)).collect();
+        let field_parts: Vec<String> = fields.iter().map(Self::build_select_field).collect();
 
         field_parts.join(", ")
     }
@@ -336,21 +335,45 @@ impl SqlGenerator {
 
     /// Build HAVING clause
     pub fn build_having_clause(group_by: Option<&GroupBy>) -> (String, Vec<Value>) {
+        Self::build_having_clause_from(group_by, 1)
+    }
+
+    /// Build HAVING clause with placeholders numbered from `first_param`
+    /// Used when HAVING values are bound after WHERE values
+    pub fn build_having_clause_from(
+        group_by: Option<&GroupBy>,
+        first_param: i32,
+    ) -> (String, Vec<Value>) {
+        let (having_clause, params) = Self::build_typed_having_clause(group_by, None, first_param);
+        (having_clause, Self::json_values(params))
+    }
+
+    /// Build HAVING clause with typed parameters numbered from `first_param`
+    pub fn build_typed_having_clause(
+        group_by: Option<&GroupBy>,
+        columns: Option<&ColumnTypes>,
+        first_param: i32,
+    ) -> (String, Vec<QueryParam>) {
         match group_by {
             Some(group) if group.has_having() => {
                 if let Some(having_conditions) = &group.having {
-                    let mut values = Vec::new();
-                    let mut param_counter = 1;
+                    let mut params = Vec::new();
+                    let mut param_counter = first_param;
 
                     let conditions_sql = having_conditions
                         .iter()
                         .map(|condition| {
-                            Self::build_condition_sql(condition, &mut values, &mut param_counter)
+                            Self::build_condition_sql(
+                                condition,
+                                columns,
+                                &mut params,
+                                &mut param_counter,
+                            )
                         })
                         .collect::<Vec<_>>()
                         .join(" AND ");
 
-                    (format!("HAVING {}", conditions_sql), values)
+                    (format!("HAVING {}", conditions_sql), params)
                 } else {
                     ("".to_string(), Vec::new())
                 }
@@ -3,9 +3,17 @@
 //! This module provides SQL query construction utilities.
 
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
-    use crate::query_builder::{QueryBuilder, QueryFilter, QueryOperator, SortOrder};
+    use crate::query_builder::{
+        Column, ColumnTypes, CursorCodec, CursorDirection, CursorPosition, GroupBy, JoinClause, JoinType,
+        PagedResult, QueryBuilder, QueryFilter, QueryOperator, QueryParam, SelectField, SortOrder,
+        UpdateSet,
+    };
+    use crate::query_builder::params::cast_type;
+    use crate::validation::ValidationError;
     use serde_json::json;
+    use type_mapping::PostgresValue;
 
     // ========================================
     // QueryFilter Edge Cases
@@ -883,4 +891,451 @@ mod tests {
         assert_eq!(JoinType::Full.to_sql(), "FULL OUTER JOIN");
         assert_eq!(JoinType::Cross.to_sql(), "CROSS JOIN");
     }
+
+    // ========================================
+    // Full SELECT Statement
+    // ========================================
+
+    #[test]
+    fn test_having_placeholders_follow_where_placeholders() {
+        use crate::query_builder::GroupBy;
+
+        let builder = QueryBuilder::new()
+            .filter(QueryFilter::eq("status", json!("completed")))
+            .filter(QueryFilter::gt("total", json!(10)))
+            .group_by(GroupBy::single("user_id").having(QueryFilter::gt("COUNT(*)", json!(3))));
+
+        let (_, _, where_clause, _, having_clause, _, _, where_values, having_values) =
+            builder.build_full();
+
+        assert_eq!(where_clause, "WHERE status = $1 AND total > $2");
+        assert_eq!(having_clause, "HAVING COUNT(*) > $3");
+        assert_eq!(where_values.len(), 2);
+        assert_eq!(having_values, vec![json!(3)]);
+    }
+
+    #[test]
+    fn test_build_select_sql_all_parts() {
+        use crate::query_builder::{GroupBy, JoinClause, JoinType, SelectField};
+
+        let builder = QueryBuilder::new()
+            .select_fields(vec![
+                SelectField::field("users.name"),
+                SelectField::count("orders.id").with_alias("order_count"),
+            ])
+            .join(JoinClause::new_on(JoinType::Left, "orders", "users.id", "orders.user_id"))
+            .filter(QueryFilter::eq("users.country", json!("DE")))
+            .group_by(GroupBy::single("users.name").having(QueryFilter::gte("COUNT(orders.id)", json!(2))))
+            .order_by("order_count", SortOrder::Desc)
+            .limit(5);
+
+        let (sql, values) = builder.build_select_sql("users", None);
+
+        assert_eq!(
+            sql,
+            "SELECT users.name, COUNT(orders.id) AS order_count FROM users \
+             LEFT JOIN orders ON users.id = orders.user_id \
+             WHERE users.country = $1 GROUP BY users.name \
+             HAVING COUNT(orders.id) >= $2 ORDER BY order_count DESC LIMIT 5"
+        );
+        assert_eq!(values, vec![json!("DE"), json!(2)]);
+    }
+
+    #[test]
+    fn test_build_select_sql_extra_condition() {
+        let builder = QueryBuilder::new();
+        let (sql, values) = builder.build_select_sql("users", Some("users.is_active = TRUE"));
+        assert_eq!(sql, "SELECT * FROM users WHERE users.is_active = TRUE");
+        assert!(values.is_empty());
+
+        let builder = QueryBuilder::new().filter(QueryFilter::or(vec![
+            QueryFilter::eq("role", json!("admin")),
+            QueryFilter::eq("role", json!("owner")),
+        ]));
+        let (sql, _) = builder.build_select_sql("users", Some("users.is_active = TRUE"));
+        assert_eq!(
+            sql,
+            "SELECT * FROM users WHERE ((role = $1 OR role = $2)) AND users.is_active = TRUE"
+        );
+    }
+
+    #[test]
+    fn test_lock_clause_generation() {
+        assert_eq!(QueryBuilder::new().build_lock_clause(), "");
+        assert_eq!(QueryBuilder::new().for_update().build_lock_clause(), "FOR UPDATE");
+        assert_eq!(QueryBuilder::new().for_share().build_lock_clause(), "FOR SHARE");
+        assert_eq!(
+            QueryBuilder::new().for_update().skip_locked().build_lock_clause(),
+            "FOR UPDATE SKIP LOCKED"
+        );
+        assert_eq!(
+            QueryBuilder::new().for_share().nowait().build_lock_clause(),
+            "FOR SHARE NOWAIT"
+        );
+
+        // The wait policy survives a later lock strength and implies FOR UPDATE on its own
+        assert_eq!(
+            QueryBuilder::new().skip_locked().for_share().build_lock_clause(),
+            "FOR SHARE SKIP LOCKED"
+        );
+        assert_eq!(QueryBuilder::new().nowait().build_lock_clause(), "FOR UPDATE NOWAIT");
+    }
+
+    #[test]
+    fn test_build_select_sql_with_lock() {
+        let builder = QueryBuilder::new()
+            .filter(QueryFilter::eq("status", json!("pending")))
+            .order_by("created_at", SortOrder::Asc)
+            .limit(10)
+            .for_update()
+            .skip_locked();
+
+        let (sql, values) = builder.build_select_sql("jobs", None);
+        assert_eq!(
+            sql,
+            "SELECT * FROM jobs WHERE status = $1 ORDER BY created_at ASC LIMIT 10 FOR UPDATE SKIP LOCKED"
+        );
+        assert_eq!(values, vec![json!("pending")]);
+    }
+
+    #[test]
+    fn test_page_cursor_direction() {
+        let builder = QueryBuilder::new().order_by("name", SortOrder::Asc);
+        assert!(builder.get_cursor().is_none());
+
+        let builder = builder.after("abc");
+        let cursor = builder.get_cursor().unwrap();
+        assert_eq!(cursor.direction, CursorDirection::After);
+        assert_eq!(cursor.token, "abc");
+
+        // The last cursor wins
+        let builder = builder.before("def");
+        let cursor = builder.get_cursor().unwrap();
+        assert_eq!(cursor.direction, CursorDirection::Before);
+        assert_eq!(cursor.token, "def");
+    }
+
+    #[test]
+    fn test_cursor_codec_roundtrip_and_tampering() {
+        let codec = CursorCodec::new("secret");
+        let position = CursorPosition {
+            table: "users".to_string(),
+            ordering: vec!["name ASC".to_string(), "id ASC".to_string()],
+            values: vec![json!("Ann"), json!("7c5b7a5e-0000-4000-8000-000000000000")],
+        };
+
+        let cursor = codec.encode(&position);
+        assert_eq!(codec.decode(&cursor), Some(position.clone()));
+
+        // Another key, an edited payload or a truncated cursor are rejected
+        assert_eq!(CursorCodec::new("other").decode(&cursor), None);
+        let (payload, signature) = cursor.split_once('.').unwrap();
+        let forged = CursorPosition {
+            values: vec![json!("Zed"), json!("7c5b7a5e-0000-4000-8000-000000000000")],
+            ..position
+        };
+        let forged_payload = codec.encode(&forged);
+        let (forged_payload, _) = forged_payload.split_once('.').unwrap();
+        assert_eq!(codec.decode(&format!("{}.{}", forged_payload, signature)), None);
+        assert_eq!(codec.decode(payload), None);
+        assert_eq!(codec.decode("not a cursor"), None);
+    }
+
+    #[test]
+    fn test_paged_result_metadata() {
+        let page = PagedResult::new(vec![1, 2, 3], 23, 1, 10);
+        assert_eq!(page.total_pages, 3);
+        assert!(page.has_next_page());
+        assert!(!page.has_prev_page());
+
+        let last = PagedResult::new(vec![21, 22, 23], 23, 3, 10);
+        assert!(!last.has_next_page());
+        assert!(last.has_prev_page());
+
+        let exact = PagedResult::<i32>::new(vec![], 20, 2, 10);
+        assert_eq!(exact.total_pages, 2);
+
+        let empty = PagedResult::<i32>::new(vec![], 0, 1, 10);
+        assert_eq!(empty.total_pages, 0);
+        assert!(!empty.has_next_page());
+    }
+
+    // ========================================
+    // Typed Parameters
+    // ========================================
+
+    fn user_columns() -> ColumnTypes {
+        ColumnTypes::new(
+            "users",
+            [
+                ("id", "UUID"),
+                ("name", "TEXT"),
+                ("age", "INTEGER"),
+                ("balance", "NUMERIC(28,10)"),
+                ("seq", "BIGSERIAL"),
+                ("__tags__", "TEXT[]"),
+                ("metadata", "JSONB"),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_column_types_lookup() {
+        let columns = user_columns();
+        assert_eq!(columns.get("name"), Some("TEXT"));
+        assert_eq!(columns.get("users.name"), Some("TEXT"));
+        assert_eq!(columns.get("orders.name"), None);
+        assert_eq!(columns.get("metadata->>'type'"), None);
+    }
+
+    #[test]
+    fn test_build_typed_casts_placeholders() {
+        let builder = QueryBuilder::new()
+            .filter(QueryFilter::eq("name", json!("2024-01-01T00:00:00Z")))
+            .filter(QueryFilter::gte("users.age", 18))
+            .filter(QueryFilter::in_list("seq", vec![1i64, 2]))
+            .filter(QueryFilter::like("age", "1%"))
+            .filter(QueryFilter::has_any_tag(vec!["vip".to_string()]))
+            .filter(QueryFilter::ne("orders.status", json!("open")));
+
+        let (where_clause, _, _, params) = builder.build_typed(&user_columns(), 1);
+        assert_eq!(
+            where_clause,
+            "WHERE name = $1::TEXT AND users.age >= $2::INTEGER AND seq IN ($3::BIGINT, $4::BIGINT) \
+             AND age LIKE $5::TEXT AND __tags__ && ARRAY[$6::TEXT] AND orders.status != $7"
+        );
+        assert_eq!(
+            params[0],
+            QueryParam::new(
+                "name",
+                PostgresValue::Json(json!("2024-01-01T00:00:00Z")),
+                Some("TEXT")
+            )
+        );
+        assert_eq!(params[1].value, PostgresValue::Integer(18));
+        assert_eq!(params[6].pg_type, None);
+
+        // The untyped builder keeps plain placeholders and JSON values
+        let (where_clause, _, _, values) = builder.build();
+        assert!(where_clause.starts_with("WHERE name = $1 AND users.age >= $2"));
+        assert_eq!(values[1], json!(18));
+    }
+
+    #[test]
+    fn test_build_typed_numbers_from_first_param() {
+        let builder = QueryBuilder::new()
+            .filter(QueryFilter::eq("name", "Ann"))
+            .filter(QueryFilter::eq("balance", PostgresValue::Decimal("0.1".to_string())));
+
+        let (where_clause, _, _, params) = builder.build_typed(&user_columns(), 3);
+        // Type modifiers are dropped so the value is not rounded before comparing
+        assert_eq!(where_clause, "WHERE name = $3::TEXT AND balance = $4::NUMERIC");
+        assert_eq!(params.len(), 2);
+
+        // Decimals are cast to NUMERIC even without a declared type
+        let param = QueryParam::new("total", PostgresValue::Decimal("1.5".to_string()), None);
+        assert_eq!(param.placeholder(1), "$1::NUMERIC");
+    }
+
+    #[test]
+    fn test_cast_types_drop_modifiers() {
+        assert_eq!(cast_type("NUMERIC(28,10)"), "NUMERIC");
+        assert_eq!(cast_type("VARCHAR(255)"), "VARCHAR");
+        assert_eq!(cast_type("VARCHAR(255)[]"), "VARCHAR[]");
+        assert_eq!(cast_type("TIMESTAMP WITH TIME ZONE"), "TIMESTAMP WITH TIME ZONE");
+        assert_eq!(cast_type("BIGSERIAL"), "BIGINT");
+
+        let param = QueryParam::new("code", "abcdef".into(), Some("VARCHAR(5)"));
+        assert_eq!(param.placeholder(1), "$1::VARCHAR");
+    }
+
+    #[test]
+    fn test_update_set_typed_values() {
+        let updates = UpdateSet::new()
+            .set("name", "Ann")
+            .increment("balance", PostgresValue::Decimal("0.25".to_string()));
+
+        let set = &updates.operations["name"];
+        assert_eq!(set.value(), &PostgresValue::Text("Ann".to_string()));
+        assert_eq!(set.to_sql("name", 1), "name = $1");
+
+        let increment = &updates.operations["balance"];
+        assert_eq!(
+            increment.to_sql_with("balance", "$2::NUMERIC(28,10)"),
+            "balance = balance + $2::NUMERIC(28,10)"
+        );
+    }
+
+    #[test]
+    fn test_check_identifiers_quotes_model_columns() {
+        let query = QueryBuilder::new()
+            .filter(QueryFilter::or(vec![
+                QueryFilter::eq("name", "Ann"),
+                QueryFilter::gt("users.age", 30),
+            ]))
+            .order_by("\"age\"", SortOrder::Desc)
+            .update(UpdateSet::new().set("name", "Bob"));
+
+        let checked = query.check_identifiers(&user_columns()).unwrap();
+        let (where_clause, order_clause, _, params) = checked.build_typed(&user_columns(), 1);
+        assert_eq!(
+            where_clause,
+            "WHERE (\"name\" = $1::TEXT OR \"users\".\"age\" > $2::INTEGER)"
+        );
+        assert_eq!(order_clause, "ORDER BY \"age\" DESC");
+        assert_eq!(params[1].pg_type, Some("INTEGER"));
+        assert!(checked.get_updates().unwrap().operations.contains_key("\"name\""));
+
+        // Checking an already checked query changes nothing
+        let again = checked.check_identifiers(&user_columns()).unwrap();
+        assert_eq!(again.build_typed(&user_columns(), 1), checked.build_typed(&user_columns(), 1));
+    }
+
+    #[test]
+    fn test_check_identifiers_rejects_unknown_and_injected_names() {
+        let queries = [
+            QueryBuilder::new().filter(QueryFilter::eq("email", "a@b.c")),
+            QueryBuilder::new().filter(QueryFilter::eq("orders.total", 5)),
+            QueryBuilder::new().order_by("name; DROP TABLE users", SortOrder::Asc),
+            QueryBuilder::new().order_by("name DESC, (SELECT 1)", SortOrder::Asc),
+            QueryBuilder::new().update(UpdateSet::new().set("users.name", "x")),
+            QueryBuilder::new().group_by(GroupBy::single("name").having(QueryFilter::gt(
+                "pg_sleep(10)",
+                0,
+            ))),
+        ];
+        for query in queries {
+            let invalid = query.check_identifiers(&user_columns()).unwrap_err();
+            assert!(
+                matches!(
+                    invalid.error,
+                    ValidationError::UnknownColumn(_) | ValidationError::InvalidCharacters(_)
+                ),
+                "{:?}",
+                invalid
+            );
+        }
+
+        let invalid = QueryBuilder::new()
+            .filter(QueryFilter::eq("email", "a@b.c"))
+            .check_identifiers(&user_columns())
+            .unwrap_err();
+        assert_eq!(invalid.field, "email");
+        assert_eq!(invalid.error, ValidationError::UnknownColumn("email".to_string()));
+    }
+
+    #[test]
+    fn test_check_identifiers_requires_column_metadata() {
+        let empty = ColumnTypes::new("users", []);
+
+        let invalid = QueryBuilder::new()
+            .filter(QueryFilter::eq("name", "Ann"))
+            .check_identifiers(&empty)
+            .unwrap_err();
+        assert_eq!(invalid.error, ValidationError::NoColumnMetadata("name".to_string()));
+
+        // Queries that name no columns need no metadata
+        assert!(QueryBuilder::new().limit(5).check_identifiers(&empty).is_ok());
+    }
+
+    #[test]
+    fn test_json_path_filters_bind_keys_and_check_the_column() {
+        let query = QueryBuilder::new().filter(QueryFilter::json_path(
+            "metadata",
+            &["plan", "type"],
+            QueryOperator::Eq,
+            Some(json!("premium")),
+        ));
+
+        let checked = query.check_identifiers(&user_columns()).unwrap();
+        let (where_clause, _, _, params) = checked.build_typed(&user_columns(), 1);
+        assert_eq!(
+            where_clause,
+            "WHERE (\"metadata\" #>> $1::TEXT[]) = $2::TEXT"
+        );
+        assert_eq!(
+            params[0].value,
+            PostgresValue::Array(vec![
+                PostgresValue::Text("plan".to_string()),
+                PostgresValue::Text("type".to_string()),
+            ])
+        );
+        assert_eq!(params[1].value, PostgresValue::Text("premium".to_string()));
+
+        // Keys never reach the SQL text, so they may contain anything
+        let (where_clause, _, _, params) = QueryBuilder::new()
+            .filter(QueryFilter::json_path(
+                "metadata",
+                &["it's; DROP TABLE users"],
+                QueryOperator::In,
+                Some(json!([1, "two"])),
+            ))
+            .check_identifiers(&user_columns())
+            .unwrap()
+            .build_typed(&user_columns(), 1);
+        assert_eq!(
+            where_clause,
+            "WHERE (\"metadata\" #>> $1::TEXT[]) IN ($2::TEXT, $3::TEXT)"
+        );
+        assert_eq!(params[2].value, PostgresValue::Text("two".to_string()));
+
+        // The column itself is checked like any other
+        let invalid = QueryBuilder::new()
+            .filter(QueryFilter::json_path(
+                "metadata->>'type'",
+                &[],
+                QueryOperator::IsNotNull,
+                None,
+            ))
+            .check_identifiers(&user_columns())
+            .unwrap_err();
+        assert_eq!(invalid.field, "metadata->>'type'");
+    }
+
+    #[test]
+    fn test_check_identifiers_allows_joins_aliases_and_aggregates() {
+        let query = QueryBuilder::new()
+            .select_fields(vec![
+                SelectField::field("users.name"),
+                SelectField::field_as("o.total", "order_total"),
+                SelectField::sum("o.total").with_alias("spent"),
+            ])
+            .join(JoinClause::new_on(JoinType::Left, "orders", "users.id", "o.user_id").with_alias("o"))
+            .group_by(
+                GroupBy::new(vec!["users.name".to_string(), "o.total".to_string()])
+                    .having(QueryFilter::gt("COUNT(DISTINCT o.id)", 1)),
+            )
+            .order_by("spent", SortOrder::Desc);
+
+        let checked = query.check_identifiers(&user_columns()).unwrap();
+        let (sql, _) = checked.build_typed_select_sql("users", None, &user_columns());
+        assert!(sql.starts_with(
+            "SELECT \"users\".\"name\", \"o\".\"total\" AS \"order_total\", SUM(\"o\".\"total\") AS \"spent\" FROM users"
+        ), "{}", sql);
+        assert!(sql.contains("LEFT JOIN \"orders\" AS \"o\" ON \"users\".\"id\" = \"o\".\"user_id\""), "{}", sql);
+        assert!(sql.contains("HAVING COUNT(DISTINCT \"o\".\"id\") > $1"), "{}", sql);
+        assert!(sql.ends_with("ORDER BY \"spent\" DESC"), "{}", sql);
+
+        // Columns of tables the query does not join are unknown
+        let unjoined = QueryBuilder::new().select(SelectField::field("payments.amount"));
+        assert!(unjoined.check_identifiers(&user_columns()).is_err());
+    }
+
+    #[test]
+    fn test_typed_columns_build_filters() {
+        let age: Column<(), i32> = Column::new("age");
+        let nickname: Column<(), Option<String>> = Column::new("nickname");
+
+        assert_eq!(age.gte(18), QueryFilter::gte("age", 18));
+        assert_eq!(age.in_([1i16, 2]), QueryFilter::in_list("age", vec![1, 2]));
+        assert_eq!(nickname.eq("ann"), QueryFilter::eq("nickname", "ann"));
+        assert_eq!(nickname.ilike("a%"), QueryFilter::ilike("nickname", "a%"));
+        assert_eq!(nickname.is_null(), QueryFilter::is_null("nickname"));
+
+        let (where_clause, _, _, params) = QueryBuilder::new()
+            .filter(nickname.not_in(["bob"]))
+            .build_typed(&ColumnTypes::new("users", [("nickname", "TEXT")]), 1);
+        assert_eq!(where_clause, "WHERE nickname NOT IN ($1::TEXT)");
+        assert_eq!(params[0].value, PostgresValue::Text("bob".to_string()));
+    }
 }
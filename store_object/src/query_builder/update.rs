@@ -1,23 +1,23 @@
-use serde_json::Value;
 use std::collections::HashMap;
+use type_mapping::PostgresValue;
 
 /// Type of update operation to perform on a field
 #[derive(Debug, Clone)]
 pub enum UpdateOperation {
     /// Set field to a specific value: field = $N
-    Set(Value),
+    Set(PostgresValue),
 
     /// Increment field by a value: field = field + $N
-    Increment(Value),
+    Increment(PostgresValue),
 
     /// Decrement field by a value: field = field - $N
-    Decrement(Value),
+    Decrement(PostgresValue),
 
     /// Multiply field by a value: field = field * $N
-    Multiply(Value),
+    Multiply(PostgresValue),
 
     /// Divide field by a value: field = field / $N
-    Divide(Value),
+    Divide(PostgresValue),
 }
 
 impl UpdateOperation {
@@ -25,27 +25,33 @@ impl UpdateOperation {
     /// Returns (sql_expression, needs_field_reference)
     /// Example: ("field = field + $1", true) or ("field = $1", false)
     pub fn to_sql(&self, field_name: &str, param_number: usize) -> String {
+        self.to_sql_with(field_name, &format!("${}", param_number))
+    }
+
+    /// Generate the SQL expression for this operation with a given placeholder
+    /// Example: ("balance", "$1::BIGINT") -> "balance = balance + $1::BIGINT"
+    pub fn to_sql_with(&self, field_name: &str, placeholder: &str) -> String {
         match self {
             UpdateOperation::Set(_) => {
-                format!("{} = ${}", field_name, param_number)
+                format!("{} = {}", field_name, placeholder)
             }
             UpdateOperation::Increment(_) => {
-                format!("{} = {} + ${}", field_name, field_name, param_number)
+                format!("{} = {} + {}", field_name, field_name, placeholder)
             }
             UpdateOperation::Decrement(_) => {
-                format!("{} = {} - ${}", field_name, field_name, param_number)
+                format!("{} = {} - {}", field_name, field_name, placeholder)
             }
             UpdateOperation::Multiply(_) => {
-                format!("{} = {} * ${}", field_name, field_name, param_number)
+                format!("{} = {} * {}", field_name, field_name, placeholder)
             }
             UpdateOperation::Divide(_) => {
-                format!("{} = {} / ${}", field_name, field_name, param_number)
+                format!("{} = {} / {}", field_name, field_name, placeholder)
             }
         }
     }
 
     /// Get the value to bind as a parameter
-    pub fn value(&self) -> &Value {
+    pub fn value(&self) -> &PostgresValue {
         match self {
             UpdateOperation::Set(v)
             | UpdateOperation::Increment(v)
@@ -70,37 +76,37 @@ impl UpdateSet {
     }
 
     /// Set a field to a specific value
-    pub fn set(mut self, field: impl Into<String>, value: Value) -> Self {
+    pub fn set(mut self, field: impl Into<String>, value: impl Into<PostgresValue>) -> Self {
         self.operations
-            .insert(field.into(), UpdateOperation::Set(value));
+            .insert(field.into(), UpdateOperation::Set(value.into()));
         self
     }
 
     /// Increment a field by a value (atomic: field = field + value)
-    pub fn increment(mut self, field: impl Into<String>, value: Value) -> Self {
+    pub fn increment(mut self, field: impl Into<String>, value: impl Into<PostgresValue>) -> Self {
         self.operations
-            .insert(field.into(), UpdateOperation::Increment(value));
+            .insert(field.into(), UpdateOperation::Increment(value.into()));
         self
     }
 
     /// Decrement a field by a value (atomic: field = field - value)
-    pub fn decrement(mut self, field: impl Into<String>, value: Value) -> Self {
+    pub fn decrement(mut self, field: impl Into<String>, value: impl Into<PostgresValue>) -> Self {
         self.operations
-            .insert(field.into(), UpdateOperation::Decrement(value));
+            .insert(field.into(), UpdateOperation::Decrement(value.into()));
         self
     }
 
     /// Multiply a field by a value (atomic: field = field * value)
-    pub fn multiply(mut self, field: impl Into<String>, value: Value) -> Self {
+    pub fn multiply(mut self, field: impl Into<String>, value: impl Into<PostgresValue>) -> Self {
         self.operations
-            .insert(field.into(), UpdateOperation::Multiply(value));
+            .insert(field.into(), UpdateOperation::Multiply(value.into()));
         self
     }
 
     /// Divide a field by a value (atomic: field = field / value)
-    pub fn divide(mut self, field: impl Into<String>, value: Value) -> Self {
+    pub fn divide(mut self, field: impl Into<String>, value: impl Into<PostgresValue>) -> Self {
         self.operations
-            .insert(field.into(), UpdateOperation::Divide(value));
+            .insert(field.into(), UpdateOperation::Divide(value.into()));
         self
     }
 
@@ -23,6 +23,13 @@ pub trait StoreObject: Clone + Send + Sync + Debug {
         tags: Option<Vec<String>>,
     ) -> Result<Self::Model, StorehausError>;
 
+    /// Create multiple objects in one transaction
+    ///
+    /// Rows are inserted with multi-row INSERT statements chunked under the
+    /// bind parameter limit, and one batched Create signal is emitted.
+    async fn create_many(&self, data: Vec<Self::Model>)
+        -> Result<Vec<Self::Model>, StorehausError>;
+
     /// Get an object by its ID
     async fn get_by_id(&self, id: &Self::Id) -> Result<Option<Self::Model>, StorehausError>;
 
@@ -37,6 +44,25 @@ pub trait StoreObject: Clone + Send + Sync + Debug {
         tags: Option<Vec<String>>,
     ) -> Result<Self::Model, StorehausError>;
 
+    /// Insert an object, or update the row it conflicts with
+    ///
+    /// `conflict_target` names the primary key or a `#[unique]` field/composite;
+    /// on conflict only `update_fields()` are overwritten, or nothing with
+    /// `OnConflict::do_nothing()`. Signals report a create or an update.
+    async fn upsert(
+        &self,
+        data: Self::Model,
+        conflict_target: crate::generic_store::OnConflict,
+        tags: Option<Vec<String>>,
+    ) -> Result<crate::generic_store::UpsertOutcome<Self::Model>, StorehausError>;
+
+    /// Upsert multiple objects in one transaction
+    async fn upsert_many(
+        &self,
+        data: Vec<Self::Model>,
+        conflict_target: crate::generic_store::OnConflict,
+    ) -> Result<Vec<crate::generic_store::UpsertOutcome<Self::Model>>, StorehausError>;
+
     /// Update multiple objects by their IDs
     async fn update_many(
         &self,
@@ -53,6 +79,9 @@ pub trait StoreObject: Clone + Send + Sync + Debug {
     async fn count(&self) -> Result<i64, StorehausError>;
 
     /// Find records matching query conditions
+    ///
+    /// Only WHERE, ORDER BY and LIMIT/OFFSET are applied; use `GenericStore::find_rows`
+    /// for queries with SELECT fields, JOINs or GROUP BY
     async fn find(&self, query: crate::QueryBuilder) -> Result<Vec<Self::Model>, StorehausError>;
 
     /// Find first record matching query conditions
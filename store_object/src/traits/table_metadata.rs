@@ -7,13 +7,28 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
+/// Column definition as declared by a model, used for schema diffing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnDefinition {
+    /// Column name
+    pub name: &'static str,
+    /// PostgreSQL type as used in `CREATE TABLE` (e.g. `TEXT`, `NUMERIC(28,10)`, `SERIAL`)
+    pub pg_type: &'static str,
+    /// Whether the column accepts NULL values
+    pub nullable: bool,
+    /// Default expression, if any (e.g. `NOW()`)
+    pub default: Option<&'static str>,
+    /// Whether the column is the primary key
+    pub primary_key: bool,
+}
+
 /// Metadata about database table structure and operations
 /// This trait should be derived using the `#[model]` attribute macro, which
 /// automatically includes all necessary derives.
 ///
 /// Recommended usage:
 /// ```
-/// use table_derive::model;
+/// use storehaus::prelude::*;
 ///
 /// #[model]
 /// #[table(name = "customers")]
@@ -24,22 +39,34 @@ use std::fmt::Debug;
 ///     #[field(create, update)]
 ///     pub first_name: String,
 ///
-///     #[field(readonly)]
-///     pub __created_at__: DateTime<Utc>,
-///
 ///     #[soft_delete]
+///     #[field(create, update)]
 ///     pub is_enabled: bool,
 /// }
 /// ```
 ///
-/// Manual usage (not recommended):
+/// Manual usage (not recommended), declaring the system fields `#[model]` adds:
 /// ```
-/// use table_derive::TableMetadata;
+/// # use chrono::{DateTime, Utc};
+/// use storehaus::prelude::*;
 ///
-/// #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow, sqlx::Type, TableMetadata)]
+/// #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow, TableMetadata)]
 /// #[table(name = "customers")]
 /// pub struct Customer {
-///     // fields...
+///     #[primary_key]
+///     pub id: Uuid,
+///
+///     #[field(create, update)]
+///     pub first_name: String,
+///
+///     #[readonly]
+///     pub __created_at__: DateTime<Utc>,
+///
+///     #[readonly]
+///     pub __updated_at__: DateTime<Utc>,
+///
+///     #[field(update)]
+///     pub __tags__: Option<Vec<String>>,
 /// }
 /// ```
 pub trait TableMetadata:
@@ -54,7 +81,8 @@ pub trait TableMetadata:
         + HasUniversalId
         + for<'q> sqlx::Encode<'q, sqlx::Postgres>
         + for<'r> sqlx::Decode<'r, sqlx::Postgres>
-        + sqlx::Type<sqlx::Postgres>;
+        + sqlx::Type<sqlx::Postgres>
+        + sqlx::postgres::PgHasArrayType;
 
     /// The table name in the database
     fn table_name() -> &'static str;
@@ -93,6 +121,19 @@ pub trait TableMetadata:
         None
     }
 
+    /// Get the name of the optimistic concurrency version field if it exists
+    ///
+    /// Updates of versioned records only match the version they were read
+    /// with and increment it.
+    fn version_field() -> Option<&'static str> {
+        None
+    }
+
+    /// Get the current value of the `#[version]` field, if any
+    fn version(&self) -> Option<i64> {
+        None
+    }
+
     /// Extract ID from model instance
     fn extract_id(&self) -> Self::Id;
 
@@ -102,6 +143,14 @@ pub trait TableMetadata:
     /// Get field names for UPDATE operation
     fn update_fields() -> Vec<&'static str>;
 
+    /// Get field names bound by upsert: the primary key (unless generated) and the CREATE fields
+    fn upsert_fields() -> Vec<&'static str>;
+
+    /// Get the column sets of `#[unique]` fields and `#[unique(...)]` composites
+    fn unique_keys() -> Vec<Vec<&'static str>> {
+        vec![]
+    }
+
     /// Get the primary key field name
     fn primary_key_field() -> &'static str;
 
@@ -113,6 +162,18 @@ pub trait TableMetadata:
         format!("DROP TABLE IF EXISTS {}", Self::table_name())
     }
 
+    /// Generate SQL checking whether a row with primary key `$1` exists
+    ///
+    /// Used to tell a missing record from a version conflict when a
+    /// versioned update matches no row.
+    fn exists_by_id_sql() -> String {
+        format!(
+            "SELECT EXISTS (SELECT 1 FROM \"{}\" WHERE \"{}\" = $1)",
+            Self::table_name(),
+            Self::primary_key_field()
+        )
+    }
+
     /// Generate CREATE INDEX SQL statements
     fn create_indexes_sql() -> Vec<String> {
         vec![]
@@ -123,6 +184,12 @@ pub trait TableMetadata:
         vec![]
     }
 
+    /// Get full column definitions matching `create_table_sql()`
+    /// Used by schema diffing migrations; an empty list disables diffing
+    fn get_table_columns() -> Vec<ColumnDefinition> {
+        vec![]
+    }
+
     /// Generate UPDATE WHERE SQL statement (for bulk updates)
     fn update_where_sql() -> &'static str {
         "UPDATE table_placeholder SET field_placeholder WHERE condition_placeholder"
@@ -148,6 +215,30 @@ pub trait TableMetadata:
         &'a self,
         sql: &'a str,
     ) -> sqlx::query::Query<'a, sqlx::Postgres, sqlx::postgres::PgArguments>;
+
+    /// Bind parameters for upsert operations (`upsert_fields()`) to a raw query
+    /// Returns a new query with bound parameters
+    fn bind_upsert_params<'a>(
+        &'a self,
+        sql: &'a str,
+    ) -> sqlx::query::Query<'a, sqlx::Postgres, sqlx::postgres::PgArguments>;
+
+    /// Bind parameters for CREATE operations (`create_fields()`) to an existing query
+    /// Used to bind one row after another of a multi-row INSERT
+    fn bind_create_params<'a>(
+        &'a self,
+        query: sqlx::query::Query<'a, sqlx::Postgres, sqlx::postgres::PgArguments>,
+    ) -> sqlx::query::Query<'a, sqlx::Postgres, sqlx::postgres::PgArguments>;
+
+    /// Bind parameters for UPDATE operations (`update_fields()`) to an existing query
+    /// Used to bind one row after another of a set-based UPDATE
+    fn bind_update_params<'a>(
+        &'a self,
+        query: sqlx::query::Query<'a, sqlx::Postgres, sqlx::postgres::PgArguments>,
+    ) -> sqlx::query::Query<'a, sqlx::Postgres, sqlx::postgres::PgArguments>;
+
+    /// Append the CREATE field values (`create_fields()`) as one binary COPY tuple
+    fn encode_copy_row(&self, buf: &mut Vec<u8>) -> Result<(), sqlx::Error>;
 }
 
 /// Async trait for database operations that properly handles async/await
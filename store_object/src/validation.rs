@@ -21,6 +21,10 @@ pub enum ValidationError {
     InvalidStartCharacter(String),
     /// Name is a reserved SQL keyword
     ReservedKeyword(String),
+    /// Name does not refer to a known column, table or alias
+    UnknownColumn(String),
+    /// Name cannot be checked because the model declares no columns
+    NoColumnMetadata(String),
 }
 
 impl fmt::Display for ValidationError {
@@ -49,6 +53,16 @@ impl fmt::Display for ValidationError {
             ValidationError::ReservedKeyword(name) => {
                 write!(f, "Name '{}' is a reserved SQL keyword", name)
             }
+            ValidationError::UnknownColumn(name) => {
+                write!(f, "Unknown column '{}'", name)
+            }
+            ValidationError::NoColumnMetadata(name) => {
+                write!(
+                    f,
+                    "Cannot check column '{}': the model declares no columns",
+                    name
+                )
+            }
         }
     }
 }
@@ -81,6 +95,18 @@ impl ValidatedTableName {
 
     /// Common validation logic for SQL identifiers
     fn validate_identifier(name: &str) -> Result<(), ValidationError> {
+        Self::validate_characters(name)?;
+
+        // Check for reserved keywords
+        if Self::is_reserved_keyword(name) {
+            return Err(ValidationError::ReservedKeyword(name.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Length and character rules shared by every identifier
+    fn validate_characters(name: &str) -> Result<(), ValidationError> {
         // Check if empty
         if name.is_empty() {
             return Err(ValidationError::Empty);
@@ -106,11 +132,6 @@ impl ValidatedTableName {
             return Err(ValidationError::InvalidCharacters(name.to_string()));
         }
 
-        // Check for reserved keywords
-        if Self::is_reserved_keyword(name) {
-            return Err(ValidationError::ReservedKeyword(name.to_string()));
-        }
-
         Ok(())
     }
 
@@ -267,6 +288,20 @@ impl ValidatedFieldName {
         Ok(Self(name.to_string()))
     }
 
+    /// Validate a name referenced by a query rather than declared by a model
+    ///
+    /// Applies the length and character rules of `new` but accepts reserved
+    /// words and system fields, since referenced names are always quoted.
+    pub fn referenced(name: &str) -> Result<Self, ValidationError> {
+        ValidatedTableName::validate_characters(name)?;
+        Ok(Self(name.to_string()))
+    }
+
+    /// The name as a quoted SQL identifier
+    pub fn quoted(&self) -> String {
+        format!("\"{}\"", self.0)
+    }
+
     /// Get the validated name as a string slice
     pub fn as_str(&self) -> &str {
         &self.0
@@ -486,6 +521,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_referenced_field_names() {
+        // Quoted references may use reserved words and system fields
+        for name in ["order", "__created_at__", "user_id"] {
+            let field = ValidatedFieldName::referenced(name).unwrap();
+            assert_eq!(field.quoted(), format!("\"{}\"", name));
+        }
+
+        assert_eq!(
+            ValidatedFieldName::referenced("name; DROP TABLE users"),
+            Err(ValidationError::InvalidCharacters(
+                "name; DROP TABLE users".to_string()
+            ))
+        );
+        assert!(ValidatedFieldName::referenced("a\"b").is_err());
+        assert!(ValidatedFieldName::referenced("").is_err());
+    }
+
     #[test]
     fn test_display_traits() {
         let table_name = ValidatedTableName::new("users").unwrap();
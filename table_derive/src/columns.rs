@@ -0,0 +1,53 @@
+//! Generation of typed column handles
+//!
+//! For a model `User` this generates a `UserColumns` struct with one
+//! `store_object::query_builder::Column<User, FieldType>` per field, returned
+//! by `User::columns()`.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, Fields, Ident, Visibility};
+
+/// Generate the `<Model>Columns` struct and the `columns()` constructor
+pub fn generate_columns_impl(name: &Ident, vis: &Visibility, data: &Data) -> TokenStream {
+    let fields = match data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return TokenStream::new(),
+        },
+        _ => return TokenStream::new(),
+    };
+
+    let columns_name = format_ident!("{}Columns", name);
+    let struct_doc = format!(
+        "Typed columns of [`{}`], returned by `{}::columns()`",
+        name, name
+    );
+
+    let column_fields = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let ty = &field.ty;
+        quote! { pub #ident: store_object::query_builder::Column<#name, #ty> }
+    });
+    let column_values = fields.iter().map(|field| {
+        let ident = &field.ident;
+        quote! { #ident: store_object::query_builder::Column::new(stringify!(#ident)) }
+    });
+
+    quote! {
+        #[doc = #struct_doc]
+        #[derive(Debug, Clone, Copy)]
+        #vis struct #columns_name {
+            #(#column_fields),*
+        }
+
+        impl #name {
+            /// Typed column handles for building filters, e.g. `columns().id.eq(id)`
+            pub const fn columns() -> #columns_name {
+                #columns_name {
+                    #(#column_values),*
+                }
+            }
+        }
+    }
+}
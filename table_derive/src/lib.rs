@@ -6,10 +6,12 @@
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
 
+mod columns;
 mod model_macro;
 mod parsing;
 mod sql_generation;
 
+use columns::generate_columns_impl;
 use model_macro::model_attribute;
 use parsing::{parse_field_attributes, parse_table_attributes};
 use sql_generation::{
@@ -23,7 +25,9 @@ use sql_generation::{
 ///
 /// Manual usage (not recommended):
 /// ```rust
-/// #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow, sqlx::Type, TableMetadata)]
+/// # use storehaus::prelude::*;
+/// # use chrono::{DateTime, Utc};
+/// #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow, TableMetadata)]
 /// #[table(name = "customers")]
 /// pub struct Customer {
 ///     #[primary_key]
@@ -32,37 +36,48 @@ use sql_generation::{
 ///     #[field(create, update)]
 ///     pub first_name: String,
 ///
-///     #[field(readonly)]
+///     #[readonly]
 ///     pub __created_at__: DateTime<Utc>,
 ///
+///     #[readonly]
+///     pub __updated_at__: DateTime<Utc>,
+///
+///     #[field(update)]
+///     pub __tags__: Option<Vec<String>>,
+///
 ///     #[soft_delete]
 ///     pub __is_active__: bool,
 /// }
 /// ```
 ///
-/// Recommended usage:
+/// Recommended usage, with the system fields added by the macro:
 /// ```rust
+/// # use storehaus::prelude::*;
 /// use table_derive::model;
 ///
 /// #[model]
-/// #[table(name = "customers")]
+/// #[table(name = "customers", auto_soft_delete)]
 /// pub struct Customer {
 ///     #[primary_key]
 ///     pub id: Uuid,
 ///
 ///     #[field(create, update)]
 ///     pub first_name: String,
-///
-///     #[field(readonly)]
-///     pub __created_at__: DateTime<Utc>,
-///
-///     #[soft_delete]
-///     pub __is_active__: bool,
 /// }
 /// ```
 #[proc_macro_derive(
     TableMetadata,
-    attributes(table, primary_key, field, soft_delete, auto_increment, readonly, index, unique)
+    attributes(
+        table,
+        primary_key,
+        field,
+        soft_delete,
+        version,
+        auto_increment,
+        readonly,
+        index,
+        unique
+    )
 )]
 pub fn derive_table_metadata(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -90,10 +105,14 @@ pub fn derive_table_metadata(input: TokenStream) -> TokenStream {
     // Generate the DatabaseExecutor implementation
     let database_executor_impl = generate_database_executor_impl(name, &field_info);
 
+    // Generate the typed column handles
+    let columns_impl = generate_columns_impl(name, &input.vis, &input.data);
+
     let expanded = quote::quote! {
         #table_metadata_impl
         #helper_impl
         #database_executor_impl
+        #columns_impl
     };
 
     TokenStream::from(expanded)
@@ -103,6 +122,7 @@ pub fn derive_table_metadata(input: TokenStream) -> TokenStream {
 ///
 /// Usage:
 /// ```rust
+/// # use storehaus::prelude::*;
 /// use table_derive::model;
 ///
 /// #[model]
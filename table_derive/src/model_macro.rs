@@ -3,7 +3,7 @@
 //! This module provides the `#[model]` macro that automatically adds
 //! system fields and derives to database model structs.
 
-use crate::parsing::parse_table_attributes;
+use crate::parsing::{has_attribute, parse_table_attributes};
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, Data, DeriveInput, Fields};
@@ -16,6 +16,7 @@ use syn::{parse_macro_input, Data, DeriveInput, Fields};
 ///
 /// Usage:
 /// ```rust
+/// # use storehaus::prelude::*;
 /// use table_derive::model;
 ///
 /// #[model]
@@ -33,11 +34,23 @@ use syn::{parse_macro_input, Data, DeriveInput, Fields};
 /// // With soft delete support:
 /// #[model]
 /// #[table(name = "users", auto_soft_delete)]
-/// pub struct User {
+/// pub struct SoftDeletedUser {
+///     #[primary_key]
+///     pub id: Uuid,
+///     #[field(create, update)]
+///     pub name: String,
+/// }
+///
+/// // With optimistic concurrency (`version` is not a `new()` parameter and starts at 1):
+/// #[model]
+/// #[table(name = "users")]
+/// pub struct VersionedUser {
 ///     #[primary_key]
 ///     pub id: Uuid,
 ///     #[field(create, update)]
 ///     pub name: String,
+///     #[version]
+///     pub version: i64,
 /// }
 /// ```
 pub fn model_attribute(_attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -94,10 +107,14 @@ pub fn model_attribute(_attr: TokenStream, item: TokenStream) -> TokenStream {
     // Combine original fields with system fields
     let fields_vec: Vec<_> = fields.named.iter().collect();
 
+    // Version fields start at 1 and are maintained by the database
+    let is_version = |field: &&syn::Field| has_attribute(&field.attrs, "version");
+
     // Generate new() method parameters - only user-defined fields
     let new_params: Vec<_> = fields
         .named
         .iter()
+        .filter(|field| !is_version(field))
         .map(|field| {
             let name = &field.ident;
             let ty = &field.ty;
@@ -111,7 +128,11 @@ pub fn model_attribute(_attr: TokenStream, item: TokenStream) -> TokenStream {
         .iter()
         .map(|field| {
             let name = &field.ident;
-            quote! { #name }
+            if is_version(&field) {
+                quote! { #name: 1 }
+            } else {
+                quote! { #name }
+            }
         })
         .collect();
 
@@ -130,7 +151,11 @@ pub fn model_attribute(_attr: TokenStream, item: TokenStream) -> TokenStream {
         .iter()
         .map(|field| {
             let name = &field.ident;
-            quote! { #name: Default::default() }
+            if is_version(&field) {
+                quote! { #name: 1 }
+            } else {
+                quote! { #name: Default::default() }
+            }
         })
         .collect();
 
@@ -155,6 +180,7 @@ pub fn model_attribute(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
         impl #generics #name #generics {
             /// Create a new instance with automatic system field initialization
+            #[allow(clippy::too_many_arguments)]
             pub fn new(#(#new_params),*) -> Self {
                 Self {
                     #(#user_field_assignments),*,
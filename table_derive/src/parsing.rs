@@ -238,9 +238,7 @@ pub struct TableInfo {
     pub name: String,
     pub has_auto_increment: bool,
     pub auto_soft_delete: bool,
-    #[allow(dead_code)]
     pub composite_indexes: Vec<Vec<String>>,        // #[index(field1, field2)]
-    #[allow(dead_code)]
     pub composite_unique_indexes: Vec<Vec<String>>, // #[unique(field1, field2)]
 }
 
@@ -251,10 +249,9 @@ pub struct FieldInfo {
     pub create_fields: Vec<String>,
     pub update_fields: Vec<String>,
     pub soft_delete_field: Option<String>,
+    pub version_field: Option<String>,
     pub field_types: HashMap<String, String>, // field_name -> rust_type
-    #[allow(dead_code)]
     pub indexed_fields: Vec<String>,          // fields marked with #[index]
-    #[allow(dead_code)]
     pub unique_fields: Vec<String>,           // fields marked with #[unique]
 }
 
@@ -287,9 +284,8 @@ pub fn parse_table_attributes(attrs: &[Attribute]) -> Result<TableInfo> {
                                 if let Some(proc_macro2::TokenTree::Literal(lit)) = tokens.next() {
                                     let value = lit.to_string().trim_matches('"').to_string();
 
-                                    match key_str.as_str() {
-                                        "name" => name = Some(value),
-                                        _ => {} // ignore unknown keys
+                                    if key_str == "name" {
+                                        name = Some(value);
                                     }
                                 }
                             }
@@ -385,6 +381,7 @@ pub fn parse_field_attributes(data: &Data, table_info: &TableInfo) -> Result<Fie
             let mut create_fields = Vec::new();
             let mut update_fields = Vec::new();
             let mut soft_delete_field = None;
+            let mut version_field = None;
             let mut field_types = HashMap::new();
             let mut indexed_fields = Vec::new();
             let mut unique_fields = Vec::new();
@@ -410,6 +407,24 @@ pub fn parse_field_attributes(data: &Data, table_info: &TableInfo) -> Result<Fie
                 // Store field type
                 field_types.insert(field_name_str.clone(), normalized_type_string.clone());
 
+                // Version fields are set by the database, never bound on create or update
+                if has_attribute(&field.attrs, "version") {
+                    if version_field.is_some() {
+                        return Err(Error::new_spanned(
+                            field,
+                            "only one #[version] field is allowed per table",
+                        ));
+                    }
+                    if !matches!(normalized_type_string.as_str(), "i16" | "i32" | "i64") {
+                        return Err(Error::new_spanned(
+                            ty,
+                            "#[version] field must be of type i16, i32 or i64",
+                        ));
+                    }
+                    version_field = Some(field_name_str);
+                    continue;
+                }
+
                 // Check for primary_key attribute
                 if has_attribute(&field.attrs, "primary_key") {
                     primary_key_field = Some(field_name.clone());
@@ -475,6 +490,7 @@ pub fn parse_field_attributes(data: &Data, table_info: &TableInfo) -> Result<Fie
                 create_fields,
                 update_fields,
                 soft_delete_field,
+                version_field,
                 field_types,
                 indexed_fields,
                 unique_fields,
@@ -64,6 +64,7 @@ pub fn generate_table_metadata_impl(
     let create_fields = &field_info.create_fields;
     let update_fields = &field_info.update_fields;
     let soft_delete_field = &field_info.soft_delete_field;
+    let version_field = &field_info.version_field;
 
     // Parse the primary key type into a TokenStream if present
     let primary_key_type_tokens: Option<TokenStream> = primary_key_type.as_ref().map(|pk_type| {
@@ -97,18 +98,42 @@ pub fn generate_table_metadata_impl(
             .enumerate()
             .map(|(i, field)| format!("{} = ${}", safe_sql_identifier(field), i + 1))
             .collect();
-        format!(
-            "UPDATE {} SET {}, __updated_at__ = NOW() WHERE {} = ${} RETURNING *",
-            safe_sql_identifier(table_name),
-            update_assignments.join(", "),
-            safe_sql_identifier(&pk_field.to_string()),
-            update_fields.len() + 1
-        )
+        match version_field {
+            // Optimistic concurrency: only match the version the record was read with
+            Some(version_field) => format!(
+                "UPDATE {} SET {}, {} = {} + 1, __updated_at__ = NOW() WHERE {} = ${} AND {} = ${} RETURNING *",
+                safe_sql_identifier(table_name),
+                update_assignments.join(", "),
+                safe_sql_identifier(version_field),
+                safe_sql_identifier(version_field),
+                safe_sql_identifier(&pk_field.to_string()),
+                update_fields.len() + 1,
+                safe_sql_identifier(version_field),
+                update_fields.len() + 2
+            ),
+            None => format!(
+                "UPDATE {} SET {}, __updated_at__ = NOW() WHERE {} = ${} RETURNING *",
+                safe_sql_identifier(table_name),
+                update_assignments.join(", "),
+                safe_sql_identifier(&pk_field.to_string()),
+                update_fields.len() + 1
+            ),
+        }
     } else {
         // For tables without primary key, UPDATE is not supported via this method
         String::new()
     };
 
+    // Upsert fields - the primary key is bound unless the database generates it
+    let mut upsert_fields: Vec<String> = Vec::new();
+    if let Some(pk_field) = primary_key_field {
+        let pk_name = pk_field.to_string();
+        if !table_info.has_auto_increment && !create_fields.contains(&pk_name) {
+            upsert_fields.push(pk_name);
+        }
+    }
+    upsert_fields.extend(create_fields.iter().cloned());
+
     // Generate LIST_ALL SQL
     let list_all_sql = if let Some(soft_delete_field_name) = soft_delete_field {
         format!(
@@ -200,6 +225,18 @@ pub fn generate_table_metadata_impl(
         vec![#(#update_fields),*]
     };
 
+    let upsert_fields_vec = quote! {
+        vec![#(#upsert_fields),*]
+    };
+
+    let unique_keys: Vec<_> = field_info
+        .unique_fields
+        .iter()
+        .map(|field| vec![field.clone()])
+        .chain(table_info.composite_unique_indexes.iter().cloned())
+        .map(|fields| quote! { vec![#(#fields),*] })
+        .collect();
+
     let soft_delete_field_option = match soft_delete_field {
         Some(field) => quote! { Some(#field) },
         None => quote! { None },
@@ -207,6 +244,11 @@ pub fn generate_table_metadata_impl(
 
     let has_soft_delete = soft_delete_field.is_some();
 
+    let version_field_option = match version_field {
+        Some(field) => quote! { Some(#field) },
+        None => quote! { None },
+    };
+
     // Generate binding expressions for update fields
     let _bind_calls: Vec<_> = update_fields
         .iter()
@@ -217,6 +259,43 @@ pub fn generate_table_metadata_impl(
         })
         .collect();
 
+    // Generate binding expressions for upsert fields
+    let upsert_bind_calls: Vec<_> = upsert_fields
+        .iter()
+        .map(|field_name| {
+            let field_ident: proc_macro2::Ident =
+                Ident::new(field_name, proc_macro2::Span::call_site());
+            quote! { .bind(self.#field_ident.clone()) }
+        })
+        .collect();
+
+    // Generate binding and binary COPY encoding for create fields
+    let create_bind_calls: Vec<_> = create_fields
+        .iter()
+        .map(|field_name| {
+            let field_ident: proc_macro2::Ident =
+                Ident::new(field_name, proc_macro2::Span::call_site());
+            quote! { .bind(self.#field_ident.clone()) }
+        })
+        .collect();
+    let copy_encode_calls: Vec<_> = create_fields
+        .iter()
+        .map(|field_name| {
+            let field_ident: proc_macro2::Ident =
+                Ident::new(field_name, proc_macro2::Span::call_site());
+            quote! { store_object::generic_store::encode_copy_field(buf, &self.#field_ident)?; }
+        })
+        .collect();
+    let copy_field_count = create_fields.len() as i16;
+
+    let version_value = match version_field {
+        Some(field) => {
+            let field_ident = Ident::new(field, proc_macro2::Span::call_site());
+            quote! { Some(i64::from(self.#field_ident)) }
+        }
+        None => quote! { None },
+    };
+
     // Generate type Id and methods that depend on primary key
     let (id_type, extract_id_impl, primary_key_field_impl) = if let Some(pk_type_tokens) = primary_key_type_tokens {
         let pk_field = primary_key_field.as_ref().unwrap();
@@ -297,6 +376,14 @@ pub fn generate_table_metadata_impl(
                 #soft_delete_field_option
             }
 
+            fn version_field() -> Option<&'static str> {
+                #version_field_option
+            }
+
+            fn version(&self) -> Option<i64> {
+                #version_value
+            }
+
             #extract_id_impl
 
             fn create_fields() -> Vec<&'static str> {
@@ -307,6 +394,14 @@ pub fn generate_table_metadata_impl(
                 #update_fields_vec
             }
 
+            fn upsert_fields() -> Vec<&'static str> {
+                #upsert_fields_vec
+            }
+
+            fn unique_keys() -> Vec<Vec<&'static str>> {
+                vec![#(#unique_keys),*]
+            }
+
             #primary_key_field_impl
 
             fn create_table_sql() -> String {
@@ -317,6 +412,10 @@ pub fn generate_table_metadata_impl(
                 Self::generate_table_fields()
             }
 
+            fn get_table_columns() -> Vec<store_object::ColumnDefinition> {
+                Self::generate_table_columns()
+            }
+
             fn create_indexes_sql() -> Vec<String> {
                 Self::generate_indexes_sql()
             }
@@ -343,6 +442,37 @@ pub fn generate_table_metadata_impl(
                 query #(#_bind_calls)*
             }
 
+            fn bind_upsert_params<'a>(
+                &'a self,
+                sql: &'a str
+            ) -> sqlx::query::Query<'a, sqlx::Postgres, sqlx::postgres::PgArguments>
+            {
+                let query = sqlx::query(sql);
+                query #(#upsert_bind_calls)*
+            }
+
+            fn bind_create_params<'a>(
+                &'a self,
+                query: sqlx::query::Query<'a, sqlx::Postgres, sqlx::postgres::PgArguments>
+            ) -> sqlx::query::Query<'a, sqlx::Postgres, sqlx::postgres::PgArguments>
+            {
+                query #(#create_bind_calls)*
+            }
+
+            fn bind_update_params<'a>(
+                &'a self,
+                query: sqlx::query::Query<'a, sqlx::Postgres, sqlx::postgres::PgArguments>
+            ) -> sqlx::query::Query<'a, sqlx::Postgres, sqlx::postgres::PgArguments>
+            {
+                query #(#_bind_calls)*
+            }
+
+            fn encode_copy_row(&self, buf: &mut Vec<u8>) -> Result<(), sqlx::Error> {
+                buf.extend_from_slice(&#copy_field_count.to_be_bytes());
+                #(#copy_encode_calls)*
+                Ok(())
+            }
+
         }
     }
 }
@@ -375,6 +505,41 @@ pub fn generate_helper_impl(
         None => quote! { None },
     };
 
+    let version_field_option = match &field_info.version_field {
+        Some(field) => quote! { Some(#field) },
+        None => quote! { None },
+    };
+
+    // Generate #[index] / #[unique] index statements, single-field and composite
+    let safe_table_name = safe_sql_identifier(table_name);
+    let index_sql: Vec<String> = field_info
+        .indexed_fields
+        .iter()
+        .map(|field| vec![field.clone()])
+        .chain(table_info.composite_indexes.iter().cloned())
+        .map(|fields| (fields, false))
+        .chain(
+            field_info
+                .unique_fields
+                .iter()
+                .map(|field| vec![field.clone()])
+                .chain(table_info.composite_unique_indexes.iter().cloned())
+                .map(|fields| (fields, true)),
+        )
+        .map(|(fields, unique)| {
+            let columns: Vec<_> = fields.iter().map(|f| safe_sql_identifier(f)).collect();
+            format!(
+                "CREATE {}INDEX IF NOT EXISTS idx_{}_{}{} ON {} ({})",
+                if unique { "UNIQUE " } else { "" },
+                table_name,
+                fields.join("_"),
+                if unique { "_unique" } else { "" },
+                safe_table_name,
+                columns.join(", ")
+            )
+        })
+        .collect();
+
     // Generate field type mappings for compile-time injection
     let field_type_mappings: Vec<_> = field_info
         .field_types
@@ -471,6 +636,16 @@ pub fn generate_helper_impl(
                     }
                 }
 
+                // Add version column for optimistic concurrency if present
+                if let Some(version_field_name) = #version_field_option {
+                    let pg_type = field_types
+                        .get(version_field_name)
+                        .map(|rust_type| Self::rust_type_to_pg_type(rust_type))
+                        .unwrap_or("BIGINT");
+                    let safe_field_name = Self::safe_sql_identifier(version_field_name);
+                    field_definitions.push(format!("{} {} NOT NULL DEFAULT 1", safe_field_name, pg_type));
+                }
+
                 // Add readonly system fields (timestamps)
                 field_definitions.push("__created_at__ TIMESTAMP WITH TIME ZONE DEFAULT NOW()".to_string());
                 field_definitions.push("__updated_at__ TIMESTAMP WITH TIME ZONE DEFAULT NOW()".to_string());
@@ -511,6 +686,14 @@ pub fn generate_helper_impl(
                     }
                 }
 
+                if let Some(version_field_name) = #version_field_option {
+                    let pg_type = field_types
+                        .get(version_field_name)
+                        .map(|rust_type| Self::rust_type_to_pg_type(rust_type))
+                        .unwrap_or("BIGINT");
+                    fields.push((version_field_name, pg_type));
+                }
+
                 fields.push(("__created_at__", "TIMESTAMP WITH TIME ZONE"));
                 fields.push(("__updated_at__", "TIMESTAMP WITH TIME ZONE"));
 
@@ -518,9 +701,105 @@ pub fn generate_helper_impl(
                     fields.push((soft_delete_field_name, "BOOLEAN"));
                 }
 
+                fields.push(("__tags__", "TEXT[]"));
+
                 fields
             }
 
+            fn generate_table_columns() -> Vec<store_object::ColumnDefinition> {
+                let mut columns = Vec::new();
+                let pk_field_name = stringify!(#primary_key_field);
+
+                // Mirrors generate_create_table_sql() column for column
+                if !pk_field_name.is_empty() {
+                    let pk_rust_type = stringify!(#primary_key_type_tokens);
+                    let (pg_type, default) = if #has_auto_increment {
+                        let pg_type = match pk_rust_type.trim() {
+                            "i16" => "SMALLSERIAL",
+                            "i64" => "BIGSERIAL",
+                            _ => "SERIAL",
+                        };
+                        (pg_type, None)
+                    } else {
+                        let default = match pk_rust_type.trim() {
+                            "Uuid" | "uuid :: Uuid" | "uuid::Uuid" => Some("gen_random_uuid()"),
+                            _ => None,
+                        };
+                        (Self::rust_type_to_pg_type(pk_rust_type), default)
+                    };
+                    columns.push(store_object::ColumnDefinition {
+                        name: pk_field_name,
+                        pg_type,
+                        nullable: false,
+                        default,
+                        primary_key: true,
+                    });
+                }
+
+                let field_types = Self::get_field_types();
+                for field_name in Self::create_fields() {
+                    if field_name != pk_field_name {
+                        let (pg_type, nullable) = match field_types.get(field_name) {
+                            Some(rust_type) => (
+                                Self::rust_type_to_pg_type(rust_type),
+                                ::storehaus::type_mapping::is_optional_type(rust_type),
+                            ),
+                            None => ("VARCHAR", false),
+                        };
+                        columns.push(store_object::ColumnDefinition {
+                            name: field_name,
+                            pg_type,
+                            nullable,
+                            default: None,
+                            primary_key: false,
+                        });
+                    }
+                }
+
+                if let Some(version_field_name) = #version_field_option {
+                    columns.push(store_object::ColumnDefinition {
+                        name: version_field_name,
+                        pg_type: field_types
+                            .get(version_field_name)
+                            .map(|rust_type| Self::rust_type_to_pg_type(rust_type))
+                            .unwrap_or("BIGINT"),
+                        nullable: false,
+                        default: Some("1"),
+                        primary_key: false,
+                    });
+                }
+
+                for timestamp_field in ["__created_at__", "__updated_at__"] {
+                    columns.push(store_object::ColumnDefinition {
+                        name: timestamp_field,
+                        pg_type: "TIMESTAMP WITH TIME ZONE",
+                        nullable: true,
+                        default: Some("NOW()"),
+                        primary_key: false,
+                    });
+                }
+
+                if let Some(soft_delete_field_name) = #soft_delete_field_option {
+                    columns.push(store_object::ColumnDefinition {
+                        name: soft_delete_field_name,
+                        pg_type: "BOOLEAN",
+                        nullable: true,
+                        default: Some("TRUE"),
+                        primary_key: false,
+                    });
+                }
+
+                columns.push(store_object::ColumnDefinition {
+                    name: "__tags__",
+                    pg_type: "TEXT[]",
+                    nullable: true,
+                    default: Some("'{}'"),
+                    primary_key: false,
+                });
+
+                columns
+            }
+
             fn generate_indexes_sql() -> Vec<String> {
                 let table_name = #table_name;
                 let safe_table_name = Self::safe_sql_identifier(table_name);
@@ -553,6 +832,9 @@ pub fn generate_helper_impl(
                     table_name, safe_table_name
                 ));
 
+                // Add #[index] and #[unique] indexes declared on the model
+                #(indexes.push(#index_sql.to_string());)*
+
                 indexes
             }
 
@@ -597,22 +879,65 @@ pub fn generate_database_executor_impl(name: &Ident, field_info: &FieldInfo) ->
         })
         .collect();
 
+    // Versioned updates match zero rows when the record changed since it was read,
+    // or when it does not exist at all
+    let execute_update_body = |executor: TokenStream| match &field_info.version_field {
+        Some(version_field) => {
+            let version_ident = Ident::new(version_field, proc_macro2::Span::call_site());
+            quote! {
+                let sql = Self::update_sql();
+                let id = self.extract_id();
+                let updated = sqlx::query_as::<_, Self>(sql)
+                    #(#update_bind_calls)*
+                    .bind(&id)
+                    .bind(self.#version_ident)
+                    .fetch_optional(#executor)
+                    .await
+                    .map_err(|e| store_object::StorehausError::database_operation(Self::table_name(), "update", e))?;
+                if let Some(updated) = updated {
+                    return Ok(updated);
+                }
+
+                let exists: bool = sqlx::query_scalar(&Self::exists_by_id_sql())
+                    .bind(&id)
+                    .fetch_one(#executor)
+                    .await
+                    .map_err(|e| store_object::StorehausError::database_operation(Self::table_name(), "update", e))?;
+                let identifier = store_object::HasUniversalId::universal_id(&id).to_string_fast();
+                if exists {
+                    Err(store_object::StorehausError::concurrency_conflict(
+                        Self::table_name(),
+                        &identifier,
+                        i64::from(self.#version_ident),
+                    ))
+                } else {
+                    Err(store_object::StorehausError::not_found(Self::table_name(), &identifier))
+                }
+            }
+        }
+        None => quote! {
+            let sql = Self::update_sql();
+            let id = self.extract_id();
+            sqlx::query_as::<_, Self>(sql)
+                #(#update_bind_calls)*
+                .bind(&id)
+                .fetch_one(#executor)
+                .await
+                .map_err(|e| store_object::StorehausError::database_operation(Self::table_name(), "update", e))
+        },
+    };
+
     // Generate update methods only if primary key exists
     let update_methods = if field_info.primary_key_field.is_some() {
+        let update_body = execute_update_body(quote! { pool });
+        let update_tx_body = execute_update_body(quote! { tx.as_mut() });
         quote! {
             async fn execute_update(&self, pool: &sqlx::PgPool) -> Result<Self, store_object::StorehausError>
             where
                 Self: Sized + Send + Sync,
                 Self: for<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow>
             {
-                let sql = Self::update_sql();
-                let id = self.extract_id();
-                sqlx::query_as::<_, Self>(sql)
-                    #(#update_bind_calls)*
-                    .bind(&id)
-                    .fetch_one(pool)
-                    .await
-                    .map_err(|e| store_object::StorehausError::database_operation(Self::table_name(), "update", e))
+                #update_body
             }
 
             async fn execute_update_tx(&self, tx: &mut sqlx::Transaction<'_, sqlx::Postgres>) -> Result<Self, store_object::StorehausError>
@@ -620,14 +945,7 @@ pub fn generate_database_executor_impl(name: &Ident, field_info: &FieldInfo) ->
                 Self: Sized + Send + Sync,
                 Self: for<'r> sqlx::FromRow<'r, sqlx::postgres::PgRow>
             {
-                let sql = Self::update_sql();
-                let id = self.extract_id();
-                sqlx::query_as::<_, Self>(sql)
-                    #(#update_bind_calls)*
-                    .bind(&id)
-                    .fetch_one(tx.as_mut())
-                    .await
-                    .map_err(|e| store_object::StorehausError::database_operation(Self::table_name(), "update", e))
+                #update_tx_body
             }
         }
     } else {
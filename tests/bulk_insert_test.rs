@@ -0,0 +1,152 @@
+//! Integration tests for bulk inserts (multi-row INSERT and binary COPY)
+
+mod common;
+
+use common::Received;
+use serde_json::json;
+use sqlx::PgPool;
+use storehaus::prelude::*;
+use storehaus::store_object::generic_store::MAX_BIND_PARAMS;
+
+#[model]
+#[table(name = "bulk_insert_test_readings")]
+pub struct Reading {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub sensor: String,
+
+    #[field(create, update)]
+    pub value: i64,
+
+    #[field(create, update)]
+    pub note: Option<String>,
+
+    #[field(create, update)]
+    pub labels: Vec<String>,
+
+    #[field(create, update)]
+    pub metadata: serde_json::Value,
+}
+
+/// Generated key and system fields only, so there are no CREATE fields
+#[model]
+#[table(name = "bulk_insert_test_ticks", auto_increment)]
+pub struct Tick {
+    #[primary_key]
+    pub id: i32,
+}
+
+async fn setup() -> (PgPool, GenericStore<Reading>, Received) {
+    let pool = common::connect().await;
+
+    common::recreate_table::<Reading>(&pool).await;
+
+    let (signal_manager, received) = common::recording_signals().await;
+
+    let store = GenericStore::<Reading>::new(pool.clone(), Some(signal_manager), None);
+    (pool, store, received)
+}
+
+async fn cleanup(pool: &PgPool) {
+    common::drop_table::<Reading>(pool).await;
+}
+
+fn readings(count: usize) -> Vec<Reading> {
+    (0..count)
+        .map(|i| {
+            Reading::new(
+                Uuid::new_v4(),
+                format!("sensor-{}", i % 10),
+                i as i64,
+                (i % 2 == 0).then(|| format!("note {}", i)),
+                vec!["bulk".to_string()],
+                json!({ "index": i }),
+            )
+        })
+        .collect()
+}
+
+fn created_count(event: &DatabaseEvent) -> Option<&PostgresValue> {
+    event.payload.get("created_count")
+}
+
+#[tokio::test]
+async fn test_create_many_spans_several_statements() {
+    let (pool, store, received) = setup().await;
+
+    // More rows than fit into one statement's bind parameters
+    let count = MAX_BIND_PARAMS / Reading::create_fields().len() + 100;
+    let data = readings(count);
+    let created = store.create_many(data).await.expect("Failed to create");
+
+    assert_eq!(created.len(), count);
+    assert_eq!(created[0].value, 0);
+    assert_eq!(created[count - 1].value, count as i64 - 1);
+    assert_eq!(created[1].note, None);
+    assert_eq!(created[2].note.as_deref(), Some("note 2"));
+    assert_eq!(created[2].metadata, json!({ "index": 2 }));
+    assert_eq!(store.count().await.unwrap(), count as i64);
+
+    let events = received.lock().unwrap().clone();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0].event_type, EventType::Create));
+    assert!(matches!(
+        created_count(&events[0]),
+        Some(PostgresValue::Integer(n)) if *n == count as i32
+    ));
+
+    // Nothing to insert is not a write
+    assert!(store.create_many(vec![]).await.unwrap().is_empty());
+    assert_eq!(received.lock().unwrap().len(), 1);
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_copy_in_loads_rows() {
+    let (pool, store, received) = setup().await;
+
+    let data = readings(5_000);
+    let copied = store.copy_in(&data).await.expect("Failed to copy");
+    assert_eq!(copied, 5_000);
+    assert_eq!(store.count().await.unwrap(), 5_000);
+
+    let stored = store
+        .find(QueryBuilder::new().filter(QueryFilter::eq("value", json!(42))))
+        .await
+        .expect("Failed to find");
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[0].sensor, "sensor-2");
+    assert_eq!(stored[0].note.as_deref(), Some("note 42"));
+    assert_eq!(stored[0].labels, vec!["bulk".to_string()]);
+    assert_eq!(stored[0].metadata, json!({ "index": 42 }));
+
+    let events = received.lock().unwrap().clone();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0].event_type, EventType::Create));
+    assert!(matches!(
+        created_count(&events[0]),
+        Some(PostgresValue::Integer(5_000))
+    ));
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_create_many_without_create_fields() {
+    let pool = common::connect().await;
+    common::recreate_table::<Tick>(&pool).await;
+    let store = GenericStore::<Tick>::new(pool.clone(), None, None);
+
+    let created = store
+        .create_many(vec![Tick::new(0), Tick::new(0)])
+        .await
+        .expect("Failed to create");
+    assert_eq!(created.len(), 2);
+    assert_ne!(created[0].id, created[1].id);
+    assert_eq!(store.count().await.expect("Failed to count"), 2);
+
+    common::drop_table::<Tick>(&pool).await;
+}
@@ -0,0 +1,217 @@
+//! Integration tests for set-based update_many and delete_many
+
+mod common;
+
+use serde_json::json;
+use storehaus::prelude::*;
+use storehaus::store_object::generic_store::MAX_BIND_PARAMS;
+
+#[model]
+#[table(name = "bulk_update_test_tasks")]
+pub struct Task {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub title: String,
+
+    #[field(create, update)]
+    pub priority: i32,
+
+    #[field(create, update)]
+    pub assignee: Option<String>,
+
+    #[version]
+    pub version: i64,
+}
+
+#[model]
+#[table(name = "bulk_update_test_sessions", auto_soft_delete)]
+pub struct Session {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub token: String,
+}
+
+/// Derived without `#[model]`, so there is no `__tags__` UPDATE field either
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow, TableMetadata)]
+#[table(name = "bulk_update_test_entries")]
+pub struct Entry {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create)]
+    pub body: String,
+
+    #[readonly]
+    pub __created_at__: chrono::DateTime<chrono::Utc>,
+
+    #[readonly]
+    pub __updated_at__: chrono::DateTime<chrono::Utc>,
+}
+
+fn tasks(count: usize) -> Vec<Task> {
+    (0..count)
+        .map(|i| Task::new(Uuid::new_v4(), format!("task {}", i), 0, None))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_update_many_spans_several_statements() {
+    let pool = common::connect().await;
+    common::recreate_table::<Task>(&pool).await;
+    let store = GenericStore::<Task>::new(pool.clone(), None, None);
+
+    // More rows than fit into one statement's bind parameters
+    let count = MAX_BIND_PARAMS / (Task::update_fields().len() + 2) + 50;
+    let created = store.create_many(tasks(count)).await.expect("Failed to create");
+
+    let updates: Vec<_> = created
+        .iter()
+        .rev()
+        .map(|task| {
+            let mut task = task.clone();
+            task.priority = 5;
+            task.assignee = Some("ops".to_string());
+            (task.id, task)
+        })
+        .collect();
+    let updated = store.update_many(updates).await.expect("Failed to update");
+
+    // Results follow the input order
+    assert_eq!(updated.len(), count);
+    assert_eq!(updated[0].id, created[count - 1].id);
+    assert_eq!(updated[count - 1].id, created[0].id);
+    assert!(updated.iter().all(|task| task.priority == 5 && task.version == 2));
+    assert_eq!(
+        store
+            .count_where(QueryBuilder::new().filter(QueryFilter::eq("priority", json!(5))))
+            .await
+            .unwrap(),
+        count as i64
+    );
+
+    assert!(store.update_many(vec![]).await.unwrap().is_empty());
+
+    common::drop_table::<Task>(&pool).await;
+}
+
+#[tokio::test]
+async fn test_update_many_is_atomic() {
+    let pool = common::connect().await;
+    common::recreate_table::<Task>(&pool).await;
+    let store = GenericStore::<Task>::new(pool.clone(), None, None);
+
+    let created = store.create_many(tasks(3)).await.expect("Failed to create");
+
+    // A stale version fails the whole batch
+    let mut stale = created[1].clone();
+    stale.version = 7;
+    let updates = vec![
+        (created[0].id, created[0].clone()),
+        (stale.id, stale),
+        (created[2].id, created[2].clone()),
+    ];
+    let err = store.update_many(updates).await.unwrap_err();
+    assert!(matches!(
+        err,
+        StorehausError::ConcurrencyConflict { expected_version: 7, .. }
+    ));
+
+    // As with update, an unknown id is not found
+    let missing = Task::new(Uuid::new_v4(), "missing".to_string(), 0, None);
+    let updates = vec![(created[0].id, created[0].clone()), (missing.id, missing)];
+    let err = store.update_many(updates).await.unwrap_err();
+    assert!(matches!(err, StorehausError::NotFound { .. }));
+
+    for task in &created {
+        let stored = store.get_by_id(&task.id).await.unwrap().unwrap();
+        assert_eq!(stored.version, 1);
+    }
+
+    common::drop_table::<Task>(&pool).await;
+}
+
+#[tokio::test]
+async fn test_delete_many_in_one_statement() {
+    let pool = common::connect().await;
+    common::recreate_table::<Task>(&pool).await;
+    let store = GenericStore::<Task>::new(pool.clone(), None, None);
+
+    let created = store.create_many(tasks(4)).await.expect("Failed to create");
+
+    // Unknown ids are skipped and duplicates reported once
+    let unknown = Uuid::new_v4();
+    let deleted = store
+        .delete_many(vec![created[2].id, unknown, created[0].id, created[2].id])
+        .await
+        .expect("Failed to delete");
+    assert_eq!(deleted, vec![created[2].id, created[0].id]);
+    assert_eq!(store.count().await.unwrap(), 2);
+
+    assert!(store.delete_many(vec![]).await.unwrap().is_empty());
+
+    common::drop_table::<Task>(&pool).await;
+}
+
+#[tokio::test]
+async fn test_soft_delete_many_in_one_statement() {
+    let pool = common::connect().await;
+    common::recreate_table::<Session>(&pool).await;
+    let store = GenericStore::<Session>::new(pool.clone(), None, None);
+
+    let sessions: Vec<_> = (0..3)
+        .map(|i| Session::new(Uuid::new_v4(), format!("token-{}", i)))
+        .collect();
+    let created = store.create_many(sessions).await.expect("Failed to create");
+
+    // Unversioned models report unknown ids as not found
+    let missing = Session::new(Uuid::new_v4(), "missing".to_string());
+    let err = store
+        .update_many(vec![(created[0].id, created[0].clone()), (missing.id, missing)])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, StorehausError::NotFound { .. }));
+
+    let deleted = store
+        .delete_many(vec![created[0].id, created[1].id])
+        .await
+        .expect("Failed to soft delete");
+    assert_eq!(deleted, vec![created[0].id, created[1].id]);
+
+    // Rows are kept but deactivated
+    assert_eq!(store.count_active().await.unwrap(), 1);
+    let rows: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM {}",
+        Session::table_name()
+    ))
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(rows, 3);
+
+    common::drop_table::<Session>(&pool).await;
+}
+
+#[tokio::test]
+async fn test_update_many_without_update_fields_is_rejected() {
+    let pool = common::connect().await;
+    let store = GenericStore::<Entry>::new(pool, None, None);
+
+    let entry = Entry {
+        id: Uuid::new_v4(),
+        body: "first".to_string(),
+        __created_at__: chrono::Utc::now(),
+        __updated_at__: chrono::Utc::now(),
+    };
+    let err = store
+        .update_many(vec![(entry.id, entry)])
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        StorehausError::ValidationError { ref field, .. } if field == "update_fields"
+    ));
+}
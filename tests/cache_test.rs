@@ -0,0 +1,389 @@
+//! Integration tests for store caching
+//!
+//! Uses the in-memory cache backend, so only PostgreSQL is required.
+
+mod common;
+
+use serde_json::json;
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use storehaus::prelude::*;
+
+#[model]
+#[table(name = "cache_test_products")]
+pub struct Product {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub name: String,
+
+    #[field(create, update)]
+    pub price: i64,
+}
+
+/// Model with a natural key, so created ids are known before insertion
+#[model]
+#[table(name = "cache_test_coupons")]
+pub struct Coupon {
+    #[primary_key]
+    #[field(create)]
+    pub code: i64,
+
+    #[field(create, update)]
+    pub percent: i32,
+}
+
+const PREFIX: &str = "cache_test";
+
+async fn setup(
+    write_mode: CacheWriteMode,
+    query_invalidation: QueryInvalidation,
+) -> (PgPool, GenericStore<Product>, Arc<CacheManager>) {
+    setup_with(
+        write_mode,
+        query_invalidation,
+        StampedeProtection::default(),
+    )
+    .await
+}
+
+async fn setup_with(
+    write_mode: CacheWriteMode,
+    query_invalidation: QueryInvalidation,
+    stampede: StampedeProtection,
+) -> (PgPool, GenericStore<Product>, Arc<CacheManager>) {
+    let pool = common::connect().await;
+
+    common::recreate_table::<Product>(&pool).await;
+
+    let cache_manager = Arc::new(
+        CacheManager::new(CacheConfig::memory(100))
+            .expect("Failed to create cache manager")
+            .with_query_invalidation(query_invalidation),
+    );
+    let cache_params = CacheParams::new(cache_manager.clone(), 60, PREFIX)
+        .with_query_cache(true)
+        .with_write_mode(write_mode)
+        .with_stampede_protection(stampede);
+    let store = GenericStore::<Product>::new(pool.clone(), None, Some(cache_params));
+
+    (pool, store, cache_manager)
+}
+
+async fn cleanup(pool: &PgPool) {
+    common::drop_table::<Product>(pool).await;
+}
+
+/// Change a row behind the store's back, so only cached reads still see the old value
+async fn set_price_directly(pool: &PgPool, id: &Uuid, price: i64) {
+    sqlx::query("UPDATE cache_test_products SET price = $1 WHERE id = $2")
+        .bind(price)
+        .bind(id)
+        .execute(pool)
+        .await
+        .expect("Failed to update row directly");
+}
+
+#[tokio::test]
+async fn test_record_cache_invalidated_on_writes() {
+    let (pool, store, cache) = setup(CacheWriteMode::Invalidate, QueryInvalidation::TagSet).await;
+
+    let product = store
+        .create(Product::new(Uuid::new_v4(), "Lamp".to_string(), 30), None)
+        .await
+        .expect("Failed to create product");
+    let key = product.id.to_string();
+
+    store.get_by_id(&product.id).await.expect("Failed to get");
+    assert!(
+        cache
+            .record_exists(PREFIX, Product::table_name(), &key)
+            .await
+            .unwrap()
+    );
+
+    // Cached reads don't see out-of-band changes
+    set_price_directly(&pool, &product.id, 35).await;
+    let cached = store.get_by_id(&product.id).await.unwrap().unwrap();
+    assert_eq!(cached.price, 30);
+
+    // Updates through the store evict the record
+    store
+        .update(
+            &product.id,
+            Product {
+                price: 40,
+                ..cached
+            },
+            None,
+        )
+        .await
+        .expect("Failed to update");
+    assert!(
+        !cache
+            .record_exists(PREFIX, Product::table_name(), &key)
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        store.get_by_id(&product.id).await.unwrap().unwrap().price,
+        40
+    );
+
+    // Bulk deletes evict every affected record
+    store
+        .delete_where(QueryBuilder::new().filter(QueryFilter::eq("name", json!("Lamp"))))
+        .await
+        .expect("Failed to delete");
+    assert!(
+        !cache
+            .record_exists(PREFIX, Product::table_name(), &key)
+            .await
+            .unwrap()
+    );
+    assert!(store.get_by_id(&product.id).await.unwrap().is_none());
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_write_through_updates_record_cache() {
+    let (pool, store, cache) = setup(CacheWriteMode::WriteThrough, QueryInvalidation::TagSet).await;
+
+    let product = store
+        .create(Product::new(Uuid::new_v4(), "Desk".to_string(), 200), None)
+        .await
+        .expect("Failed to create product");
+
+    store
+        .update_where(
+            QueryBuilder::new().filter(QueryFilter::eq("name", json!("Desk"))),
+            Some(Product::new(product.id, "Desk".to_string(), 250)),
+        )
+        .await
+        .expect("Failed to update");
+
+    let cached: Option<Product> = cache
+        .get_record(PREFIX, Product::table_name(), &product.id.to_string())
+        .await
+        .unwrap();
+    assert_eq!(cached.map(|p| p.price), Some(250));
+
+    cleanup(&pool).await;
+}
+
+async fn assert_query_cache(query_invalidation: QueryInvalidation) {
+    let (pool, store, _) = setup(CacheWriteMode::Invalidate, query_invalidation).await;
+
+    let chair = store
+        .create(Product::new(Uuid::new_v4(), "Chair".to_string(), 50), None)
+        .await
+        .expect("Failed to create product");
+
+    let cheap = || QueryBuilder::new().filter(QueryFilter::lt("price", json!(100)));
+
+    assert_eq!(store.find(cheap()).await.unwrap().len(), 1);
+    assert_eq!(store.count_where(cheap()).await.unwrap(), 1);
+
+    // Served from cache while the table only changes behind the store's back
+    set_price_directly(&pool, &chair.id, 500).await;
+    assert_eq!(store.find(cheap()).await.unwrap().len(), 1);
+    assert_eq!(store.count_where(cheap()).await.unwrap(), 1);
+
+    // Different parameters are cached separately
+    let expensive = QueryBuilder::new().filter(QueryFilter::gte("price", json!(100)));
+    assert_eq!(store.find(expensive).await.unwrap().len(), 1);
+
+    // Any write through the store invalidates cached queries
+    store
+        .create(Product::new(Uuid::new_v4(), "Stool".to_string(), 20), None)
+        .await
+        .expect("Failed to create product");
+    let fresh = store.find(cheap()).await.unwrap();
+    assert_eq!(fresh.len(), 1);
+    assert_eq!(fresh[0].name, "Stool");
+    assert_eq!(store.count_where(cheap()).await.unwrap(), 1);
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_query_cache_with_tag_sets() {
+    assert_query_cache(QueryInvalidation::TagSet).await;
+}
+
+#[tokio::test]
+async fn test_query_cache_with_versioned_namespaces() {
+    assert_query_cache(QueryInvalidation::Versioned).await;
+}
+
+#[tokio::test]
+async fn test_query_cache_with_scan() {
+    assert_query_cache(QueryInvalidation::Scan).await;
+}
+
+#[tokio::test]
+async fn test_query_cache_is_opt_in() {
+    let (pool, _, cache) = setup(CacheWriteMode::Invalidate, QueryInvalidation::TagSet).await;
+    let store = GenericStore::<Product>::new(
+        pool.clone(),
+        None,
+        Some(CacheParams::new(cache, 60, PREFIX)),
+    );
+
+    let chair = store
+        .create(Product::new(Uuid::new_v4(), "Chair".to_string(), 50), None)
+        .await
+        .expect("Failed to create product");
+    let cheap = || QueryBuilder::new().filter(QueryFilter::lt("price", json!(100)));
+    assert_eq!(store.count_where(cheap()).await.unwrap(), 1);
+
+    // Without `with_query_cache(true)` every query reads the table
+    set_price_directly(&pool, &chair.id, 500).await;
+    assert_eq!(store.count_where(cheap()).await.unwrap(), 0);
+    assert!(store.find(cheap()).await.unwrap().is_empty());
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_unavailable_query_cache_falls_through_to_database() {
+    let (pool, _, _) = setup(CacheWriteMode::Invalidate, QueryInvalidation::Versioned).await;
+    // Nothing listens on this port, so every cache operation fails
+    let unreachable = Arc::new(
+        CacheManager::new(CacheConfig::new(
+            "redis://127.0.0.1:1".to_string(),
+            1,
+            100,
+            100,
+            100,
+        ))
+        .expect("Failed to create cache manager")
+        .with_query_invalidation(QueryInvalidation::Versioned),
+    );
+    let cache_params = CacheParams::new(unreachable, 60, PREFIX).with_query_cache(true);
+    let store = GenericStore::<Product>::new(pool.clone(), None, Some(cache_params));
+
+    store
+        .create(Product::new(Uuid::new_v4(), "Lamp".to_string(), 30), None)
+        .await
+        .expect("Failed to create product");
+    let cheap = || QueryBuilder::new().filter(QueryFilter::lt("price", json!(100)));
+    assert_eq!(store.find(cheap()).await.unwrap().len(), 1);
+    assert_eq!(store.count_where(cheap()).await.unwrap(), 1);
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_concurrent_misses_load_once() {
+    let stampede =
+        StampedeProtection::default().with_lock(Duration::from_secs(5), Duration::from_secs(1));
+    let (pool, store, cache) = setup_with(
+        CacheWriteMode::Invalidate,
+        QueryInvalidation::TagSet,
+        stampede,
+    )
+    .await;
+    let store = Arc::new(store);
+
+    let product = store
+        .create(Product::new(Uuid::new_v4(), "Shelf".to_string(), 80), None)
+        .await
+        .expect("Failed to create product");
+
+    // Another node is reloading the record: readers wait for it instead of querying
+    let lock = cache
+        .try_lock_record(
+            PREFIX,
+            Product::table_name(),
+            &product.id.to_string(),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .expect("Lock should be free");
+
+    let readers: Vec<_> = (0..8)
+        .map(|_| {
+            let store = store.clone();
+            let id = product.id;
+            tokio::spawn(async move { store.get_by_id(&id).await })
+        })
+        .collect();
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    cache
+        .set_record(
+            PREFIX,
+            Product::table_name(),
+            &product.id.to_string(),
+            &Product {
+                price: 85,
+                ..product.clone()
+            },
+            60,
+        )
+        .await
+        .unwrap();
+    cache.unlock_record(&lock).await.unwrap();
+
+    for reader in readers {
+        let loaded = reader.await.unwrap().unwrap().unwrap();
+        assert_eq!(loaded.price, 85);
+    }
+
+    // Without a lock holder, a miss reloads from the database
+    cache
+        .delete_record(PREFIX, Product::table_name(), &product.id.to_string())
+        .await
+        .unwrap();
+    let reloaded = store.get_by_id(&product.id).await.unwrap().unwrap();
+    assert_eq!(reloaded.price, 80);
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_negative_cache_dropped_on_create() {
+    let (pool, _, cache) = setup(CacheWriteMode::Invalidate, QueryInvalidation::TagSet).await;
+    common::recreate_table::<Coupon>(&pool).await;
+    let cache_params = CacheParams::new(cache.clone(), 60, PREFIX).with_negative_caching(5);
+    let store = GenericStore::<Coupon>::new(pool.clone(), None, Some(cache_params));
+
+    let code: i64 = 2024;
+    assert!(store.get_by_id(&code).await.unwrap().is_none());
+
+    // The miss is cached with the short TTL
+    let ttl = cache
+        .record_ttl(PREFIX, Coupon::table_name(), &code.to_string())
+        .await
+        .unwrap();
+    assert!((1..=5).contains(&ttl));
+
+    // Rows inserted behind the store's back stay hidden until the entry expires
+    sqlx::query("INSERT INTO cache_test_coupons (code, percent) VALUES ($1, 10)")
+        .bind(code)
+        .execute(&pool)
+        .await
+        .expect("Failed to insert row directly");
+    assert!(store.get_by_id(&code).await.unwrap().is_none());
+    sqlx::query("DELETE FROM cache_test_coupons WHERE code = $1")
+        .bind(code)
+        .execute(&pool)
+        .await
+        .expect("Failed to delete row directly");
+
+    // Creating the id through the store drops the cached miss
+    store
+        .create(Coupon::new(code, 15), None)
+        .await
+        .expect("Failed to create coupon");
+    let found = store.get_by_id(&code).await.unwrap().unwrap();
+    assert_eq!(found.percent, 15);
+
+    common::drop_table::<Coupon>(&pool).await;
+    cleanup(&pool).await;
+}
@@ -0,0 +1,64 @@
+//! Shared setup for the integration tests
+//!
+//! Every test binary connects to `DATABASE_URL` and recreates the tables it
+//! uses, so the Makefile runs them with `--test-threads=1`.
+
+// Each test binary uses only some of the helpers
+#![allow(dead_code)]
+
+use std::sync::{Arc, Mutex};
+use storehaus::prelude::*;
+
+/// Events received by a `recording_signals` callback
+pub type Received = Arc<Mutex<Vec<DatabaseEvent>>>;
+
+/// Connect to the test database
+pub async fn connect() -> PgPool {
+    let database_url =
+        std::env::var("DATABASE_URL").expect("DATABASE_URL must be set for integration tests");
+
+    PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to database")
+}
+
+/// Run table setup statements in order
+pub async fn execute_all(pool: &PgPool, statements: impl IntoIterator<Item = String>) {
+    for sql in statements {
+        sqlx::query(&sql)
+            .execute(pool)
+            .await
+            .expect("Failed to prepare table");
+    }
+}
+
+/// Drop and create the table of `T` together with its indexes
+pub async fn recreate_table<T: TableMetadata>(pool: &PgPool) {
+    let mut statements = vec![T::drop_table_sql(), T::create_table_sql()];
+    statements.extend(T::create_indexes_sql());
+    execute_all(pool, statements).await;
+}
+
+/// Drop the table of `T`, ignoring errors
+pub async fn drop_table<T: TableMetadata>(pool: &PgPool) {
+    let _ = sqlx::query(&T::drop_table_sql()).execute(pool).await;
+}
+
+/// Signal manager whose only callback records every event
+pub async fn recording_signals() -> (Arc<SignalManager>, Received) {
+    let signal_manager = SignalManager::new(SignalConfig::new(5, 10, false, 3, 60, false, 300));
+    let received: Received = Arc::new(Mutex::new(Vec::new()));
+    let sink = received.clone();
+    signal_manager
+        .add_callback(move |event: DatabaseEvent| {
+            let sink = sink.clone();
+            async move {
+                sink.lock().unwrap().push(event);
+                Ok(())
+            }
+        })
+        .await
+        .expect("Failed to add callback");
+
+    (signal_manager, received)
+}
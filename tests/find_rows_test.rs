@@ -0,0 +1,202 @@
+//! Integration tests for full QueryBuilder execution
+//!
+//! Covers `find_rows`, `find_rows_as` and `aggregate` with SELECT fields,
+//! JOINs, GROUP BY and HAVING.
+
+mod common;
+
+use serde_json::json;
+use sqlx::PgPool;
+use storehaus::prelude::*;
+
+#[model]
+#[table(name = "rows_test_customers", auto_soft_delete)]
+pub struct Customer {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub name: String,
+
+    #[field(create, update)]
+    pub country: String,
+}
+
+#[model]
+#[table(name = "rows_test_orders")]
+pub struct Order {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub customer_id: Uuid,
+
+    #[field(create, update)]
+    pub total: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct CustomerTotals {
+    name: String,
+    order_count: i64,
+    spent: Option<sqlx::types::Decimal>,
+}
+
+async fn setup() -> (PgPool, GenericStore<Customer>, GenericStore<Order>) {
+    let pool = common::connect().await;
+
+    common::drop_table::<Order>(&pool).await;
+    common::recreate_table::<Customer>(&pool).await;
+    common::recreate_table::<Order>(&pool).await;
+
+    let customers = GenericStore::<Customer>::new(pool.clone(), None, None);
+    let orders = GenericStore::<Order>::new(pool.clone(), None, None);
+
+    let seed = [
+        ("Alice", "DE", vec![100, 250]),
+        ("Bob", "DE", vec![40]),
+        ("Carol", "FR", vec![70, 30, 20]),
+    ];
+    for (name, country, totals) in seed {
+        let customer = customers
+            .create(
+                Customer::new(Uuid::new_v4(), name.to_string(), country.to_string()),
+                None,
+            )
+            .await
+            .expect("Failed to create customer");
+        for total in totals {
+            orders
+                .create(Order::new(Uuid::new_v4(), customer.id, total), None)
+                .await
+                .expect("Failed to create order");
+        }
+    }
+
+    (pool, customers, orders)
+}
+
+async fn cleanup(pool: &PgPool) {
+    common::drop_table::<Order>(pool).await;
+    common::drop_table::<Customer>(pool).await;
+}
+
+fn totals_query() -> QueryBuilder {
+    QueryBuilder::new()
+        .select_fields(vec![
+            SelectField::field("rows_test_customers.name"),
+            SelectField::count("rows_test_orders.id").with_alias("order_count"),
+            SelectField::sum("rows_test_orders.total").with_alias("spent"),
+        ])
+        .join(JoinClause::new_on(
+            JoinType::Left,
+            "rows_test_orders",
+            "rows_test_customers.id",
+            "rows_test_orders.customer_id",
+        ))
+        .group_by(GroupBy::single("rows_test_customers.name"))
+        .order_by("rows_test_customers.name", SortOrder::Asc)
+}
+
+#[tokio::test]
+async fn test_find_rows_with_join_and_group_by() {
+    let (pool, customers, _) = setup().await;
+
+    let rows = customers
+        .find_rows(totals_query())
+        .await
+        .expect("Failed to run grouped query");
+
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0]["name"], json!("Alice"));
+    assert_eq!(rows[0]["order_count"], json!(2));
+    assert_eq!(rows[0]["spent"], json!(350));
+    assert_eq!(rows[2]["name"], json!("Carol"));
+    assert_eq!(rows[2]["order_count"], json!(3));
+
+    // HAVING values are bound after WHERE values
+    let query = totals_query()
+        .filter(QueryFilter::eq("rows_test_customers.country", json!("DE")))
+        .group_by(
+            GroupBy::single("rows_test_customers.name")
+                .having(QueryFilter::gt("SUM(rows_test_orders.total)", json!(100))),
+        );
+    let rows = customers
+        .find_rows(query)
+        .await
+        .expect("Failed to run HAVING query");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["name"], json!("Alice"));
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_find_rows_as_and_soft_delete() {
+    let (pool, customers, _) = setup().await;
+
+    let bob = customers
+        .find_one(QueryBuilder::new().filter(QueryFilter::eq("name", json!("Bob"))))
+        .await
+        .expect("Failed to find customer")
+        .expect("Bob should exist");
+    customers
+        .delete(&bob.id)
+        .await
+        .expect("Failed to soft delete");
+
+    let totals: Vec<CustomerTotals> = customers
+        .find_rows_as(totals_query())
+        .await
+        .expect("Failed to run typed query");
+
+    // Soft deleted customers are excluded like in find()
+    let names: Vec<&str> = totals.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["Alice", "Carol"]);
+    assert_eq!(totals[1].order_count, 3);
+    assert_eq!(
+        totals[1].spent.map(|d| d.to_string()),
+        Some("120".to_string())
+    );
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_aggregate() {
+    let (pool, _, orders) = setup().await;
+
+    let query = QueryBuilder::new()
+        .select_fields(vec![
+            SelectField::count_all().with_alias("orders"),
+            SelectField::avg("total").with_alias("average"),
+            SelectField::max("total").with_alias("largest"),
+        ])
+        .filter(QueryFilter::gte("total", json!(30)));
+
+    let row = orders.aggregate(query).await.expect("Failed to aggregate");
+    assert_eq!(row["orders"], json!(5));
+    assert_eq!(row["average"], json!(98));
+    assert_eq!(row["largest"], json!(250));
+
+    let grouped = QueryBuilder::new()
+        .select(SelectField::count_all())
+        .group_by(GroupBy::single("customer_id"));
+    assert!(matches!(
+        orders.aggregate(grouped).await,
+        Err(StorehausError::ValidationError { .. })
+    ));
+
+    // Plain rows are not an aggregation
+    let plain = QueryBuilder::new().select(SelectField::field("total"));
+    assert!(matches!(
+        orders.aggregate(plain).await,
+        Err(StorehausError::ValidationError { .. })
+    ));
+    assert!(matches!(
+        orders.aggregate(QueryBuilder::new()).await,
+        Err(StorehausError::ValidationError { .. })
+    ));
+
+    cleanup(&pool).await;
+}
@@ -0,0 +1,227 @@
+//! Integration tests for checking query identifiers against model columns
+
+mod common;
+
+use serde_json::json;
+use sqlx::PgPool;
+use storehaus::prelude::*;
+
+#[model]
+#[table(name = "identifier_test_authors")]
+pub struct Author {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub name: String,
+}
+
+#[model]
+#[table(name = "identifier_test_books")]
+pub struct Book {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub author_id: Uuid,
+
+    #[field(create, update)]
+    pub pages: i32,
+}
+
+#[model]
+#[table(name = "identifier_test_profiles")]
+pub struct Profile {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub settings: serde_json::Value,
+}
+
+async fn setup() -> (PgPool, GenericStore<Author>, GenericStore<Book>) {
+    let pool = common::connect().await;
+
+    common::recreate_table::<Author>(&pool).await;
+    common::recreate_table::<Book>(&pool).await;
+
+    (
+        pool.clone(),
+        GenericStore::<Author>::new(pool.clone(), None, None),
+        GenericStore::<Book>::new(pool, None, None),
+    )
+}
+
+async fn cleanup(pool: &PgPool) {
+    common::drop_table::<Book>(pool).await;
+    common::drop_table::<Author>(pool).await;
+}
+
+fn assert_rejected<T: std::fmt::Debug>(result: Result<T, StorehausError>, name: &str) {
+    match result {
+        Err(StorehausError::ValidationError { field, .. }) => assert_eq!(field, name),
+        other => panic!(
+            "Expected a validation error for '{}', got {:?}",
+            name, other
+        ),
+    }
+}
+
+#[tokio::test]
+async fn test_unknown_and_injected_names_are_rejected() {
+    let (pool, authors, _) = setup().await;
+    authors
+        .create(Author::new(Uuid::new_v4(), "Ann".to_string()), None)
+        .await
+        .expect("Failed to create");
+
+    let injected = "name = name OR 1=1 --";
+    assert_rejected(
+        authors
+            .find(QueryBuilder::new().filter(QueryFilter::eq(injected, json!("x"))))
+            .await,
+        injected,
+    );
+    assert_rejected(
+        authors
+            .find(QueryBuilder::new().order_by("(SELECT pg_sleep(1))", SortOrder::Asc))
+            .await,
+        "(SELECT pg_sleep(1))",
+    );
+    assert_rejected(
+        authors
+            .count_where(QueryBuilder::new().filter(QueryFilter::eq("email", json!("x"))))
+            .await,
+        "email",
+    );
+    assert_rejected(
+        authors
+            .update_where(
+                QueryBuilder::new().update(UpdateSet::new().set("name = 'x', id", json!("y"))),
+                None,
+            )
+            .await,
+        "name = 'x', id",
+    );
+    assert_rejected(
+        authors
+            .delete_where(QueryBuilder::new().filter(QueryFilter::ne("1=1 OR name", json!(""))))
+            .await,
+        "1=1 OR name",
+    );
+
+    // Nothing was changed by the rejected queries
+    let all = authors
+        .find(QueryBuilder::new())
+        .await
+        .expect("Failed to find");
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].name, "Ann");
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_joined_tables_and_aliases_are_allowed() {
+    let (pool, authors, books) = setup().await;
+    let ann = authors
+        .create(Author::new(Uuid::new_v4(), "Ann".to_string()), None)
+        .await
+        .expect("Failed to create");
+    for pages in [120, 300] {
+        books
+            .create(Book::new(Uuid::new_v4(), ann.id, pages), None)
+            .await
+            .expect("Failed to create");
+    }
+
+    let query = QueryBuilder::new()
+        .select_fields(vec![
+            SelectField::field("identifier_test_authors.name"),
+            SelectField::sum("b.pages").with_alias("total_pages"),
+        ])
+        .join(
+            JoinClause::new_on(
+                JoinType::Inner,
+                "identifier_test_books",
+                "identifier_test_authors.id",
+                "b.author_id",
+            )
+            .with_alias("b"),
+        )
+        .filter(QueryFilter::gt("b.pages", 100))
+        .group_by(
+            GroupBy::single("identifier_test_authors.name")
+                .having(QueryFilter::gt("COUNT(b.id)", 1)),
+        )
+        .order_by("total_pages", SortOrder::Desc);
+
+    let rows = authors.find_rows(query).await.expect("Failed to find rows");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["name"], json!("Ann"));
+    assert_eq!(rows[0]["total_pages"], json!(420));
+
+    // The same column through a table the query does not join is unknown
+    let unjoined = QueryBuilder::new().filter(QueryFilter::gt("identifier_test_books.pages", 100));
+    assert_rejected(
+        authors.find_rows(unjoined).await,
+        "identifier_test_books.pages",
+    );
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_json_path_filters() {
+    let (pool, _, _) = setup().await;
+    common::recreate_table::<Profile>(&pool).await;
+    let profiles = GenericStore::<Profile>::new(pool.clone(), None, None);
+    for (plan, seats) in [("premium", 5), ("basic", 1)] {
+        profiles
+            .create(
+                Profile::new(
+                    Uuid::new_v4(),
+                    json!({ "plan": { "type": plan, "seats": seats } }),
+                ),
+                None,
+            )
+            .await
+            .expect("Failed to create");
+    }
+
+    let premium = profiles
+        .find(QueryBuilder::new().filter(QueryFilter::json_path(
+            "settings",
+            &["plan", "type"],
+            QueryOperator::Eq,
+            Some(json!("premium")),
+        )))
+        .await
+        .expect("Failed to find");
+    assert_eq!(premium.len(), 1);
+    assert_eq!(premium[0].settings["plan"]["seats"], json!(5));
+
+    let counted = profiles
+        .count_where(QueryBuilder::new().filter(QueryFilter::json_path(
+            "settings",
+            &["plan", "seats"],
+            QueryOperator::In,
+            Some(json!([1, 5])),
+        )))
+        .await
+        .expect("Failed to count");
+    assert_eq!(counted, 2);
+
+    // Operators written into the field name are still rejected
+    assert_rejected(
+        profiles
+            .find(
+                QueryBuilder::new().filter(QueryFilter::eq("settings->>'plan'", json!("premium"))),
+            )
+            .await,
+        "settings->>'plan'",
+    );
+
+    common::drop_table::<Profile>(&pool).await;
+    cleanup(&pool).await;
+}
@@ -266,7 +266,7 @@ async fn test_typed_json_crud() {
     assert!(created.optional_config.is_some());
 
     let fetched = typed_store.get_by_id(&created.id).await.unwrap().unwrap();
-    assert_eq!(fetched.config.0.enabled, true);
+    assert!(fetched.config.0.enabled);
     assert_eq!(fetched.config.0.max_retries, 5);
     assert_eq!(fetched.config.0.endpoints.len(), 2);
 
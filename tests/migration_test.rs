@@ -0,0 +1,227 @@
+//! Integration tests for schema diffing migrations
+//!
+//! Two models share one table name to simulate a model evolving between
+//! deployments; `auto_migrate` must alter the existing table in place.
+
+mod common;
+
+use sqlx::PgPool;
+use storehaus::prelude::*;
+
+/// Initial version of the model
+#[model]
+#[table(name = "migration_test_model")]
+pub struct ModelV1 {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub name: String,
+
+    #[field(create, update)]
+    pub score: i32,
+}
+
+/// Evolved version: `score` widened and made optional, `nickname` added
+#[model]
+#[table(name = "migration_test_model")]
+pub struct ModelV2 {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub name: String,
+
+    #[field(create, update)]
+    pub score: Option<i64>,
+
+    #[field(create, update)]
+    pub nickname: Option<String>,
+}
+
+/// Evolved version adding a required column, which existing rows cannot fill
+#[model]
+#[table(name = "migration_test_model")]
+pub struct ModelRequiredEmail {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub name: String,
+
+    #[field(create, update)]
+    pub score: i32,
+
+    #[field(create, update)]
+    pub email: String,
+}
+
+/// Standalone model for create-only planning
+#[model]
+#[table(name = "migration_test_fresh")]
+pub struct FreshModel {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub name: String,
+}
+
+async fn setup() -> (StoreHaus, PgPool) {
+    let pool = common::connect().await;
+    (StoreHaus::from_pool(pool.clone()), pool)
+}
+
+#[tokio::test]
+async fn test_plan_for_missing_table_creates_it() {
+    let (storehaus, _pool) = setup().await;
+    sqlx::query(&FreshModel::drop_table_sql())
+        .execute(storehaus.pool())
+        .await
+        .expect("Failed to drop table");
+
+    let plan = storehaus
+        .plan_migration::<FreshModel>()
+        .await
+        .expect("Failed to plan migration");
+    assert!(plan.creates_table);
+    assert_eq!(plan.statements, vec![FreshModel::create_table_sql()]);
+}
+
+#[tokio::test]
+async fn test_auto_migrate_alters_existing_table() {
+    let (storehaus, pool) = setup().await;
+    storehaus
+        .auto_migrate::<ModelV1>(true)
+        .await
+        .expect("Failed to migrate v1");
+
+    let store = GenericStore::<ModelV1>::new(pool.clone(), None, None);
+    let created = store
+        .create(ModelV1::new(Uuid::new_v4(), "Alice".to_string(), 42), None)
+        .await
+        .expect("Failed to create record");
+
+    // Unchanged model plans nothing
+    let plan = storehaus
+        .plan_migration::<ModelV1>()
+        .await
+        .expect("Failed to plan migration");
+    assert!(
+        plan.is_empty(),
+        "unexpected statements: {:?}",
+        plan.statements
+    );
+
+    // Dry run reports the changes without applying them
+    let plan = storehaus
+        .plan_migration::<ModelV2>()
+        .await
+        .expect("Failed to plan migration");
+    assert!(!plan.creates_table);
+    assert_eq!(
+        plan.statements.len(),
+        3,
+        "statements: {:?}",
+        plan.statements
+    );
+    assert!(
+        plan.statements
+            .iter()
+            .any(|s| s.contains("ADD COLUMN \"nickname\" TEXT"))
+    );
+    assert!(
+        plan.statements
+            .iter()
+            .any(|s| s.contains("\"score\" TYPE BIGINT"))
+    );
+    assert!(
+        plan.statements
+            .iter()
+            .any(|s| s.contains("\"score\" DROP NOT NULL"))
+    );
+
+    let columns = storehaus
+        .existing_columns(ModelV2::table_name())
+        .await
+        .expect("Failed to read columns");
+    assert!(!columns.iter().any(|c| c.name == "nickname"));
+
+    // Apply and verify the existing row survived
+    storehaus
+        .auto_migrate::<ModelV2>(false)
+        .await
+        .expect("Failed to migrate v2");
+    let plan = storehaus
+        .plan_migration::<ModelV2>()
+        .await
+        .expect("Failed to plan migration");
+    assert!(
+        plan.is_empty(),
+        "unexpected statements: {:?}",
+        plan.statements
+    );
+
+    let store = GenericStore::<ModelV2>::new(pool.clone(), None, None);
+    let migrated = store
+        .get_by_id(&created.id)
+        .await
+        .expect("Failed to fetch record")
+        .expect("Record should survive migration");
+    assert_eq!(migrated.score, Some(42));
+    assert_eq!(migrated.nickname, None);
+
+    // Columns dropped from the model are reported, not removed
+    let plan = storehaus
+        .plan_migration::<ModelV1>()
+        .await
+        .expect("Failed to plan migration");
+    assert_eq!(plan.unmanaged_columns, vec!["nickname".to_string()]);
+
+    sqlx::query(&ModelV2::drop_table_sql())
+        .execute(&pool)
+        .await
+        .expect("Failed to drop table");
+}
+
+#[tokio::test]
+async fn test_auto_migrate_refuses_required_column_on_filled_table() {
+    let (storehaus, pool) = setup().await;
+    storehaus
+        .auto_migrate::<ModelV1>(true)
+        .await
+        .expect("Failed to migrate v1");
+
+    // An empty table can take the column as NOT NULL right away
+    let plan = storehaus
+        .plan_migration::<ModelRequiredEmail>()
+        .await
+        .expect("Failed to plan migration");
+    assert_eq!(
+        plan.statements,
+        vec![r#"ALTER TABLE "migration_test_model" ADD COLUMN "email" TEXT NOT NULL"#]
+    );
+
+    let store = GenericStore::<ModelV1>::new(pool.clone(), None, None);
+    store
+        .create(ModelV1::new(Uuid::new_v4(), "Alice".to_string(), 42), None)
+        .await
+        .expect("Failed to create record");
+
+    let result = storehaus.auto_migrate::<ModelRequiredEmail>(false).await;
+    assert!(
+        matches!(&result, Err(StoreHausError::InvalidMigration(message)) if message.contains("\"email\"")),
+        "unexpected result: {:?}",
+        result
+    );
+    let columns = storehaus
+        .existing_columns(ModelRequiredEmail::table_name())
+        .await
+        .expect("Failed to read columns");
+    assert!(!columns.iter().any(|c| c.name == "email"));
+
+    sqlx::query(&ModelV1::drop_table_sql())
+        .execute(&pool)
+        .await
+        .expect("Failed to drop table");
+}
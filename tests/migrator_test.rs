@@ -0,0 +1,179 @@
+//! Integration tests for the versioned migration runner
+
+mod common;
+
+use storehaus::prelude::*;
+
+const TEST_VERSIONS: [i64; 3] = [900_001, 900_002, 900_003];
+
+async fn setup() -> StoreHaus {
+    let pool = common::connect().await;
+    let storehaus = StoreHaus::from_pool(pool);
+
+    // Make sure the tracking table exists, then forget earlier runs of this test
+    storehaus
+        .applied_migrations()
+        .await
+        .expect("Failed to load applied migrations");
+    sqlx::query("DELETE FROM storehaus_migrations WHERE version = ANY($1)")
+        .bind(&TEST_VERSIONS[..])
+        .execute(storehaus.pool())
+        .await
+        .expect("Failed to reset migrations");
+    let _ = sqlx::query("DROP TABLE IF EXISTS migrator_test_items")
+        .execute(storehaus.pool())
+        .await;
+
+    storehaus
+}
+
+fn migrator() -> Migrator {
+    Migrator::new()
+        .add_migration(
+            Migration::sql(
+                TEST_VERSIONS[0],
+                "create_items",
+                "CREATE TABLE migrator_test_items (id SERIAL PRIMARY KEY, title TEXT NOT NULL)",
+            )
+            .with_down_sql("DROP TABLE migrator_test_items"),
+        )
+        .add_migration(
+            Migration::sql(
+                TEST_VERSIONS[1],
+                "add_slug",
+                "ALTER TABLE migrator_test_items ADD COLUMN slug TEXT; \
+                 INSERT INTO migrator_test_items (title) VALUES ('Hello World');",
+            )
+            .with_down_sql("ALTER TABLE migrator_test_items DROP COLUMN slug"),
+        )
+        .add_migration(
+            Migration::rust(TEST_VERSIONS[2], "backfill_slug", |conn| {
+                Box::pin(async move {
+                    sqlx::query(
+                        "UPDATE migrator_test_items SET slug = lower(replace(title, ' ', '-'))",
+                    )
+                    .execute(&mut *conn)
+                    .await?;
+                    Ok(())
+                })
+            })
+            .with_down(|conn| {
+                Box::pin(async move {
+                    sqlx::query("UPDATE migrator_test_items SET slug = NULL")
+                        .execute(&mut *conn)
+                        .await?;
+                    Ok(())
+                })
+            }),
+        )
+}
+
+#[tokio::test]
+async fn test_run_and_rollback_migrations() {
+    let storehaus = setup().await;
+    let migrator = migrator();
+
+    let pending = storehaus
+        .pending_migrations(&migrator)
+        .await
+        .expect("Failed to list pending migrations");
+    assert_eq!(pending, TEST_VERSIONS.to_vec());
+
+    let applied = storehaus
+        .run_migrations(&migrator)
+        .await
+        .expect("Failed to run migrations");
+    assert_eq!(applied, TEST_VERSIONS.to_vec());
+
+    let slug: Option<String> = sqlx::query_scalar("SELECT slug FROM migrator_test_items")
+        .fetch_one(storehaus.pool())
+        .await
+        .expect("Failed to read backfilled row");
+    assert_eq!(slug.as_deref(), Some("hello-world"));
+
+    // Re-running is a no-op
+    let applied = storehaus
+        .run_migrations(&migrator)
+        .await
+        .expect("Failed to re-run migrations");
+    assert!(applied.is_empty());
+
+    let records = storehaus
+        .applied_migrations()
+        .await
+        .expect("Failed to load applied migrations");
+    let record = records
+        .iter()
+        .find(|r| r.version == TEST_VERSIONS[0])
+        .expect("Migration should be recorded");
+    assert_eq!(record.name, "create_items");
+    assert_eq!(record.checksum, migrator.migrations()[0].checksum());
+
+    // Roll back the two most recent migrations
+    let rolled_back = storehaus
+        .rollback_migrations(&migrator, 2)
+        .await
+        .expect("Failed to roll back");
+    assert_eq!(rolled_back, vec![TEST_VERSIONS[2], TEST_VERSIONS[1]]);
+
+    let has_slug: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM information_schema.columns \
+         WHERE table_name = 'migrator_test_items' AND column_name = 'slug')",
+    )
+    .fetch_one(storehaus.pool())
+    .await
+    .expect("Failed to inspect columns");
+    assert!(!has_slug);
+
+    let rolled_back = storehaus
+        .rollback_migrations(&migrator, 1)
+        .await
+        .expect("Failed to roll back");
+    assert_eq!(rolled_back, vec![TEST_VERSIONS[0]]);
+}
+
+#[tokio::test]
+async fn test_edited_migration_is_rejected() {
+    let storehaus = setup().await;
+    let original = Migrator::new().add_migration(
+        Migration::sql(
+            TEST_VERSIONS[0],
+            "create_items",
+            "CREATE TABLE migrator_test_items (id INT)",
+        )
+        .with_down_sql("DROP TABLE migrator_test_items"),
+    );
+    storehaus
+        .run_migrations(&original)
+        .await
+        .expect("Failed to run migrations");
+
+    let edited = Migrator::new().add_migration(Migration::sql(
+        TEST_VERSIONS[0],
+        "create_items",
+        "CREATE TABLE migrator_test_items (id BIGINT)",
+    ));
+    let result = storehaus.run_migrations(&edited).await;
+    assert!(matches!(
+        result,
+        Err(StoreHausError::MigrationChecksumMismatch { .. })
+    ));
+
+    // A failing migration leaves nothing behind
+    let failing = original.clone().add_migration(Migration::sql(
+        TEST_VERSIONS[1],
+        "broken",
+        "ALTER TABLE does_not_exist ADD COLUMN x INT",
+    ));
+    assert!(storehaus.run_migrations(&failing).await.is_err());
+    let pending = storehaus
+        .pending_migrations(&failing)
+        .await
+        .expect("Failed to list pending migrations");
+    assert_eq!(pending, vec![TEST_VERSIONS[1]]);
+
+    storehaus
+        .rollback_migrations(&original, 1)
+        .await
+        .expect("Failed to roll back");
+}
@@ -0,0 +1,217 @@
+//! Integration tests for the transactional signal outbox
+
+mod common;
+
+use common::Received;
+use sqlx::PgPool;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use storehaus::prelude::*;
+
+#[model]
+#[table(name = "outbox_test_orders")]
+pub struct Order {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub item: String,
+}
+
+async fn setup(
+    fail_delivery: Arc<AtomicBool>,
+) -> (PgPool, GenericStore<Order>, Arc<EventOutbox>, Received) {
+    let pool = common::connect().await;
+
+    common::recreate_table::<Order>(&pool).await;
+    common::execute_all(&pool, ["DROP TABLE IF EXISTS storehaus_events".to_string()]).await;
+
+    // Keep failing callbacks registered so deliveries can be retried
+    let signal_manager = SignalManager::new(SignalConfig::new(5, 10, false, 3, 60, false, 300));
+    let received: Received = Arc::new(Mutex::new(Vec::new()));
+    let sink = received.clone();
+    signal_manager
+        .add_callback(move |event: DatabaseEvent| {
+            let sink = sink.clone();
+            let fail_delivery = fail_delivery.clone();
+            async move {
+                if fail_delivery.load(Ordering::SeqCst) {
+                    anyhow::bail!("consumer unavailable");
+                }
+                sink.lock().unwrap().push(event);
+                Ok(())
+            }
+        })
+        .await
+        .expect("Failed to add callback");
+
+    let outbox = Arc::new(EventOutbox::new(pool.clone(), signal_manager.clone()));
+    outbox
+        .ensure_table()
+        .await
+        .expect("Failed to create outbox");
+
+    let mut store = GenericStore::<Order>::new(pool.clone(), Some(signal_manager), None);
+    store.set_outbox(outbox.clone());
+
+    (pool, store, outbox, received)
+}
+
+async fn cleanup(pool: &PgPool) {
+    common::drop_table::<Order>(pool).await;
+    let _ = sqlx::query("DROP TABLE IF EXISTS storehaus_events")
+        .execute(pool)
+        .await;
+}
+
+#[tokio::test]
+async fn test_events_delivered_after_commit() {
+    let (pool, store, outbox, received) = setup(Arc::new(AtomicBool::new(false))).await;
+
+    let order = store
+        .create(Order::new(Uuid::new_v4(), "Lamp".to_string()), None)
+        .await
+        .expect("Failed to create order");
+    store
+        .update(&order.id, Order::new(order.id, "Desk".to_string()), None)
+        .await
+        .expect("Failed to update order");
+
+    // Nothing is emitted inline; the events wait in the outbox
+    assert!(received.lock().unwrap().is_empty());
+    assert_eq!(outbox.pending_count().await.unwrap(), 2);
+
+    assert_eq!(outbox.dispatch_pending().await.unwrap(), 2);
+    assert_eq!(outbox.pending_count().await.unwrap(), 0);
+
+    let events = received.lock().unwrap().clone();
+    assert_eq!(events.len(), 2);
+    assert!(matches!(events[0].event_type, EventType::Create));
+    assert!(matches!(events[1].event_type, EventType::Update));
+    assert!(events[0].id.is_some() && events[0].id < events[1].id);
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_rolled_back_writes_produce_no_events() {
+    let (pool, store, outbox, _) = setup(Arc::new(AtomicBool::new(false))).await;
+
+    let order = store
+        .create(Order::new(Uuid::new_v4(), "Chair".to_string()), None)
+        .await
+        .expect("Failed to create order");
+    outbox.dispatch_pending().await.unwrap();
+
+    // The second row does not exist, so the whole batch rolls back
+    let missing = Uuid::new_v4();
+    let result = store
+        .update_many(vec![
+            (order.id, Order::new(order.id, "Stool".to_string())),
+            (missing, Order::new(missing, "Bench".to_string())),
+        ])
+        .await;
+    assert!(result.is_err());
+    assert_eq!(outbox.pending_count().await.unwrap(), 0);
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_failed_delivery_is_retried() {
+    let fail_delivery = Arc::new(AtomicBool::new(true));
+    let (pool, store, outbox, received) = setup(fail_delivery.clone()).await;
+
+    store
+        .create(Order::new(Uuid::new_v4(), "Rug".to_string()), None)
+        .await
+        .expect("Failed to create order");
+
+    assert_eq!(outbox.dispatch_pending().await.unwrap(), 0);
+    assert_eq!(outbox.pending_count().await.unwrap(), 1);
+
+    // Once the consumer recovers, the background dispatcher delivers the event
+    fail_delivery.store(false, Ordering::SeqCst);
+    let dispatcher = outbox.spawn_dispatcher(std::time::Duration::from_millis(20));
+    for _ in 0..100 {
+        if !received.lock().unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    dispatcher.stop();
+
+    assert_eq!(received.lock().unwrap().len(), 1);
+    assert_eq!(outbox.pending_count().await.unwrap(), 0);
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_poison_event_is_dead_lettered() {
+    let fail_delivery = Arc::new(AtomicBool::new(true));
+    let (pool, store, outbox, received) = setup(fail_delivery.clone()).await;
+    let outbox = EventOutbox::new(pool.clone(), outbox.signal_manager().clone())
+        .with_max_attempts(2);
+
+    for item in ["Vase", "Shelf"] {
+        store
+            .create(Order::new(Uuid::new_v4(), item.to_string()), None)
+            .await
+            .expect("Failed to create order");
+    }
+
+    // The first event fails twice and is dead-lettered; the second is attempted next
+    assert_eq!(outbox.dispatch_pending().await.unwrap(), 0);
+    assert_eq!(outbox.dispatch_pending().await.unwrap(), 0);
+    assert_eq!(outbox.failed_count().await.unwrap(), 1);
+    assert_eq!(outbox.pending_count().await.unwrap(), 1);
+
+    let (attempts, last_error): (i32, Option<String>) = sqlx::query_as(
+        "SELECT attempts, last_error FROM storehaus_events WHERE failed_at IS NOT NULL",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(attempts, 2);
+    assert!(last_error.is_some());
+
+    // The queue moves past the dead letter once the consumer recovers
+    fail_delivery.store(false, Ordering::SeqCst);
+    assert_eq!(outbox.dispatch_pending().await.unwrap(), 1);
+    assert_eq!(outbox.pending_count().await.unwrap(), 0);
+
+    // Requeued dead letters are delivered again
+    assert_eq!(outbox.requeue_failed().await.unwrap(), 1);
+    assert_eq!(outbox.dispatch_pending().await.unwrap(), 1);
+    assert_eq!(outbox.failed_count().await.unwrap(), 0);
+    assert_eq!(received.lock().unwrap().len(), 2);
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_claimed_events_are_not_delivered_twice() {
+    let (pool, store, outbox, received) = setup(Arc::new(AtomicBool::new(false))).await;
+
+    store
+        .create(Order::new(Uuid::new_v4(), "Lamp".to_string()), None)
+        .await
+        .expect("Failed to create order");
+
+    // A claim of a dispatcher that stopped mid-batch holds until it expires
+    sqlx::query("UPDATE storehaus_events SET claimed_until = NOW() + INTERVAL '1 hour'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(outbox.dispatch_pending().await.unwrap(), 0);
+
+    sqlx::query("UPDATE storehaus_events SET claimed_until = NOW() - INTERVAL '1 second'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(outbox.dispatch_pending().await.unwrap(), 1);
+    assert_eq!(received.lock().unwrap().len(), 1);
+
+    cleanup(&pool).await;
+}
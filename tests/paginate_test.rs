@@ -0,0 +1,254 @@
+//! Integration tests for keyset (cursor) and numbered pagination
+
+mod common;
+
+use serde_json::json;
+use sqlx::PgPool;
+use std::sync::Arc;
+use storehaus::prelude::*;
+
+#[model]
+#[table(name = "paginate_test_posts", auto_soft_delete)]
+pub struct Post {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub author: String,
+
+    #[field(create, update)]
+    pub score: i64,
+}
+
+#[model]
+#[table(name = "paginate_test_entries")]
+pub struct Entry {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    #[serde(rename = "rank")]
+    pub score: i64,
+}
+
+async fn setup(count: usize) -> (PgPool, GenericStore<Post>) {
+    let pool = common::connect().await;
+
+    common::recreate_table::<Post>(&pool).await;
+
+    let store = GenericStore::<Post>::new(pool.clone(), None, None);
+    // Scores repeat, so the primary key has to break ties
+    let posts: Vec<_> = (0..count)
+        .map(|i| Post::new(Uuid::new_v4(), format!("author-{}", i % 3), (i % 4) as i64))
+        .collect();
+    store.create_many(posts).await.expect("Failed to create posts");
+
+    (pool, store)
+}
+
+async fn cleanup(pool: &PgPool) {
+    common::drop_table::<Post>(pool).await;
+}
+
+fn by_score() -> QueryBuilder {
+    QueryBuilder::new().order_by("score", SortOrder::Desc)
+}
+
+fn ids(posts: &[Post]) -> Vec<Uuid> {
+    posts.iter().map(|post| post.id).collect()
+}
+
+#[tokio::test]
+async fn test_paginate_forward_and_back() {
+    let (pool, store) = setup(23).await;
+
+    let expected = store
+        .find(by_score().order_by("id", SortOrder::Asc))
+        .await
+        .expect("Failed to find");
+
+    // Walk forward page by page
+    let mut pages = Vec::new();
+    let mut query = by_score();
+    loop {
+        let page = store.paginate(query, 5).await.expect("Failed to paginate");
+        let next = page.next_cursor.clone();
+        pages.push(page);
+        match next {
+            Some(cursor) => query = by_score().after(cursor),
+            None => break,
+        }
+    }
+    assert_eq!(pages.len(), 5);
+    assert!(pages[0].prev_cursor.is_none());
+    assert!(pages[..4].iter().all(|page| page.has_more));
+    assert!(!pages[4].has_more);
+    assert_eq!(pages[4].items.len(), 3);
+    let walked: Vec<Uuid> = pages.iter().flat_map(|page| ids(&page.items)).collect();
+    assert_eq!(walked, ids(&expected));
+
+    // Walk back from the last page
+    let mut query = by_score().before(pages[4].prev_cursor.clone().unwrap());
+    for expected_page in pages[..4].iter().rev() {
+        let page = store.paginate(query, 5).await.expect("Failed to paginate");
+        assert_eq!(ids(&page.items), ids(&expected_page.items));
+        query = match page.prev_cursor {
+            Some(cursor) => by_score().before(cursor),
+            None => break,
+        };
+    }
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_paginate_is_stable_under_inserts_and_filters() {
+    let (pool, store) = setup(12).await;
+    let query = || by_score().filter(QueryFilter::ne("author", json!("author-2")));
+
+    let first = store.paginate(query(), 3).await.expect("Failed to paginate");
+
+    // A row inserted before the cursor does not shift the next page
+    store
+        .create(Post::new(Uuid::new_v4(), "author-0".to_string(), 99), None)
+        .await
+        .expect("Failed to create");
+    let second = store
+        .paginate(query().after(first.next_cursor.clone().unwrap()), 3)
+        .await
+        .expect("Failed to paginate");
+
+    let expected = store
+        .find(query().order_by("id", SortOrder::Asc))
+        .await
+        .expect("Failed to find");
+    assert_eq!(ids(&second.items), ids(&expected[4..7]));
+    assert!(second.items.iter().all(|post| post.author != "author-2"));
+
+    // Soft-deleted rows are skipped
+    store.delete(&second.items[0].id).await.expect("Failed to delete");
+    let again = store
+        .paginate(query().after(first.next_cursor.unwrap()), 3)
+        .await
+        .expect("Failed to paginate");
+    assert_eq!(ids(&again.items), ids(&expected[5..8]));
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_paginate_rejects_foreign_cursors() {
+    let (pool, mut store) = setup(6).await;
+
+    let page = store.paginate(by_score(), 2).await.expect("Failed to paginate");
+    let cursor = page.next_cursor.unwrap();
+
+    let mut tampered = cursor.clone();
+    tampered.insert(0, 'x');
+    let other_ordering = QueryBuilder::new().order_by("author", SortOrder::Asc);
+    for query in [by_score().after(tampered), other_ordering.after(cursor.clone())] {
+        let err = store.paginate(query, 2).await.unwrap_err();
+        assert!(matches!(err, StorehausError::ValidationError { .. }));
+    }
+
+    // Cursors only verify with the secret they were signed with
+    store.set_cursor_secret("shared secret");
+    let err = store.paginate(by_score().after(cursor), 2).await.unwrap_err();
+    assert!(matches!(err, StorehausError::ValidationError { .. }));
+
+    let err = store.paginate(by_score().offset(2), 2).await.unwrap_err();
+    assert!(matches!(err, StorehausError::ValidationError { .. }));
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_find_page_counts_in_one_query() {
+    let (pool, store) = setup(23).await;
+    let query = || by_score().order_by("id", SortOrder::Asc);
+
+    let expected = store.find(query()).await.expect("Failed to find");
+
+    let first = store.find_page(query(), 1, 10).await.expect("Failed to page");
+    assert_eq!(ids(&first.items), ids(&expected[..10]));
+    assert_eq!((first.total, first.page, first.per_page, first.total_pages), (23, 1, 10, 3));
+    assert!(first.has_next_page() && !first.has_prev_page());
+
+    let last = store.find_page(query(), 3, 10).await.expect("Failed to page");
+    assert_eq!(ids(&last.items), ids(&expected[20..]));
+    assert!(!last.has_next_page());
+
+    // The total follows filters and soft delete
+    let deleted = expected.iter().find(|post| post.author == "author-1").unwrap();
+    store.delete(&deleted.id).await.expect("Failed to delete");
+    let filtered = query().filter(QueryFilter::eq("author", json!("author-1")));
+    let page = store.find_page(filtered.clone(), 1, 5).await.expect("Failed to page");
+    let matching = store.count_where(filtered.clone()).await.unwrap();
+    assert_eq!((page.total, matching), (7, 7));
+    assert_eq!(page.total_pages, 2);
+    assert!(page.items.iter().all(|post| post.author == "author-1"));
+
+    // Past the end there are no items but still a total
+    let beyond = store.find_page(filtered, 9, 5).await.expect("Failed to page");
+    assert!(beyond.items.is_empty());
+    assert_eq!(beyond.total, matching);
+
+    let err = store.find_page(query(), 0, 10).await.unwrap_err();
+    assert!(matches!(err, StorehausError::ValidationError { .. }));
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_find_page_past_the_end_skips_the_query_cache() {
+    let (pool, store) = setup(4).await;
+    let cache_manager = Arc::new(
+        CacheManager::new(CacheConfig::memory(100)).expect("Failed to create cache manager"),
+    );
+    let cache_params = CacheParams::new(cache_manager, 60, "paginate_test").with_query_cache(true);
+    let cached = GenericStore::<Post>::new(pool.clone(), None, Some(cache_params));
+
+    assert_eq!(cached.count_where(by_score()).await.unwrap(), 4);
+
+    // Written through another store, so the cached count is now stale
+    store
+        .create(Post::new(Uuid::new_v4(), "author-9".to_string(), 1), None)
+        .await
+        .expect("Failed to create");
+    assert_eq!(cached.count_where(by_score()).await.unwrap(), 4);
+
+    let beyond = cached.find_page(by_score(), 9, 5).await.expect("Failed to page");
+    assert!(beyond.items.is_empty());
+    assert_eq!(beyond.total, 5);
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_paginate_reads_cursor_values_from_columns() {
+    let (pool, _) = setup(0).await;
+    common::recreate_table::<Entry>(&pool).await;
+    let store = GenericStore::<Entry>::new(pool.clone(), None, None);
+    let entries: Vec<_> = (0..7).map(|i| Entry::new(Uuid::new_v4(), i)).collect();
+    store.create_many(entries).await.expect("Failed to create entries");
+
+    // `score` serializes as `rank`, but cursors still carry the column value
+    let by_rank = || QueryBuilder::new().order_by("score", SortOrder::Asc);
+    let first = store.paginate(by_rank(), 3).await.expect("Failed to paginate");
+    let second = store
+        .paginate(by_rank().after(first.next_cursor.unwrap()), 3)
+        .await
+        .expect("Failed to paginate");
+    let scores: Vec<_> = second.items.iter().map(|entry| entry.score).collect();
+    assert_eq!(scores, vec![3, 4, 5]);
+
+    let back = store
+        .paginate(by_rank().before(second.prev_cursor.unwrap()), 3)
+        .await
+        .expect("Failed to paginate");
+    let scores: Vec<_> = back.items.iter().map(|entry| entry.score).collect();
+    assert_eq!(scores, vec![0, 1, 2]);
+
+    common::drop_table::<Entry>(&pool).await;
+    cleanup(&pool).await;
+}
@@ -0,0 +1,163 @@
+//! Integration tests for streaming reads (sqlx fetch and server-side cursors)
+
+mod common;
+
+use futures::{StreamExt, TryStreamExt};
+use serde_json::json;
+use sqlx::PgPool;
+use storehaus::prelude::*;
+
+#[model]
+#[table(name = "stream_test_events", auto_soft_delete)]
+pub struct Event {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub kind: String,
+
+    #[field(create, update)]
+    pub position: i64,
+}
+
+async fn setup(count: usize) -> (PgPool, GenericStore<Event>) {
+    let pool = common::connect().await;
+
+    common::recreate_table::<Event>(&pool).await;
+
+    let store = GenericStore::<Event>::new(pool.clone(), None, None);
+    let events: Vec<_> = (0..count)
+        .map(|i| {
+            let kind = if i % 2 == 0 { "click" } else { "view" };
+            Event::new(Uuid::new_v4(), kind.to_string(), i as i64)
+        })
+        .collect();
+    store.copy_in(&events).await.expect("Failed to load events");
+
+    (pool, store)
+}
+
+async fn cleanup(pool: &PgPool) {
+    common::drop_table::<Event>(pool).await;
+}
+
+fn clicks() -> QueryBuilder {
+    QueryBuilder::new()
+        .filter(QueryFilter::eq("kind", json!("click")))
+        .order_by("position", SortOrder::Asc)
+}
+
+#[tokio::test]
+async fn test_find_stream_matches_find() {
+    let (pool, store) = setup(2_000).await;
+
+    let streamed: Vec<Event> = store
+        .find_stream(clicks())
+        .try_collect()
+        .await
+        .expect("Failed to stream");
+    let found = store.find(clicks()).await.expect("Failed to find");
+
+    assert_eq!(streamed.len(), 1_000);
+    assert_eq!(
+        streamed.iter().map(|e| e.position).collect::<Vec<_>>(),
+        found.iter().map(|e| e.position).collect::<Vec<_>>()
+    );
+
+    // list_all_stream honors soft delete like list_all
+    store
+        .delete(&streamed[0].id)
+        .await
+        .expect("Failed to soft delete");
+    let all: Vec<Event> = store
+        .list_all_stream()
+        .try_collect()
+        .await
+        .expect("Failed to stream");
+    assert_eq!(all.len(), 1_999);
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_cursor_stream_reads_in_batches() {
+    let (pool, store) = setup(2_500).await;
+
+    // Batch boundaries that do and do not divide the row count
+    for batch_size in [100, 333, 5_000] {
+        let positions: Vec<i64> = store
+            .find_stream_with(clicks(), StreamMode::Cursor { batch_size })
+            .map_ok(|event| event.position)
+            .try_collect()
+            .await
+            .expect("Failed to stream");
+        assert_eq!(positions.len(), 1_250);
+        assert!(positions.windows(2).all(|pair| pair[0] + 2 == pair[1]));
+    }
+
+    let empty = QueryBuilder::new().filter(QueryFilter::eq("kind", json!("purchase")));
+    let none: Vec<Event> = store
+        .find_stream_with(empty, StreamMode::cursor())
+        .try_collect()
+        .await
+        .expect("Failed to stream");
+    assert!(none.is_empty());
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_dropped_stream_releases_connection() {
+    let (pool, _) = setup(2_000).await;
+
+    // With a single connection, a reader left running would starve the count below
+    let single = sqlx::postgres::PgPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(std::time::Duration::from_secs(2))
+        .connect(&std::env::var("DATABASE_URL").unwrap())
+        .await
+        .expect("Failed to connect to database");
+    let store = GenericStore::<Event>::new(single, None, None);
+
+    for mode in [StreamMode::Fetch, StreamMode::Cursor { batch_size: 10 }] {
+        let first: Vec<Event> = store
+            .find_stream_with(clicks(), mode)
+            .take(3)
+            .try_collect()
+            .await
+            .expect("Failed to stream");
+        assert_eq!(first.len(), 3);
+        assert_eq!(store.count().await.unwrap(), 2_000);
+    }
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_stream_reports_query_errors() {
+    let (pool, store) = setup(10).await;
+
+    let query = QueryBuilder::new().filter(QueryFilter::eq("no_such_column", json!(1)));
+    for mode in [StreamMode::Fetch, StreamMode::cursor()] {
+        let result: Result<Vec<Event>, _> =
+            store.find_stream_with(query.clone(), mode).try_collect().await;
+        assert!(result.is_err());
+    }
+
+    cleanup(&pool).await;
+}
+
+#[test]
+fn test_find_stream_can_be_built_outside_a_runtime() {
+    let runtime = tokio::runtime::Runtime::new().expect("Failed to start runtime");
+    let (pool, store) = runtime.block_on(setup(10));
+
+    // Nothing is spawned until the stream is polled
+    let stream = store.find_stream(clicks());
+    let streamed: Vec<Event> = runtime
+        .block_on(stream.try_collect())
+        .expect("Failed to stream");
+    assert_eq!(streamed.len(), 5);
+
+    runtime.block_on(cleanup(&pool));
+}
@@ -0,0 +1,278 @@
+//! Integration tests for store operations on GenericStoreTransaction
+//!
+//! Uses the in-memory cache backend, so only PostgreSQL is required.
+
+mod common;
+
+use common::Received;
+use serde_json::json;
+use sqlx::PgPool;
+use std::sync::Arc;
+use storehaus::prelude::*;
+
+#[model]
+#[table(name = "transaction_test_accounts")]
+pub struct Account {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub owner: String,
+
+    #[field(create, update)]
+    pub balance: i64,
+}
+
+const PREFIX: &str = "transaction_test";
+
+async fn setup() -> (PgPool, GenericStore<Account>, Arc<CacheManager>, Received) {
+    let pool = common::connect().await;
+
+    common::recreate_table::<Account>(&pool).await;
+
+    let (signal_manager, received) = common::recording_signals().await;
+
+    let cache_manager = Arc::new(
+        CacheManager::new(CacheConfig::memory(100)).expect("Failed to create cache manager"),
+    );
+    let cache_params = CacheParams::new(cache_manager.clone(), 60, PREFIX)
+        .with_write_mode(CacheWriteMode::WriteThrough);
+    let store =
+        GenericStore::<Account>::new(pool.clone(), Some(signal_manager), Some(cache_params));
+
+    (pool, store, cache_manager, received)
+}
+
+async fn cleanup(pool: &PgPool) {
+    common::drop_table::<Account>(pool).await;
+}
+
+async fn cached_balance(cache: &CacheManager, id: &Uuid) -> Option<i64> {
+    cache
+        .get_record::<Account>(PREFIX, Account::table_name(), &id.to_string())
+        .await
+        .unwrap()
+        .map(|account| account.balance)
+}
+
+#[tokio::test]
+async fn test_effects_applied_on_commit() {
+    let (pool, store, cache, received) = setup().await;
+
+    let alice = store
+        .create(Account::new(Uuid::new_v4(), "alice".to_string(), 100), None)
+        .await
+        .expect("Failed to create account");
+    store.get_by_id(&alice.id).await.expect("Failed to get");
+    received.lock().unwrap().clear();
+
+    let mut tx = store.begin_transaction().await.expect("Failed to begin");
+    let bob = tx
+        .create(Account::new(Uuid::new_v4(), "bob".to_string(), 0), None)
+        .await
+        .expect("Failed to create account");
+    tx.update(
+        &alice.id,
+        Account::new(alice.id, "alice".to_string(), 60),
+        None,
+    )
+    .await
+    .expect("Failed to update account");
+    tx.update_where(
+        QueryBuilder::new()
+            .filter(QueryFilter::eq("owner", json!("bob")))
+            .update(UpdateSet::new().increment("balance", json!(40))),
+        None,
+    )
+    .await
+    .expect("Failed to update accounts");
+
+    // Reads inside the transaction see its changes
+    let found = tx
+        .find(QueryBuilder::new().filter(QueryFilter::eq("balance", json!(40))))
+        .await
+        .expect("Failed to find");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].id, bob.id);
+
+    // Nothing leaks before commit
+    assert!(received.lock().unwrap().is_empty());
+    assert_eq!(cached_balance(&cache, &alice.id).await, Some(100));
+
+    tx.commit().await.expect("Failed to commit");
+
+    let events = received.lock().unwrap().clone();
+    assert_eq!(events.len(), 3);
+    assert!(matches!(events[0].event_type, EventType::Create));
+    assert!(matches!(events[1].event_type, EventType::Update));
+    assert!(matches!(events[2].event_type, EventType::Update));
+    assert_eq!(cached_balance(&cache, &alice.id).await, Some(60));
+    assert_eq!(cached_balance(&cache, &bob.id).await, Some(40));
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_effects_discarded_on_rollback() {
+    let (pool, store, cache, received) = setup().await;
+
+    let alice = store
+        .create(Account::new(Uuid::new_v4(), "alice".to_string(), 100), None)
+        .await
+        .expect("Failed to create account");
+    store.get_by_id(&alice.id).await.expect("Failed to get");
+    received.lock().unwrap().clear();
+
+    let mut tx = store.begin_transaction().await.expect("Failed to begin");
+    tx.update(
+        &alice.id,
+        Account::new(alice.id, "alice".to_string(), 0),
+        None,
+    )
+    .await
+    .expect("Failed to update account");
+    assert!(tx.delete(&alice.id).await.expect("Failed to delete"));
+    assert!(tx.get_by_id(&alice.id).await.unwrap().is_none());
+    tx.rollback().await.expect("Failed to roll back");
+
+    // Dropping an unfinished transaction rolls it back as well
+    {
+        let mut tx = store.begin_transaction().await.expect("Failed to begin");
+        tx.delete_where(QueryBuilder::new().filter(QueryFilter::eq("owner", json!("alice"))))
+            .await
+            .expect("Failed to delete");
+    }
+
+    assert!(received.lock().unwrap().is_empty());
+    assert_eq!(cached_balance(&cache, &alice.id).await, Some(100));
+    assert_eq!(
+        store.count().await.expect("Failed to count"),
+        1,
+        "rolled-back changes must not be visible"
+    );
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_savepoint_discards_failed_part() {
+    let (pool, store, _, received) = setup().await;
+
+    let mut tx = store.begin_transaction().await.expect("Failed to begin");
+    let alice = tx
+        .create(Account::new(Uuid::new_v4(), "alice".to_string(), 100), None)
+        .await
+        .expect("Failed to create account");
+
+    let savepoint = tx.savepoint().await.expect("Failed to create savepoint");
+    tx.update(
+        &alice.id,
+        Account::new(alice.id, "alice".to_string(), 0),
+        None,
+    )
+    .await
+    .expect("Failed to update account");
+    let failed = sqlx::query("SELECT 1 / 0")
+        .execute(&mut **tx.as_mut())
+        .await;
+    assert!(failed.is_err());
+
+    // The failed statement aborted the transaction until the rollback to the savepoint
+    tx.rollback_to(&savepoint)
+        .await
+        .expect("Failed to roll back to savepoint");
+    tx.create(Account::new(Uuid::new_v4(), "bob".to_string(), 0), None)
+        .await
+        .expect("Failed to create account");
+    tx.commit().await.expect("Failed to commit");
+
+    let events = received.lock().unwrap().clone();
+    assert_eq!(events.len(), 2);
+    assert!(
+        events
+            .iter()
+            .all(|event| matches!(event.event_type, EventType::Create))
+    );
+    assert_eq!(store.count().await.unwrap(), 2);
+    assert_eq!(
+        store.get_by_id(&alice.id).await.unwrap().unwrap().balance,
+        100
+    );
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_read_only_transaction_rejects_writes() {
+    let (pool, store, _, _) = setup().await;
+
+    let alice = store
+        .create(Account::new(Uuid::new_v4(), "alice".to_string(), 100), None)
+        .await
+        .expect("Failed to create account");
+
+    let mut tx = store
+        .begin_transaction_with(TransactionOptions::serializable().read_only().deferrable())
+        .await
+        .expect("Failed to begin");
+    assert!(tx.get_by_id(&alice.id).await.unwrap().is_some());
+    assert!(
+        tx.create(Account::new(Uuid::new_v4(), "bob".to_string(), 0), None)
+            .await
+            .is_err()
+    );
+    tx.rollback().await.expect("Failed to roll back");
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_locked_rows_are_skipped_or_rejected() {
+    let (pool, store, _, _) = setup().await;
+
+    for owner in ["alice", "bob"] {
+        store
+            .create(Account::new(Uuid::new_v4(), owner.to_string(), 100), None)
+            .await
+            .expect("Failed to create account");
+    }
+
+    let mut holder = store.begin_transaction().await.expect("Failed to begin");
+    let locked = holder
+        .find_one(
+            QueryBuilder::new()
+                .filter(QueryFilter::eq("owner", json!("alice")))
+                .for_update(),
+        )
+        .await
+        .expect("Failed to lock")
+        .expect("Account missing");
+
+    let mut worker = store.begin_transaction().await.expect("Failed to begin");
+    let available = worker
+        .find(QueryBuilder::new().for_update().skip_locked())
+        .await
+        .expect("Failed to find");
+    assert_eq!(available.len(), 1);
+    assert_eq!(available[0].owner, "bob");
+    worker.rollback().await.expect("Failed to roll back");
+
+    let mut worker = store.begin_transaction().await.expect("Failed to begin");
+    // Plain reads are not blocked by row locks
+    assert!(worker.get_by_id(&locked.id).await.unwrap().is_some());
+    assert!(
+        worker
+            .find(
+                QueryBuilder::new()
+                    .filter(QueryFilter::eq("owner", json!("alice")))
+                    .for_update()
+                    .nowait()
+            )
+            .await
+            .is_err()
+    );
+    worker.rollback().await.expect("Failed to roll back");
+
+    holder.commit().await.expect("Failed to commit");
+    cleanup(&pool).await;
+}
@@ -0,0 +1,147 @@
+//! Integration tests for binding filter and update values by column type
+
+mod common;
+
+use serde_json::json;
+use sqlx::types::Decimal;
+use sqlx::PgPool;
+use std::str::FromStr;
+use storehaus::prelude::*;
+
+#[model]
+#[table(name = "typed_binding_test_entries")]
+pub struct Entry {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub label: String,
+
+    #[field(create, update)]
+    pub amount: Decimal,
+
+    #[field(create, update)]
+    pub hits: i64,
+}
+
+async fn setup() -> (PgPool, GenericStore<Entry>) {
+    let pool = common::connect().await;
+
+    common::recreate_table::<Entry>(&pool).await;
+
+    (pool.clone(), GenericStore::<Entry>::new(pool, None, None))
+}
+
+async fn cleanup(pool: &PgPool) {
+    common::drop_table::<Entry>(pool).await;
+}
+
+fn decimal(digits: &str) -> Decimal {
+    Decimal::from_str(digits).expect("Invalid decimal")
+}
+
+#[tokio::test]
+async fn test_text_columns_are_not_parsed() {
+    let (pool, store) = setup().await;
+
+    // Strings that look like timestamps or uuids stay text for text columns
+    let uuid_label = Uuid::new_v4().to_string();
+    for label in ["2024-01-01T00:00:00Z", uuid_label.as_str(), "plain"] {
+        store
+            .create(Entry::new(Uuid::new_v4(), label.to_string(), decimal("1"), 0), None)
+            .await
+            .expect("Failed to create");
+    }
+
+    for label in ["2024-01-01T00:00:00Z", uuid_label.as_str()] {
+        let found = store
+            .find(QueryBuilder::new().filter(QueryFilter::eq("label", json!(label))))
+            .await
+            .expect("Failed to find");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].label, label);
+    }
+
+    let count = store
+        .count_where(QueryBuilder::new().filter(QueryFilter::in_list(
+            "label",
+            vec!["2024-01-01T00:00:00Z", "plain"],
+        )))
+        .await
+        .expect("Failed to count");
+    assert_eq!(count, 2);
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_numeric_and_bigint_values_keep_precision() {
+    let (pool, store) = setup().await;
+
+    let precise = decimal("12345678901234567.0123456789");
+    let entry = store
+        .create(Entry::new(Uuid::new_v4(), "precise".to_string(), precise, 0), None)
+        .await
+        .expect("Failed to create");
+    store
+        .create(
+            Entry::new(
+                Uuid::new_v4(),
+                "nearby".to_string(),
+                decimal("12345678901234567.0123456788"),
+                0,
+            ),
+            None,
+        )
+        .await
+        .expect("Failed to create");
+
+    // f64 cannot tell these amounts apart; NUMERIC text binding can
+    let found = store
+        .find(QueryBuilder::new().filter(QueryFilter::eq("amount", precise)))
+        .await
+        .expect("Failed to find");
+    assert_eq!(found.iter().map(|e| e.id).collect::<Vec<_>>(), vec![entry.id]);
+
+    let big = i64::from(i32::MAX) * 4;
+    let updated = store
+        .update_where(
+            QueryBuilder::new()
+                .filter(QueryFilter::eq("id", entry.id))
+                .update(
+                    UpdateSet::new()
+                        .increment("amount", PostgresValue::Decimal("0.0000000001".to_string()))
+                        .set("hits", big),
+                ),
+            None,
+        )
+        .await
+        .expect("Failed to update");
+    assert_eq!(updated[0].amount, decimal("12345678901234567.0123456790"));
+    assert_eq!(updated[0].hits, big);
+
+    let over = store
+        .count_where(QueryBuilder::new().filter(QueryFilter::gt("hits", json!(i32::MAX))))
+        .await
+        .expect("Failed to count");
+    assert_eq!(over, 1);
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_values_of_the_wrong_type_are_rejected() {
+    let (pool, store) = setup().await;
+
+    let queries = [
+        QueryBuilder::new().filter(QueryFilter::eq("hits", "many")),
+        QueryBuilder::new().filter(QueryFilter::eq("id", json!("not-a-uuid"))),
+        QueryBuilder::new().filter(QueryFilter::eq("label", 5)),
+    ];
+    for query in queries {
+        let err = store.find(query).await.unwrap_err();
+        assert!(matches!(err, StorehausError::ValidationError { .. }), "{:?}", err);
+    }
+
+    cleanup(&pool).await;
+}
@@ -0,0 +1,106 @@
+//! Integration tests for the typed column handles generated by `#[model]`
+
+mod common;
+
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use storehaus::prelude::*;
+
+#[model]
+#[table(name = "typed_columns_test_members")]
+pub struct Member {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub email: String,
+
+    #[field(create, update)]
+    pub nickname: Option<String>,
+
+    #[field(create, update)]
+    pub logins: i64,
+
+    #[field(create, update)]
+    pub joined_on: NaiveDate,
+}
+
+async fn setup() -> (PgPool, GenericStore<Member>) {
+    let pool = common::connect().await;
+
+    common::recreate_table::<Member>(&pool).await;
+
+    (pool.clone(), GenericStore::<Member>::new(pool, None, None))
+}
+
+async fn cleanup(pool: &PgPool) {
+    common::drop_table::<Member>(pool).await;
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("Invalid date")
+}
+
+#[tokio::test]
+async fn test_typed_columns_filter_by_field_type() {
+    let (pool, store) = setup().await;
+    let members = [
+        ("ann@example.com", Some("ann"), 3, date(2024, 1, 15)),
+        ("bob@example.com", None, 12, date(2024, 3, 1)),
+        ("cy@example.com", Some("cy"), 40, date(2023, 11, 30)),
+    ];
+    for (email, nickname, logins, joined_on) in members {
+        store
+            .create(
+                Member::new(
+                    Uuid::new_v4(),
+                    email.to_string(),
+                    nickname.map(str::to_string),
+                    logins,
+                    joined_on,
+                ),
+                None,
+            )
+            .await
+            .expect("Failed to create");
+    }
+
+    let columns = Member::columns();
+    let emails = |found: Vec<Member>| {
+        let mut emails: Vec<_> = found.into_iter().map(|m| m.email).collect();
+        emails.sort();
+        emails
+    };
+
+    let found = store
+        .find(
+            QueryBuilder::new()
+                .filter(columns.logins.gt(5))
+                .filter(columns.joined_on.gte(date(2024, 1, 1))),
+        )
+        .await
+        .expect("Failed to find");
+    assert_eq!(emails(found), vec!["bob@example.com"]);
+
+    let found = store
+        .find(QueryBuilder::new().filter(columns.nickname.is_null()))
+        .await
+        .expect("Failed to find");
+    assert_eq!(emails(found), vec!["bob@example.com"]);
+
+    let found = store
+        .find(
+            QueryBuilder::new()
+                .filter(columns.email.in_(["ann@example.com", "cy@example.com"]))
+                .filter(columns.nickname.like("c%"))
+                .order_by(columns.logins.name(), SortOrder::Desc),
+        )
+        .await
+        .expect("Failed to find");
+    assert_eq!(emails(found), vec!["cy@example.com"]);
+
+    assert_eq!(columns.email.name(), "email");
+    assert_eq!(columns.email.qualified(), "typed_columns_test_members.email");
+
+    cleanup(&pool).await;
+}
@@ -0,0 +1,276 @@
+//! Integration tests for cross-store units of work
+
+mod common;
+
+use common::Received;
+use serde_json::json;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use storehaus::prelude::*;
+
+#[model]
+#[table(name = "uow_test_orders")]
+pub struct Order {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub sku: String,
+}
+
+#[model]
+#[table(name = "uow_test_stock")]
+pub struct Stock {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub sku: String,
+
+    #[field(create, update)]
+    pub quantity: i32,
+}
+
+async fn setup() -> (StoreHaus, Received) {
+    let pool = common::connect().await;
+
+    common::recreate_table::<Order>(&pool).await;
+    common::recreate_table::<Stock>(&pool).await;
+
+    let (signal_manager, received) = common::recording_signals().await;
+
+    let stock = GenericStore::<Stock>::new(pool.clone(), Some(signal_manager.clone()), None);
+    stock
+        .create(Stock::new(Uuid::new_v4(), "LAMP".to_string(), 5), None)
+        .await
+        .expect("Failed to create stock");
+    received.lock().unwrap().clear();
+
+    let mut storehaus = StoreHaus::from_pool(pool.clone());
+    storehaus
+        .register_store(
+            "orders".to_string(),
+            GenericStore::<Order>::new(pool, Some(signal_manager), None),
+        )
+        .expect("Failed to register orders");
+    storehaus
+        .register_store("stock".to_string(), stock)
+        .expect("Failed to register stock");
+
+    (storehaus, received)
+}
+
+async fn cleanup(storehaus: &StoreHaus) {
+    common::drop_table::<Order>(storehaus.pool()).await;
+    common::drop_table::<Stock>(storehaus.pool()).await;
+}
+
+fn reserve_lamp() -> QueryBuilder {
+    QueryBuilder::new()
+        .filter(QueryFilter::eq("sku", json!("LAMP")))
+        .update(UpdateSet::new().decrement("quantity", json!(1)))
+}
+
+async fn lamp_quantity(storehaus: &StoreHaus) -> i32 {
+    storehaus
+        .get_store::<GenericStore<Stock>>("stock")
+        .unwrap()
+        .find_one(QueryBuilder::new().filter(QueryFilter::eq("sku", json!("LAMP"))))
+        .await
+        .expect("Failed to load stock")
+        .expect("Stock row missing")
+        .quantity
+}
+
+async fn order_count(storehaus: &StoreHaus) -> i64 {
+    storehaus
+        .get_store::<GenericStore<Order>>("orders")
+        .unwrap()
+        .count()
+        .await
+        .expect("Failed to count orders")
+}
+
+#[tokio::test]
+async fn test_commits_across_stores() {
+    let (storehaus, received) = setup().await;
+
+    let order = storehaus
+        .transaction(|uow| {
+            Box::pin(async move {
+                let order = uow
+                    .store::<Order>("orders")?
+                    .create(Order::new(Uuid::new_v4(), "LAMP".to_string()), None)
+                    .await?;
+                uow.store::<Stock>("stock")?
+                    .update_where(reserve_lamp(), None)
+                    .await?;
+
+                // Signals wait for the commit
+                Ok(order)
+            })
+        })
+        .await
+        .expect("Unit of work failed");
+
+    assert_eq!(order.sku, "LAMP");
+    assert_eq!(order_count(&storehaus).await, 1);
+    assert_eq!(lamp_quantity(&storehaus).await, 4);
+
+    let events = received.lock().unwrap().clone();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].table_name, "uow_test_orders");
+    assert_eq!(events[1].table_name, "uow_test_stock");
+
+    cleanup(&storehaus).await;
+}
+
+#[tokio::test]
+async fn test_rolls_back_all_stores_on_error() {
+    let (storehaus, received) = setup().await;
+
+    let result: Result<(), StorehausError> = storehaus
+        .transaction(|uow| {
+            Box::pin(async move {
+                uow.store::<Order>("orders")?
+                    .create(Order::new(Uuid::new_v4(), "LAMP".to_string()), None)
+                    .await?;
+                uow.store::<Stock>("stock")?
+                    .update_where(reserve_lamp(), None)
+                    .await?;
+                Err(StorehausError::validation(
+                    "uow_test_orders",
+                    "sku",
+                    "payment declined",
+                ))
+            })
+        })
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(StorehausError::ValidationError { .. })
+    ));
+    assert_eq!(order_count(&storehaus).await, 0);
+    assert_eq!(lamp_quantity(&storehaus).await, 5);
+    assert!(received.lock().unwrap().is_empty());
+
+    // Unknown stores are reported instead of panicking
+    let missing = storehaus
+        .transaction(|uow| {
+            Box::pin(async move {
+                uow.store::<Order>("payments")?;
+                Ok(())
+            })
+        })
+        .await;
+    assert!(matches!(
+        missing,
+        Err(StorehausError::InvalidConfiguration { .. })
+    ));
+
+    cleanup(&storehaus).await;
+}
+
+#[tokio::test]
+async fn test_retries_only_rolled_back_attempts() {
+    let (mut storehaus, received) = setup().await;
+    storehaus.set_transaction_retry(
+        TransactionRetry::new(3).with_base_delay(std::time::Duration::from_millis(1)),
+    );
+
+    let calls = Arc::new(AtomicU32::new(0));
+    let attempts = storehaus
+        .transaction(|uow| {
+            let calls = calls.clone();
+            Box::pin(async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                uow.store::<Order>("orders")?
+                    .create(Order::new(Uuid::new_v4(), "LAMP".to_string()), None)
+                    .await?;
+
+                // Fail the first attempt with the error of a lost serialization race
+                if uow.attempt() == 1 {
+                    sqlx::query("DO $$ BEGIN RAISE EXCEPTION USING ERRCODE = '40001'; END $$")
+                        .execute(&mut **uow.transaction())
+                        .await
+                        .map_err(|e| StorehausError::database_operation("uow", "raise", e))?;
+                }
+                Ok(uow.attempt())
+            })
+        })
+        .await
+        .expect("Unit of work failed");
+
+    assert_eq!(attempts, 2);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    // The first attempt's insert and signal were discarded
+    assert_eq!(order_count(&storehaus).await, 1);
+    assert_eq!(received.lock().unwrap().len(), 1);
+
+    // Errors that don't report a rollback are not retried: a commit that lost
+    // its connection may have been applied, and cache failures never need one
+    let failures: Vec<fn() -> StorehausError> = vec![
+        || StorehausError::not_found("uow_test_orders", "1"),
+        || {
+            let io = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset");
+            StorehausError::transaction("commit", sqlx::Error::Io(io))
+        },
+        || StorehausError::cache_operation("get", None, "unavailable".into()),
+    ];
+    for failure in failures {
+        calls.store(0, Ordering::SeqCst);
+        let result: Result<(), StorehausError> = storehaus
+            .transaction(|_uow| {
+                let calls = calls.clone();
+                Box::pin(async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Err(failure())
+                })
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    cleanup(&storehaus).await;
+}
+
+#[tokio::test]
+async fn test_serialization_failure_is_retried() {
+    let (storehaus, _) = setup().await;
+    let pool = storehaus.pool().clone();
+
+    let attempts = storehaus
+        .transaction_with(TransactionOptions::serializable(), |uow| {
+            let pool = pool.clone();
+            Box::pin(async move {
+                let stock = uow
+                    .store::<Stock>("stock")?
+                    .find_one(QueryBuilder::new().filter(QueryFilter::eq("sku", json!("LAMP"))))
+                    .await?
+                    .expect("Stock row missing");
+
+                // Another client changes the row after this transaction took its snapshot
+                if uow.attempt() == 1 {
+                    sqlx::query("UPDATE uow_test_stock SET quantity = quantity - 1 WHERE id = $1")
+                        .bind(stock.id)
+                        .execute(&pool)
+                        .await
+                        .expect("Failed to update concurrently");
+                }
+
+                uow.store::<Stock>("stock")?
+                    .update_where(reserve_lamp(), None)
+                    .await?;
+                Ok(uow.attempt())
+            })
+        })
+        .await
+        .expect("Unit of work failed");
+
+    assert_eq!(attempts, 2);
+    assert_eq!(lamp_quantity(&storehaus).await, 3);
+
+    cleanup(&storehaus).await;
+}
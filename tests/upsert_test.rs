@@ -0,0 +1,156 @@
+//! Integration tests for upserts (INSERT ... ON CONFLICT)
+
+mod common;
+
+use common::Received;
+use sqlx::PgPool;
+use storehaus::prelude::*;
+
+#[model]
+#[table(name = "upsert_test_members")]
+#[unique(team, handle)]
+pub struct Member {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    #[unique]
+    pub email: String,
+
+    #[field(create, update)]
+    pub name: String,
+
+    #[field(create)]
+    pub team: String,
+
+    #[field(create)]
+    pub handle: String,
+}
+
+async fn setup() -> (PgPool, GenericStore<Member>, Received) {
+    let pool = common::connect().await;
+
+    common::recreate_table::<Member>(&pool).await;
+
+    let (signal_manager, received) = common::recording_signals().await;
+
+    let store = GenericStore::<Member>::new(pool.clone(), Some(signal_manager), None);
+    (pool, store, received)
+}
+
+async fn cleanup(pool: &PgPool) {
+    common::drop_table::<Member>(pool).await;
+}
+
+fn member(email: &str, name: &str, handle: &str) -> Member {
+    Member::new(
+        Uuid::new_v4(),
+        email.to_string(),
+        name.to_string(),
+        "core".to_string(),
+        handle.to_string(),
+    )
+}
+
+#[tokio::test]
+async fn test_upsert_by_primary_key() {
+    let (pool, store, received) = setup().await;
+
+    let ada = member("ada@example.com", "Ada", "ada");
+    let created = store
+        .upsert(ada.clone(), OnConflict::primary_key(), None)
+        .await
+        .expect("Failed to upsert");
+    assert!(created.is_created());
+    assert_eq!(created.record().unwrap().id, ada.id);
+
+    let mut renamed = ada.clone();
+    renamed.name = "Ada Lovelace".to_string();
+    let updated = store
+        .upsert(renamed, OnConflict::primary_key(), None)
+        .await
+        .expect("Failed to upsert");
+    assert!(updated.is_updated());
+    assert_eq!(updated.into_record().unwrap().name, "Ada Lovelace");
+    assert_eq!(store.count().await.unwrap(), 1);
+
+    let events = received.lock().unwrap().clone();
+    assert_eq!(events.len(), 2);
+    assert!(matches!(events[0].event_type, EventType::Create));
+    assert!(matches!(events[1].event_type, EventType::Update));
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_upsert_by_unique_columns() {
+    let (pool, store, received) = setup().await;
+
+    let original = store
+        .upsert(
+            member("grace@example.com", "Grace", "grace"),
+            OnConflict::columns(["email"]),
+            None,
+        )
+        .await
+        .expect("Failed to upsert")
+        .into_record()
+        .unwrap();
+
+    // A new id with the same email updates the existing row
+    let updated = store
+        .upsert(
+            member("grace@example.com", "Grace Hopper", "grace"),
+            OnConflict::columns(["email"]),
+            None,
+        )
+        .await
+        .expect("Failed to upsert")
+        .into_record()
+        .unwrap();
+    assert_eq!(updated.id, original.id);
+    assert_eq!(updated.name, "Grace Hopper");
+
+    // DO NOTHING keeps the row and emits no signal
+    received.lock().unwrap().clear();
+    let skipped = store
+        .upsert(
+            member("other@example.com", "Someone", "grace"),
+            OnConflict::columns(["handle", "team"]).do_nothing(),
+            None,
+        )
+        .await
+        .expect("Failed to upsert");
+    assert!(matches!(skipped, UpsertOutcome::Skipped));
+    assert!(received.lock().unwrap().is_empty());
+
+    // Columns without a unique constraint are rejected before hitting the database
+    let invalid = store
+        .upsert(
+            member("x@example.com", "X", "x"),
+            OnConflict::columns(["name"]),
+            None,
+        )
+        .await;
+    assert!(matches!(
+        invalid,
+        Err(StorehausError::ValidationError { .. })
+    ));
+
+    let outcomes = store
+        .upsert_many(
+            vec![
+                member("linus@example.com", "Linus", "linus"),
+                member("grace@example.com", "Rear Admiral Hopper", "grace"),
+            ],
+            OnConflict::columns(["email"]),
+        )
+        .await
+        .expect("Failed to upsert many");
+    assert!(outcomes[0].is_created());
+    assert!(outcomes[1].is_updated());
+    assert_eq!(store.count().await.unwrap(), 2);
+    assert_eq!(received.lock().unwrap().len(), 2);
+
+    cleanup(&pool).await;
+}
@@ -0,0 +1,249 @@
+//! Integration tests for optimistic concurrency with `#[version]` fields
+
+mod common;
+
+use serde_json::json;
+use sqlx::PgPool;
+use storehaus::prelude::*;
+
+#[model]
+#[table(name = "version_test_documents")]
+pub struct Document {
+    #[primary_key]
+    pub id: Uuid,
+
+    #[field(create, update)]
+    pub title: String,
+
+    #[version]
+    pub version: i64,
+}
+
+async fn setup() -> (PgPool, GenericStore<Document>) {
+    let pool = common::connect().await;
+
+    common::recreate_table::<Document>(&pool).await;
+
+    let store = GenericStore::<Document>::new(pool.clone(), None, None);
+    (pool, store)
+}
+
+async fn cleanup(pool: &PgPool) {
+    common::drop_table::<Document>(pool).await;
+}
+
+#[tokio::test]
+async fn test_stale_update_is_rejected() {
+    let (pool, store) = setup().await;
+
+    let created = store
+        .create(Document::new(Uuid::new_v4(), "Draft".to_string()), None)
+        .await
+        .expect("Failed to create document");
+    assert_eq!(created.version, 1);
+
+    // Two writers read the same version
+    let mut admin_copy = created.clone();
+    let mut worker_copy = created.clone();
+
+    admin_copy.title = "Reviewed".to_string();
+    let updated = store
+        .update(&created.id, admin_copy, None)
+        .await
+        .expect("Failed to update document");
+    assert_eq!(updated.version, 2);
+
+    worker_copy.title = "Published".to_string();
+    let result = store.update(&created.id, worker_copy, None).await;
+    match result {
+        Err(StorehausError::ConcurrencyConflict {
+            table,
+            identifier,
+            expected_version,
+        }) => {
+            assert_eq!(table, "version_test_documents");
+            assert_eq!(identifier, created.id.to_string());
+            assert_eq!(expected_version, 1);
+        }
+        other => panic!("Expected a concurrency conflict, got {:?}", other),
+    }
+
+    let stored = store.get_by_id(&created.id).await.unwrap().unwrap();
+    assert_eq!(stored.title, "Reviewed");
+    assert_eq!(stored.version, 2);
+
+    // Re-reading and retrying succeeds
+    let mut fresh = stored.clone();
+    fresh.title = "Published".to_string();
+    let published = store
+        .update(&created.id, fresh, None)
+        .await
+        .expect("Failed to update document");
+    assert_eq!(published.version, 3);
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_update_where_bumps_version() {
+    let (pool, store) = setup().await;
+
+    let created = store
+        .create(Document::new(Uuid::new_v4(), "Draft".to_string()), None)
+        .await
+        .expect("Failed to create document");
+
+    store
+        .update_where(
+            QueryBuilder::new()
+                .filter(QueryFilter::eq("id", json!(created.id)))
+                .update(UpdateSet::new().set("title", json!("Archived"))),
+            None,
+        )
+        .await
+        .expect("Failed to update documents");
+
+    // Copies read before the bulk update are stale as well
+    assert!(matches!(
+        store.update(&created.id, created.clone(), None).await,
+        Err(StorehausError::ConcurrencyConflict { .. })
+    ));
+
+    // A transaction sees the conflict too and keeps its other writes
+    let mut tx = store.begin_transaction().await.expect("Failed to begin");
+    let other = tx
+        .create(Document::new(Uuid::new_v4(), "Notes".to_string()), None)
+        .await
+        .expect("Failed to create document");
+    assert!(matches!(
+        tx.update(&created.id, created.clone(), None).await,
+        Err(StorehausError::ConcurrencyConflict { .. })
+    ));
+    tx.commit().await.expect("Failed to commit");
+
+    assert!(store.get_by_id(&other.id).await.unwrap().is_some());
+    let stored = store.get_by_id(&created.id).await.unwrap().unwrap();
+    assert_eq!(stored.title, "Archived");
+    assert_eq!(stored.version, 2);
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_stale_upsert_is_rejected() {
+    let (pool, store) = setup().await;
+
+    let created = store
+        .create(Document::new(Uuid::new_v4(), "Draft".to_string()), None)
+        .await
+        .expect("Failed to create document");
+
+    let mut current = created.clone();
+    current.title = "Reviewed".to_string();
+    let outcome = store
+        .upsert(current, OnConflict::primary_key(), None)
+        .await
+        .expect("Failed to upsert document");
+    let updated = outcome.into_record().expect("Expected an updated record");
+    assert_eq!(updated.version, 2);
+
+    // The copy read before the upsert is stale, by primary key or as a column target
+    for conflict in [OnConflict::primary_key(), OnConflict::columns(["id"])] {
+        let mut stale = created.clone();
+        stale.title = "Published".to_string();
+        match store.upsert(stale, conflict, None).await {
+            Err(StorehausError::ConcurrencyConflict {
+                identifier,
+                expected_version,
+                ..
+            }) => {
+                assert_eq!(identifier, created.id.to_string());
+                assert_eq!(expected_version, 1);
+            }
+            other => panic!("Expected a concurrency conflict, got {:?}", other),
+        }
+    }
+
+    let mut tx = store.begin_transaction().await.expect("Failed to begin");
+    assert!(matches!(
+        tx.upsert(created.clone(), OnConflict::primary_key(), None)
+            .await,
+        Err(StorehausError::ConcurrencyConflict { .. })
+    ));
+    tx.rollback().await.expect("Failed to roll back");
+
+    // DO NOTHING does not compare versions
+    let skipped = store
+        .upsert(
+            created.clone(),
+            OnConflict::primary_key().do_nothing(),
+            None,
+        )
+        .await
+        .expect("Failed to upsert document");
+    assert!(skipped.record().is_none());
+
+    let stored = store.get_by_id(&created.id).await.unwrap().unwrap();
+    assert_eq!(stored.title, "Reviewed");
+    assert_eq!(stored.version, 2);
+
+    // The current version updates as before
+    let mut fresh = stored.clone();
+    fresh.title = "Published".to_string();
+    let outcome = store
+        .upsert(fresh, OnConflict::columns(["id"]), None)
+        .await
+        .expect("Failed to upsert document");
+    assert!(outcome.is_updated());
+    assert_eq!(outcome.into_record().unwrap().version, 3);
+
+    cleanup(&pool).await;
+}
+
+#[tokio::test]
+async fn test_update_of_missing_record_is_not_found() {
+    let (pool, store) = setup().await;
+
+    let created = store
+        .create(Document::new(Uuid::new_v4(), "Draft".to_string()), None)
+        .await
+        .expect("Failed to create document");
+    let missing = Document::new(Uuid::new_v4(), "Ghost".to_string());
+
+    match store.update(&missing.id, missing.clone(), None).await {
+        Err(StorehausError::NotFound { identifier, .. }) => {
+            assert_eq!(identifier, missing.id.to_string());
+        }
+        other => panic!("Expected not found, got {:?}", other),
+    }
+
+    let mut tx = store.begin_transaction().await.expect("Failed to begin");
+    assert!(matches!(
+        tx.update(&missing.id, missing.clone(), None).await,
+        Err(StorehausError::NotFound { .. })
+    ));
+    tx.rollback().await.expect("Failed to roll back");
+
+    // A missing id in a batch is not found, a stale one a conflict
+    let mut current = created.clone();
+    current.title = "Reviewed".to_string();
+    assert!(matches!(
+        store
+            .update_many(vec![
+                (created.id, current.clone()),
+                (missing.id, missing.clone())
+            ])
+            .await,
+        Err(StorehausError::NotFound { .. })
+    ));
+    store
+        .update(&created.id, current.clone(), None)
+        .await
+        .expect("Failed to update document");
+    assert!(matches!(
+        store.update_many(vec![(created.id, current)]).await,
+        Err(StorehausError::ConcurrencyConflict { .. })
+    ));
+
+    cleanup(&pool).await;
+}
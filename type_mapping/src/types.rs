@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PostgresValue {
     Text(String),
     Integer(i32),
@@ -61,6 +61,24 @@ impl From<i16> for PostgresValue {
     }
 }
 
+impl From<f64> for PostgresValue {
+    fn from(val: f64) -> Self {
+        PostgresValue::Float(val)
+    }
+}
+
+impl From<f32> for PostgresValue {
+    fn from(val: f32) -> Self {
+        PostgresValue::Float(val as f64)
+    }
+}
+
+impl From<rust_decimal::Decimal> for PostgresValue {
+    fn from(val: rust_decimal::Decimal) -> Self {
+        PostgresValue::Decimal(val.to_string())
+    }
+}
+
 impl From<bool> for PostgresValue {
     fn from(val: bool) -> Self {
         PostgresValue::Boolean(val)
@@ -79,12 +97,34 @@ impl From<chrono::DateTime<chrono::Utc>> for PostgresValue {
     }
 }
 
+impl From<chrono::NaiveDateTime> for PostgresValue {
+    fn from(val: chrono::NaiveDateTime) -> Self {
+        PostgresValue::Timestamp(val.and_utc())
+    }
+}
+
+/// Dates are carried as `YYYY-MM-DD` text and bound as DATE by column type
+impl From<chrono::NaiveDate> for PostgresValue {
+    fn from(val: chrono::NaiveDate) -> Self {
+        PostgresValue::Text(val.format("%Y-%m-%d").to_string())
+    }
+}
+
 impl From<serde_json::Value> for PostgresValue {
     fn from(val: serde_json::Value) -> Self {
         PostgresValue::Json(val)
     }
 }
 
+impl<T> From<Vec<T>> for PostgresValue
+where
+    T: Into<PostgresValue>,
+{
+    fn from(val: Vec<T>) -> Self {
+        PostgresValue::Array(val.into_iter().map(Into::into).collect())
+    }
+}
+
 impl<T> From<Option<T>> for PostgresValue
 where
     T: Into<PostgresValue>,
@@ -96,3 +136,33 @@ where
         }
     }
 }
+
+impl PostgresValue {
+    /// JSON representation of the value
+    ///
+    /// Uuids and timestamps become strings, decimals keep their exact digits
+    /// as a string, and records become objects.
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value;
+
+        match self {
+            PostgresValue::Text(s) | PostgresValue::Decimal(s) => Value::String(s.clone()),
+            PostgresValue::Integer(i) => Value::from(*i),
+            PostgresValue::BigInt(i) => Value::from(*i),
+            PostgresValue::SmallInt(i) => Value::from(*i),
+            PostgresValue::Float(f) => Value::from(*f),
+            PostgresValue::Boolean(b) => Value::Bool(*b),
+            PostgresValue::Uuid(u) => Value::String(u.to_string()),
+            PostgresValue::Timestamp(ts) => Value::String(ts.to_rfc3339()),
+            PostgresValue::Json(v) => v.clone(),
+            PostgresValue::Array(items) => Value::Array(items.iter().map(Self::to_json).collect()),
+            PostgresValue::Record(fields) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.to_json()))
+                    .collect(),
+            ),
+            PostgresValue::Null => Value::Null,
+        }
+    }
+}